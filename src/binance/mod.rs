@@ -1,30 +1,391 @@
-use std::collections::BTreeMap;
+use std::collections::{ BTreeMap, HashMap };
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
 use async_trait::async_trait;
-use serde_json::{ from_slice, Value };
-use http::{ header::{ ACCEPT, CONTENT_TYPE }, HeaderName, Request };
+use futures::stream::{ self, BoxStream, StreamExt };
+use serde::Serialize;
+use serde_json::{ json, Value };
+use http::{ header::{ ACCEPT, CONTENT_TYPE }, HeaderName, Request, Version };
 use sha2::Sha256;
-use hmac::{ Hmac, Mac };
+use base64::{ Engine as _, engine::general_purpose };
+use ed25519_dalek::{ pkcs8::DecodePrivateKey, Signer as _, SigningKey };
+use rsa::{ pkcs1::DecodeRsaPrivateKey, pkcs1v15, signature::SignatureEncoding, RsaPrivateKey };
+use crate::stream::WsManager;
+use crate::http_util::{ build_request, build_request_with_body };
+use crate::sign::hmac_hex;
 use crate::{
-    get_current_timestamp_in_millis,
+    cancel_each,
+    canonicalize_params,
+    check_min_notional,
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    check_trigger_price,
+    encode_params,
+    fetch_json_retrying,
+    filter_transfers,
+    get_client_order_id,
+    get_number_string,
+    get_optional_str,
+    get_order_id_param,
     get_query_string,
-    send,
+    get_request_timestamp,
+    get_time_in_force,
+    parse_datetime_millis,
+    parse_json_body,
+    parse_response,
+    replacement_failed,
+    resolve_symbol,
+    split_symbol,
+    supports_method,
+    top_by_volume,
+    with_request_timeout,
+    AccountInfo,
+    Balance,
+    Bbo,
+    Capabilities,
+    Clock,
     CoinList,
+    CoinListCache,
+    DepositAddress,
     Exchange,
+    ExchangeError,
+    FeeSchedule,
+    HttpClient,
+    Market,
+    Order,
     OrderBook,
     OrderBookUnit,
+    OrderState,
     Price,
+    PriceSource,
+    Quote,
+    Side,
+    SymbolInfo,
+    SymbolInfoCache,
+    Ticker,
+    TimeInForce,
+    Trade,
+    Transfer,
+    TransferKind,
+    TransferStatus,
+    TriggerType,
+    Wallet,
+    WithdrawalFee,
 };
 
+// Optional `Exchange` methods implemented here, reported by `supports`
+const IMPLEMENTED_METHODS: &[&str] = &[
+    "amend_order",
+    "cancel_all_orders",
+    "cancel_order_typed",
+    "check_auth",
+    "get_account_info",
+    "get_all_balances",
+    "get_best_quote",
+    "get_deposit_address",
+    "get_full_order_book",
+    "get_mark_price",
+    "get_order",
+    "get_recent_trades",
+    "get_ticker",
+    "get_trading_fees",
+    "get_transfer_history",
+    "get_withdrawal_fee",
+    "ping",
+    "place_conditional_order",
+    "place_order_typed",
+    "stream_bbo",
+    "stream_user_orders",
+    "symbol_info",
+    "top_markets_by_volume",
+    "withdraw",
+];
+
+// Documented alternatives to api.binance.com, tried in order when it is unavailable
+const FALLBACK_API_URLS: [&str; 4] = [
+    "https://api1.binance.com/",
+    "https://api2.binance.com/",
+    "https://api3.binance.com/",
+    "https://api4.binance.com/",
+];
+
+// Listen keys expire 60 minutes after they were created or last kept alive
+const LISTEN_KEY_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+// The listen key calls of a user data stream
+#[async_trait]
+pub(crate) trait ListenKeyApi: Send + Sync {
+    // Returns the account's active key when it has one, extending its validity
+    async fn create(&self) -> Result<String, ExchangeError>;
+    async fn keepalive(&self, listen_key: &str) -> Result<(), ExchangeError>;
+    async fn close(&self, listen_key: &str) -> Result<(), ExchangeError>;
+}
+
+// Owns what it needs, so a stream can renew its key after the `Binance` that opened it is gone
+struct ListenKeys {
+    client: HttpClient,
+    url: String,
+    api_key: String,
+}
+
+impl ListenKeys {
+    async fn send(&self, method: &str, listen_key: Option<&str>) -> Result<Value, ExchangeError> {
+        let uri = match listen_key {
+            Some(listen_key) => format!("{}?listenKey={}", self.url, listen_key),
+            None => self.url.clone(),
+        };
+        let request = build_request(
+            method,
+            &uri,
+            vec![(HeaderName::from_static("x-mbx-apikey"), self.api_key.as_str())],
+            BTreeMap::<&str, &str>::new()
+        )?;
+
+        let res = parse_response(self.client.send(request).await?, check_response)?;
+        check_response(&res)?;
+        Ok(res)
+    }
+}
+
+#[async_trait]
+impl ListenKeyApi for ListenKeys {
+    async fn create(&self) -> Result<String, ExchangeError> {
+        let res = self.send("POST", None).await?;
+        res["listenKey"]
+            .as_str()
+            .map(|listen_key| listen_key.to_string())
+            .ok_or(ExchangeError::Parse(format!("listenKey field is missing: {}", res)))
+    }
+
+    async fn keepalive(&self, listen_key: &str) -> Result<(), ExchangeError> {
+        self.send("PUT", Some(listen_key)).await.map(|_| ())
+    }
+
+    async fn close(&self, listen_key: &str) -> Result<(), ExchangeError> {
+        self.send("DELETE", Some(listen_key)).await.map(|_| ())
+    }
+}
+
+// Keeps a listen key alive every `interval` until dropped, then closes the key. Failed
+// keepalives are sent to `errors`.
+pub(crate) struct Keepalive {
+    api: Arc<dyn ListenKeyApi>,
+    listen_key: String,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Keepalive {
+    pub(crate) fn spawn(
+        api: Arc<dyn ListenKeyApi>,
+        listen_key: &str,
+        interval: Duration,
+        errors: mpsc::UnboundedSender<ExchangeError>
+    ) -> Self {
+        let task = {
+            let api = api.clone();
+            let listen_key = listen_key.to_string();
+            tokio::spawn(async move {
+                let mut ticks = tokio::time::interval_at(Instant::now() + interval, interval);
+                loop {
+                    ticks.tick().await;
+                    if let Err(e) = api.keepalive(&listen_key).await {
+                        if errors.send(e).is_err() {
+                            return;
+                        }
+                    }
+                }
+            })
+        };
+        Keepalive { api, listen_key: listen_key.to_string(), task }
+    }
+}
+
+impl Drop for Keepalive {
+    fn drop(&mut self) {
+        self.task.abort();
+        // Best effort: without a runtime the key is left to expire
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let (api, listen_key) = (self.api.clone(), self.listen_key.clone());
+            runtime.spawn(async move {
+                let _ = api.close(&listen_key).await;
+            });
+        }
+    }
+}
+
+// A user data stream, reconnected under a new listen key when Binance expires the old one
+struct UserStream {
+    listen_keys: Arc<dyn ListenKeyApi>,
+    ws_url: String,
+    max_reconnect_attempts: Option<u32>,
+    keepalive_interval: Duration,
+    messages: BoxStream<'static, Result<Value, ExchangeError>>,
+    keepalive: Keepalive,
+    keepalive_errors: (
+        mpsc::UnboundedSender<ExchangeError>,
+        mpsc::UnboundedReceiver<ExchangeError>,
+    ),
+    // Exchange symbols such as "BTCUSDT" to "BTC/USDT"
+    symbols: HashMap<String, String>,
+}
+
+impl UserStream {
+    async fn connect(
+        listen_keys: &Arc<dyn ListenKeyApi>,
+        ws_url: &str,
+        max_reconnect_attempts: Option<u32>
+    ) -> Result<(String, BoxStream<'static, Result<Value, ExchangeError>>), ExchangeError> {
+        let listen_key = listen_keys.create().await?;
+        let messages = WsManager::new(&format!("{}{}", ws_url, listen_key))
+            .with_max_reconnect_attempts(max_reconnect_attempts)
+            .subscribe().await?;
+        Ok((listen_key, messages))
+    }
+
+    async fn next_order(mut self) -> Option<(Result<Order, ExchangeError>, UserStream)> {
+        loop {
+            let message = tokio::select! {
+                message = self.messages.next() => message?,
+                Some(e) = self.keepalive_errors.1.recv() => Err(e),
+            };
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => {
+                    return Some((Err(e), self));
+                }
+            };
+            if message["e"] == "listenKeyExpired" {
+                // Closes the expired connection before opening the next
+                self.messages = stream::empty().boxed();
+                match
+                    UserStream::connect(
+                        &self.listen_keys,
+                        &self.ws_url,
+                        self.max_reconnect_attempts
+                    ).await
+                {
+                    Ok((listen_key, messages)) => {
+                        self.messages = messages;
+                        self.keepalive = Keepalive::spawn(
+                            self.listen_keys.clone(),
+                            &listen_key,
+                            self.keepalive_interval,
+                            self.keepalive_errors.0.clone()
+                        );
+                    }
+                    Err(e) => {
+                        return Some((Err(e), self));
+                    }
+                }
+                continue;
+            }
+            if let Some(order) = parse_execution_report(&message, &self.symbols).transpose() {
+                return Some((order, self));
+            }
+        }
+    }
+}
+
+// Request weight the spot api allows per minute and per IP
+const REQUEST_WEIGHT_BUDGET: u32 = 6000;
+
+// Binance meters the spot api by request weight per clock minute. Usage is counted locally
+// and replaced by the exchange's own count whenever a response reports it.
+struct WeightLimiter {
+    budget: u32,
+    // (minute since the epoch, weight used in it)
+    window: Mutex<(u64, u32)>,
+}
+
+impl WeightLimiter {
+    fn new(budget: u32) -> Self {
+        WeightLimiter { budget, window: Mutex::new((0, 0)) }
+    }
+
+    // Waits for the next minute when `weight` would exceed the budget. A request heavier than
+    // the whole budget still goes out on a fresh minute.
+    async fn acquire(&self, weight: u32, clock: &Arc<dyn Clock>) {
+        loop {
+            let wait = {
+                let now = clock.now_millis();
+                let minute = now / 60_000;
+                let mut window = self.window.lock().unwrap();
+                if window.0 != minute {
+                    *window = (minute, 0);
+                }
+                if window.1 + weight <= self.budget || window.1 == 0 {
+                    window.1 += weight;
+                    return;
+                }
+                Duration::from_millis((minute + 1) * 60_000 - now)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn observe(&self, used_weight: u32, clock: &Arc<dyn Clock>) {
+        let minute = clock.now_millis() / 60_000;
+        *self.window.lock().unwrap() = (minute, used_weight);
+    }
+
+    fn used_weight(&self, clock: &Arc<dyn Clock>) -> u32 {
+        let minute = clock.now_millis() / 60_000;
+        match *self.window.lock().unwrap() {
+            (window, used_weight) if window == minute => used_weight,
+            _ => 0,
+        }
+    }
+}
+
+// The secret half of an API key; Ed25519 keys are the PKCS#8 DER private key Binance was given
+// the public half of, RSA keys the PEM private key in either PKCS#8 or PKCS#1 form
+#[derive(Clone)]
+pub enum BinanceKey {
+    Hmac(String),
+    Ed25519(Vec<u8>),
+    Rsa(String),
+}
+
+impl From<String> for BinanceKey {
+    fn from(secret: String) -> Self {
+        BinanceKey::Hmac(secret)
+    }
+}
+
+// A key parsed once at construction, so a bad Ed25519 key fails `new` rather than every request
+enum RequestSigner {
+    Hmac(String),
+    Ed25519(SigningKey),
+    Rsa(pkcs1v15::SigningKey<Sha256>),
+}
+
 pub struct Binance {
     api_url: String,
+    // USDⓈ-M futures live on their own host, which the spot fallback cluster does not serve
+    futures_api_url: String,
+    futures_client: HttpClient,
+    ws_url: String,
+    max_reconnect_attempts: Option<u32>,
+    listen_key_keepalive_interval: Duration,
     api_key: String,
-    secret: String,
-    endpoint: BTreeMap<String, [String; 2]>,
+    signer: RequestSigner,
+    pub(crate) endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    weight_limiter: WeightLimiter,
+    symbol_info_cache: SymbolInfoCache,
+    auto_client_order_id: bool,
+    min_notional_check: bool,
 }
 
 #[allow(dead_code)]
 pub trait BinanceTrait {
-    fn new(api_key: String, secret: String) -> Result<Self, String> where Self: Sized;
+    fn new(api_key: String, secret: impl Into<BinanceKey>) -> Result<Self, String> where Self: Sized;
     fn get_api_url(&self) -> &str;
     fn get_end_point(&self) -> &BTreeMap<String, [String; 2]>;
     fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]>;
@@ -32,63 +393,423 @@ pub trait BinanceTrait {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> impl std::future::Future<Output = Result<Value, String>> + Send;
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
 }
 
+impl_endpoint_overrides!(Binance);
+impl_client_settings!(Binance, client, futures_client);
+impl_exchange_options!(Binance);
+impl_auto_client_order_id!(Binance);
+
 impl Binance {
-    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
-        if api_key.is_empty() || secret.is_empty() {
-            return Err("API key and Secret cannot be empty".to_string());
+    // A single custom host replaces the whole default cluster, fallbacks included
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self.client = self.client.with_fallback_urls(&[]);
+        self
+    }
+
+    pub fn with_futures_api_url(mut self, futures_api_url: &str) -> Self {
+        self.futures_api_url = futures_api_url.to_string();
+        self
+    }
+
+    pub fn with_ws_url(mut self, ws_url: &str) -> Self {
+        self.ws_url = ws_url.to_string();
+        self
+    }
+
+    // Streams end with an error after this many consecutive failed reconnects
+    pub fn with_max_reconnect_attempts(mut self, max_reconnect_attempts: u32) -> Self {
+        self.max_reconnect_attempts = Some(max_reconnect_attempts);
+        self
+    }
+
+    // How often an open user data stream keeps its listen key alive
+    pub fn with_listen_key_keepalive_interval(mut self, interval: Duration) -> Self {
+        self.listen_key_keepalive_interval = interval;
+        self
+    }
+
+    // Replaces the default cache (one hour TTL), e.g. to share one across handles
+    pub fn with_symbol_info_cache(mut self, symbol_info_cache: SymbolInfoCache) -> Self {
+        self.symbol_info_cache = symbol_info_cache;
+        self
+    }
+
+    // place_order_typed checks the order value against min_order_notional before sending it
+    pub fn with_min_notional_check(mut self) -> Self {
+        self.min_notional_check = true;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.futures_client = self.futures_client.with_clock(clock.clone());
+        self.client = self.client.with_clock(clock);
+        self
+    }
+
+    // The API hosts speak HTTP/2, which lets concurrent requests share one connection
+    pub fn with_http2_prior_knowledge(mut self, http2_prior_knowledge: bool) -> Self {
+        self.client = self.client.with_http2_prior_knowledge(http2_prior_knowledge);
+        self.futures_client = self.futures_client.with_http2_prior_knowledge(http2_prior_knowledge);
+        self
+    }
+
+    pub fn negotiated_http_version(&self) -> Option<Version> {
+        self.client.negotiated_version()
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
         }
-        Ok(())
+        self
+    }
+
+    // Request weight used in the current minute, as last counted or reported by Binance
+    pub fn used_weight(&self) -> u32 {
+        self.weight_limiter.used_weight(self.client.clock())
+    }
+
+    // Sends a spot api request once its weight fits in the minute's budget
+    async fn send_weighted<B: Serialize>(
+        &self,
+        request: Request<B>,
+        weight: u32
+    ) -> Result<http::Response<Vec<u8>>, ExchangeError> {
+        self.weight_limiter.acquire(weight, self.client.clock()).await;
+        let response = self.client.send(request).await?;
+
+        let used_weight = response
+            .headers()
+            .get("X-MBX-USED-WEIGHT-1M")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u32>().ok());
+        if let Some(used_weight) = used_weight {
+            self.weight_limiter.observe(used_weight, self.client.clock());
+        }
+        Ok(response)
+    }
+
+    // Unsigned GET with the params in the query string
+    async fn send_public_req(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, String> {
+        let weight = get_request_weight(endpoint_key, &param);
+        let request = self.build_public_request(&self.api_url, param, endpoint_key)?;
+
+        let response = self.send_weighted(request, weight).await.map_err(|e| e.to_string())?;
+        Ok(parse_response(response, check_response)?)
+    }
+
+    // The futures host has a budget of its own, which is not tracked
+    async fn send_public_req_to(
+        &self,
+        client: &HttpClient,
+        api_url: &str,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, String> {
+        let request = self.build_public_request(api_url, param, endpoint_key)?;
+
+        let response = client.send(request).await.map_err(|e| e.to_string())?;
+        Ok(parse_response(response, check_response)?)
+    }
+
+    fn build_public_request(
+        &self,
+        api_url: &str,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Request<BTreeMap<&str, &str>>, String> {
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        let mut uri = format!("{}{}", api_url, base[1]);
+        if !param.is_empty() {
+            uri = format!("{}?{}", uri, get_query_string(param));
+        }
+        Ok(build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?)
     }
 
-    fn create_hmac_key(&self) -> Result<Hmac<Sha256>, String> {
-        Hmac::new_from_slice(self.secret.as_bytes()).map_err(|e| e.to_string())
+    fn validate_api_credentials(api_key: &str, secret: BinanceKey) -> Result<RequestSigner, String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        match secret {
+            BinanceKey::Hmac(secret) if secret.is_empty() => Err("Secret cannot be empty".to_string()),
+            BinanceKey::Hmac(secret) => Ok(RequestSigner::Hmac(secret)),
+            BinanceKey::Ed25519(der) =>
+                SigningKey::from_pkcs8_der(&der)
+                    .map(RequestSigner::Ed25519)
+                    .map_err(|e| format!("Invalid Ed25519 key: {}", e)),
+            BinanceKey::Rsa(pem) =>
+                RsaPrivateKey::from_pkcs8_pem(&pem)
+                    .or_else(|_| RsaPrivateKey::from_pkcs1_pem(&pem))
+                    .map(|key| RequestSigner::Rsa(pkcs1v15::SigningKey::new(key)))
+                    .map_err(|e| format!("Invalid RSA key: {}", e)),
+        }
     }
 
-    fn build_request<'a>(
-        &'a self,
-        method: &str,
-        uri: &str,
-        headers: Vec<(HeaderName, &str)>,
-        body: BTreeMap<&'a str, &'a str>
-    ) -> Result<Request<BTreeMap<&str, &'a str>>, String> {
-        let mut builder = Request::builder().method(method).uri(uri);
-        for (key, value) in headers {
-            builder = builder.header(key, value);
+    // Signs the params as `encode_params` sends them, so they must already be canonical
+    // HMAC signatures go out hex encoded, Ed25519 and RSA ones base64 encoded
+    pub(crate) fn get_signature(&self, params: &[(String, String)]) -> Result<String, String> {
+        let payload = encode_params(params);
+        match &self.signer {
+            RequestSigner::Hmac(secret) => hmac_hex::<Sha256>(secret, &payload),
+            RequestSigner::Ed25519(key) => {
+                Ok(general_purpose::STANDARD.encode(key.sign(payload.as_bytes()).to_bytes()))
+            }
+            RequestSigner::Rsa(key) => {
+                Ok(general_purpose::STANDARD.encode(key.sign(payload.as_bytes()).to_bytes()))
+            }
+        }
+    }
+
+    // Returns error bodies as they are, for endpoints whose errors carry more than a code
+    async fn send_req_with_sign_unchecked(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, ExchangeError> {
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        // The signature covers the params in the exact order they are sent, and goes last
+        let weight = get_request_weight(endpoint_key, &param);
+        let mut params = canonicalize_params(param);
+        let signature = self.get_signature(&params)?;
+        params.push(("signature".to_string(), signature));
+
+        // Signed GET endpoints only accept their parameters in the query string
+        let (uri, body) = if base[0] == "GET" {
+            (format!("{}{}?{}", self.api_url, base[1], encode_params(&params)), Vec::new())
+        } else {
+            (format!("{}{}", self.api_url, base[1]), params)
+        };
+
+        // A form body of ordered pairs keeps that order on the wire
+        let request = build_request_with_body(
+            base[0].as_str(),
+            &uri,
+            vec![
+                (CONTENT_TYPE, "application/x-www-form-urlencoded"),
+                (HeaderName::from_static("x-mbx-apikey"), self.api_key.as_str())
+            ],
+            body
+        )?;
+
+        let response = self.send_weighted(request, weight).await?;
+        parse_response(response, check_response)
+    }
+
+    // Orders are placed with the RESULT response type, which carries the order's state
+    async fn send_order(&self, req: &Value) -> Result<Value, ExchangeError> {
+        let order_type = req["order_type"].as_str().unwrap_or_default();
+        let quote_order_qty = get_optional_str(&req["quote_amount"]).filter(|_| {
+            order_type.eq_ignore_ascii_case("MARKET")
+        });
+        if quote_order_qty.is_none() {
+            check_quote_amount(req)?;
+        }
+
+        // Post-only orders are their own LIMIT_MAKER type, which takes no timeInForce. Plain
+        // limit orders must carry one.
+        let (time_in_force, post_only) = get_time_in_force(req)?;
+        let order_type = match (post_only, order_type.eq_ignore_ascii_case("LIMIT")) {
+            (true, true) => "LIMIT_MAKER",
+            (true, false) => {
+                return Err(ExchangeError::InvalidRequest(
+                    "A post-only order must be a LIMIT order".to_string()
+                ));
+            }
+            (false, _) => order_type,
+        };
+        let time_in_force = match time_in_force {
+            _ if post_only => None,
+            None if order_type.eq_ignore_ascii_case("LIMIT") => Some(TimeInForce::Gtc),
+            time_in_force => time_in_force,
+        };
+
+        let timestamp_ = get_request_timestamp(req, self.client.clock());
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+        let mut params = BTreeMap::from([
+            ("symbol", symbol.as_str()),
+            ("side", req["side"].as_str().unwrap_or_default()),
+            ("type", order_type),
+            ("timestamp", &timestamp_),
+            ("newOrderRespType", "RESULT"),
+        ]);
+        if let Some(time_in_force) = time_in_force {
+            params.insert("timeInForce", time_in_force.as_str());
+        }
+        match &quote_order_qty {
+            // A market order sized in the quote currency, e.g. "buy 100 USDT of BTC"
+            Some(quote_order_qty) => {
+                if get_optional_str(&req["amount"]).is_some() {
+                    return Err(ExchangeError::InvalidRequest(
+                        "A market order takes either amount or quote_amount, not both".to_string()
+                    ));
+                }
+                params.insert("quoteOrderQty", quote_order_qty);
+            }
+            None => {
+                params.insert("price", req["price"].as_str().unwrap_or_default());
+                params.insert("quantity", req["amount"].as_str().unwrap_or_default());
+            }
+        }
+        let client_order_id = get_client_order_id(req, self.auto_client_order_id);
+        if let Some(client_order_id) = &client_order_id {
+            params.insert("newClientOrderId", client_order_id);
         }
-        builder.body(body).map_err(|e| e.to_string())
+
+        self.send_req_with_sign(params, "make_order").await
     }
 
-    fn get_signature(&self, params: &BTreeMap<&str, &str>) -> Result<String, String> {
-        let query_string = get_query_string(params.clone());
-        let mut mac = self.create_hmac_key()?;
-        mac.update(query_string.as_bytes());
+    async fn send_cancel(&self, req: &Value) -> Result<Value, ExchangeError> {
+        let timestamp_ = get_request_timestamp(req, self.client.clock());
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+        let (id_key, id) = get_order_id_param(req, "orderId", "origClientOrderId")?;
+        let params = BTreeMap::from([
+            ("symbol", symbol.as_str()),
+            (id_key, id),
+            ("timestamp", &timestamp_),
+        ]);
+
+        self.send_req_with_sign(params, "cancel_order").await
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let params = BTreeMap::from([("permissions", "SPOT")]);
+
+        let weight = get_request_weight("coin_list", &params);
+        let query_string = get_query_string(params);
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.send_weighted(request, weight).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
+
+        // Parsing response to create CoinList struct. Pairs that are not trading, e.g. delisted
+        // ones, and malformed entries without both assets are left out.
+        let market = "Binance".to_string();
+        let coin_list = res["symbols"]
+            .as_array()
+            .ok_or("Response is not an array".to_string())?
+            .iter()
+            .filter(|coin| coin["status"].as_str() == Some("TRADING"))
+            .filter_map(|coin| {
+                Some(format!("{}/{}", coin["baseAsset"].as_str()?, coin["quoteAsset"].as_str()?))
+            })
+            .collect::<Vec<String>>();
 
-        let result = mac.finalize();
-        let hmac_bytes = result.into_bytes();
-        Ok(hex::encode(hmac_bytes))
+        let coin_list_struct = CoinList {
+            market,
+            coin_list,
+        };
+
+        Ok(coin_list_struct)
     }
 }
 
 impl BinanceTrait for Binance {
-    fn new(api_key: String, secret: String) -> Result<Self, String> {
-        Binance::validate_api_credentials(&api_key, &secret)?;
+    fn new(api_key: String, secret: impl Into<BinanceKey>) -> Result<Self, String> {
+        let signer = Binance::validate_api_credentials(&api_key, secret.into())?;
 
         let endpoint = BTreeMap::from([
             ("make_order".to_string(), ["POST".to_string(), "api/v3/order".to_string()]),
             ("cancel_order".to_string(), ["DELETE".to_string(), "api/v3/order".to_string()]),
+            ("get_order".to_string(), ["GET".to_string(), "api/v3/order".to_string()]),
             ("order_book".to_string(), ["GET".to_string(), "api/v3/depth".to_string()]),
             ("current_price".to_string(), ["GET".to_string(), "api/v3/ticker/price".to_string()]),
             ("coin_list".to_string(), ["GET".to_string(), "api/v3/exchangeInfo".to_string()]),
+            ("tickers".to_string(), ["GET".to_string(), "api/v3/ticker/24hr".to_string()]),
+            ("book_ticker".to_string(), ["GET".to_string(), "api/v3/ticker/bookTicker".to_string()]),
+            ("recent_trades".to_string(), ["GET".to_string(), "api/v3/trades".to_string()]),
+            ("ping".to_string(), ["GET".to_string(), "api/v3/ping".to_string()]),
+            (
+                "user_data_stream".to_string(),
+                ["POST".to_string(), "api/v3/userDataStream".to_string()],
+            ),
+            ("mark_price".to_string(), ["GET".to_string(), "fapi/v1/premiumIndex".to_string()]),
+            (
+                "cancel_replace".to_string(),
+                ["POST".to_string(), "api/v3/order/cancelReplace".to_string()],
+            ),
+            ("open_orders".to_string(), ["GET".to_string(), "api/v3/openOrders".to_string()]),
+            ("account".to_string(), ["GET".to_string(), "api/v3/account".to_string()]),
+            (
+                "funding_balance".to_string(),
+                ["POST".to_string(), "sapi/v1/asset/get-funding-asset".to_string()],
+            ),
+            ("margin_account".to_string(), ["GET".to_string(), "sapi/v1/margin/account".to_string()]),
+            (
+                "cancel_all_orders".to_string(),
+                ["DELETE".to_string(), "api/v3/openOrders".to_string()],
+            ),
+            (
+                "deposit_address".to_string(),
+                ["GET".to_string(), "sapi/v1/capital/deposit/address".to_string()],
+            ),
+            ("trade_fee".to_string(), ["GET".to_string(), "sapi/v1/asset/tradeFee".to_string()]),
+            (
+                "withdrawal_config".to_string(),
+                ["GET".to_string(), "sapi/v1/capital/config/getall".to_string()],
+            ),
+            (
+                "withdraw".to_string(),
+                ["POST".to_string(), "sapi/v1/capital/withdraw/apply".to_string()],
+            ),
+            (
+                "deposit_history".to_string(),
+                ["GET".to_string(), "sapi/v1/capital/deposit/hisrec".to_string()],
+            ),
+            (
+                "withdrawal_history".to_string(),
+                ["GET".to_string(), "sapi/v1/capital/withdraw/history".to_string()],
+            ),
         ]);
 
         Ok(Self {
-            api_url: "https://api1.binance.com/".to_string(),
+            api_url: "https://api.binance.com/".to_string(),
+            futures_api_url: "https://fapi.binance.com/".to_string(),
+            futures_client: HttpClient::new(),
+            ws_url: "wss://stream.binance.com:9443/ws/".to_string(),
+            max_reconnect_attempts: None,
+            listen_key_keepalive_interval: LISTEN_KEY_KEEPALIVE_INTERVAL,
             api_key,
-            secret,
+            signer,
             endpoint,
+            client: HttpClient::new().with_fallback_urls(&FALLBACK_API_URLS),
+            coin_list_cache: CoinListCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            weight_limiter: WeightLimiter::new(REQUEST_WEIGHT_BUDGET),
+            symbol_info_cache: SymbolInfoCache::default(),
+            auto_client_order_id: false,
+            min_notional_check: false,
         })
     }
 
@@ -106,110 +827,211 @@ impl BinanceTrait for Binance {
 
     async fn send_req_with_sign(
         &self,
-        mut param: BTreeMap<&str, &str>,
+        param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> Result<Value, String> {
-        let base = self
-            .get_end_point_with_key(endpoint_key)
-            .ok_or("Endpoint not found".to_string())?;
-
-        let uri = format!("{}{}", self.api_url, base[1]);
-        let signature = self.get_signature(&param)?;
-
-        param.insert("signature", &signature);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![
-                (CONTENT_TYPE, "application/x-www-form-urlencoded"),
-                ("X-MBX-APIKEY".try_into().unwrap(), self.api_key.as_str())
-            ],
-            param
-        )?;
-
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        from_slice(&body).map_err(|e| e.to_string())
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let res = self.send_req_with_sign_unchecked(param, endpoint_key).await?;
+        check_response(&res)?;
+        Ok(res)
     }
 }
 
 #[async_trait]
 impl Exchange for Binance {
     async fn place_order(&self, req: Value) -> Result<Value, String> {
-        let timestamp_ = get_current_timestamp_in_millis().to_string();
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default());
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, self.send_order(&req)).await.map_err(|e| e.to_string())
+    }
+
+    async fn place_order_typed(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order_typed")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        if self.min_notional_check {
+            let symbol = req["symbol"].as_str().unwrap_or_default();
+            check_min_notional(&req, self.min_order_notional(symbol).await?)?;
+        }
+        let res = with_request_timeout(&req, self.send_order(&req)).await?;
+        parse_order(&res, req["symbol"].as_str().unwrap_or_default())
+    }
+
+    async fn place_conditional_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_conditional_order")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            let symbol = req["symbol"].as_str().unwrap_or_default();
+            let current = self.get_current_price(json!({ "symbol": symbol })).await?;
+            let trigger_type = check_trigger_price(&req, &current.price)?;
+            let limit_price = get_optional_str(&req["price"]);
+            let order_type = match (trigger_type, limit_price.is_some()) {
+                (TriggerType::StopLoss, false) => "STOP_LOSS",
+                (TriggerType::StopLoss, true) => "STOP_LOSS_LIMIT",
+                (TriggerType::TakeProfit, false) => "TAKE_PROFIT",
+                (TriggerType::TakeProfit, true) => "TAKE_PROFIT_LIMIT",
+            };
+
+            let timestamp_ = get_request_timestamp(&req, self.client.clock());
+            let market = parse_symbol(symbol)?;
+            let mut params = BTreeMap::from([
+                ("symbol", market.as_str()),
+                ("side", req["side"].as_str().unwrap_or_default()),
+                ("type", order_type),
+                ("quantity", req["amount"].as_str().unwrap_or_default()),
+                ("stopPrice", req["trigger_price"].as_str().unwrap_or_default()),
+                ("timestamp", &timestamp_),
+                ("newOrderRespType", "RESULT"),
+            ]);
+            if let Some(price) = &limit_price {
+                params.insert("price", price);
+                params.insert("timeInForce", "GTC");
+            }
+            let client_order_id = get_client_order_id(&req, self.auto_client_order_id);
+            if let Some(client_order_id) = &client_order_id {
+                params.insert("newClientOrderId", client_order_id);
+            }
+
+            let res = self.send_req_with_sign(params, "make_order").await?;
+            parse_order(&res, symbol)
+        }).await
+    }
+
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, self.send_cancel(&req)).await.map_err(|e| e.to_string())
+    }
+
+    // The cancel response carries the order as it was canceled, with its executed quantity
+    async fn cancel_order_typed(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order_typed")?;
+        let res = with_request_timeout(&req, self.send_cancel(&req)).await?;
+        parse_cancel_order(&res, req["symbol"].as_str().unwrap_or_default())
+    }
+
+    async fn get_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        let timestamp_ = get_request_timestamp(&req, self.client.clock());
+        let symbol = req["symbol"].as_str().unwrap_or_default();
+        let market = parse_symbol(symbol)?;
+        let (id_key, id) = get_order_id_param(&req, "orderId", "origClientOrderId")?;
         let params = BTreeMap::from([
-            ("symbol", symbol.as_str()),
-            ("side", req["side"].as_str().unwrap_or_default()),
-            ("type", req["order_type"].as_str().unwrap_or_default()),
-            ("price", req["price"].as_str().unwrap_or_default()),
-            ("quantity", req["amount"].as_str().unwrap_or_default()),
+            ("symbol", market.as_str()),
+            (id_key, id),
             ("timestamp", &timestamp_),
-            ("newOrderRespType", "RESULT"),
         ]);
 
-        self.send_req_with_sign(params, "make_order").await
+        let res = self.send_req_with_sign(params, "get_order").await?;
+        parse_order(&res, symbol)
     }
 
-    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
-        let timestamp_ = get_current_timestamp_in_millis().to_string();
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
+    async fn amend_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "amend_order")?;
+        let timestamp_ = get_request_timestamp(&req, self.client.clock());
+        let symbol = req["symbol"].as_str().unwrap_or_default();
+        let market = parse_symbol(symbol)?;
+        let (id_key, id) = get_order_id_param(&req, "cancelOrderId", "cancelOrigClientOrderId")?;
+        // The replacement keeps the original order's time in force unless the request sets
+        // one, so an IOC or FOK order does not come back as GTC
+        let time_in_force = match get_time_in_force(&req)?.0 {
+            Some(time_in_force) => time_in_force.as_str().to_string(),
+            None => {
+                let original = self.get_order(req.clone()).await?;
+                original.raw["timeInForce"].as_str().unwrap_or("GTC").to_string()
+            }
+        };
         let params = BTreeMap::from([
-            ("symbol", symbol.as_str()),
-            ("orderId", req["order_id"].as_str().unwrap_or_default()),
+            ("symbol", market.as_str()),
+            ("side", req["side"].as_str().unwrap_or_default()),
+            ("type", req["order_type"].as_str().unwrap_or_default()),
+            ("timeInForce", time_in_force.as_str()),
+            ("price", req["price"].as_str().unwrap_or_default()),
+            ("quantity", req["amount"].as_str().unwrap_or_default()),
+            ("cancelReplaceMode", "STOP_ON_FAILURE"),
+            (id_key, id),
             ("timestamp", &timestamp_),
+            ("newOrderRespType", "RESULT"),
         ]);
 
-        self.send_req_with_sign(params, "cancel_order").await
+        // A failed cancelReplace reports whether the cancel went through
+        let res = self.send_req_with_sign_unchecked(params, "cancel_replace").await?;
+        parse_cancel_replace(&res, symbol)
     }
 
+    // `limit` (up to 5000 levels) is optional; deeper books cost more request weight
     async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
-        let params = BTreeMap::from([("symbol", symbol.as_str())]);
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let limit = req["limit"].as_u64().map(|limit| limit.to_string());
+        let mut params = BTreeMap::from([("symbol", symbol.as_str())]);
+        if let Some(limit) = &limit {
+            params.insert("limit", limit);
+        }
 
+        let weight = get_request_weight("order_book", &params);
         let query_string = get_query_string(params);
         let base = self
             .get_end_point_with_key("order_book")
             .ok_or("Endpoint not found".to_string())?;
 
         let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
+        // The book is fetched again if the body is unreadable
+        let res = fetch_json_retrying(|| async {
+            let request = build_request(
+                base[0].as_str(),
+                &uri,
+                vec![(ACCEPT, "application/json")],
+                BTreeMap::new()
+            )?;
+            let response = self.send_weighted(request, weight).await.map_err(|e| e.to_string())?;
+            Ok(response.into_body())
+        }).await?;
+        let mut orderbook = parse_orderbook(res, req["symbol"].as_str().unwrap().to_string())?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).unwrap();
-        Ok(parse_orderbook(res, req["symbol"].as_str().unwrap().to_string())?)
+        // The depth snapshot carries no time of its own, so it is stamped on arrival
+        orderbook.timestamp = Some(self.client.clock().now_millis());
+        Ok(orderbook)
     }
 
     fn get_name(&self) -> String {
         "Binance".to_string()
     }
 
+    fn supports(&self, method: &str) -> bool {
+        supports_method(IMPLEMENTED_METHODS, method)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: false,
+            min_order_notional: None,
+        }
+    }
+
     async fn get_current_price(&self, req: Value) -> Result<Price, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
         let params = BTreeMap::from([("symbol", symbol.as_str())]);
 
+        let weight = get_request_weight("current_price", &params);
         let query_string = get_query_string(params);
         let base = self
             .get_end_point_with_key("current_price")
             .ok_or("Endpoint not found".to_string())?;
 
         let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
+        let request = build_request(
             base[0].as_str(),
             &uri,
             vec![(ACCEPT, "application/json")],
             BTreeMap::new()
         )?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
+        let response = self.send_weighted(request, weight).await.map_err(|e| e.to_string())?;
         let body = response.into_body();
-        let res: Value = from_slice(&body).map_err(|e| e.to_string())?;
+        let res = parse_json_body(&body)?;
 
         println!(">>>>>>>>>>>>>>>>>>>>> {:?}", res);
 
@@ -221,58 +1043,388 @@ impl Exchange for Binance {
             exchange: "Binance".to_string(),
             symbol: symbol_name,
             price: current_price,
+            source: PriceSource::Last,
         };
 
         Ok(price)
     }
 
+    // 5000 levels a side, at a weight of 250
+    async fn get_full_order_book(&self, symbol: &str) -> Result<OrderBook, ExchangeError> {
+        Ok(self.get_order_book(json!({ "symbol": symbol, "limit": 5000 })).await?)
+    }
+
+    async fn symbol_info(&self, symbol: &str) -> Result<SymbolInfo, ExchangeError> {
+        let key = ("Binance".to_string(), symbol.to_string());
+        self.symbol_info_cache.get_or_fetch(key, || async {
+            let market = parse_symbol(symbol)?;
+            let params = BTreeMap::from([("symbol", market.as_str())]);
+
+            let res = self.send_public_req(params, "coin_list").await?;
+            check_response(&res)?;
+            parse_symbol_info(&res, symbol)
+        }).await
+    }
+
+    async fn get_ticker(&self, symbol: &str) -> Result<Ticker, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("symbol", market.as_str())]);
+
+        let res = self.send_public_req(params, "tickers").await?;
+        check_response(&res)?;
+        parse_ticker(&res, symbol)
+    }
+
+    async fn get_best_quote(&self, symbol: &str) -> Result<Quote, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("symbol", market.as_str())]);
+
+        let res = self.send_public_req(params, "book_ticker").await?;
+        check_response(&res)?;
+        parse_quote(&res)
+    }
+
+    async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>
+    ) -> Result<Vec<Trade>, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let limit = limit.map(|limit| limit.to_string());
+        let mut params = BTreeMap::from([("symbol", market.as_str())]);
+        if let Some(limit) = limit.as_deref() {
+            params.insert("limit", limit);
+        }
+
+        let res = self.send_public_req(params, "recent_trades").await?;
+        check_response(&res)?;
+        parse_recent_trades(&res, symbol)
+    }
+
+    // Without a symbol the ticker endpoint returns every market
+    async fn get_prices(&self, symbols: &[&str]) -> Result<Vec<Price>, ExchangeError> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        let res = self.send_public_req(BTreeMap::new(), "current_price").await?;
+        check_response(&res)?;
+        parse_prices(&res, symbols)
+    }
+
     async fn get_coin_list(&self) -> Result<CoinList, String> {
-        let params = BTreeMap::from([("permissions", "SPOT")]);
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
 
-        let query_string = get_query_string(params);
-        let base = self
-            .get_end_point_with_key("coin_list")
-            .ok_or("Endpoint not found".to_string())?;
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
 
-        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
+    async fn get_mark_price(&self, symbol: &str) -> Result<Price, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("symbol", market.as_str())]);
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
+        let res = self.send_public_req_to(
+            &self.futures_client,
+            &self.futures_api_url,
+            params,
+            "mark_price"
+        ).await?;
+        parse_mark_price(&res, symbol)
+    }
 
-        // Parsing response to create CoinList struct
-        let market = "Binance".to_string();
-        let coin_list = res["symbols"]
-            .as_array()
-            .ok_or("Response is not an array".to_string())?
-            .iter()
-            .filter_map(|coin|
-                format!(
-                    "{}/{}",
-                    coin["baseAsset"].as_str().unwrap(),
-                    coin["quoteAsset"].as_str().unwrap()
-                ).into()
-            )
-            .collect::<Vec<String>>();
+    async fn ping(&self) -> Result<(), ExchangeError> {
+        self.send_public_req(BTreeMap::new(), "ping").await?;
+        Ok(())
+    }
 
-        let coin_list_struct = CoinList {
-            market,
-            coin_list,
-        };
+    async fn check_auth(&self) -> Result<(), ExchangeError> {
+        let timestamp_ = self.client.clock().now_millis().to_string();
+        let params = BTreeMap::from([
+            ("omitZeroBalances", "true"),
+            ("timestamp", timestamp_.as_str()),
+        ]);
 
-        Ok(coin_list_struct)
+        self.send_req_with_sign(params, "account").await?;
+        Ok(())
+    }
+
+    async fn top_markets_by_volume(
+        &self,
+        quote: &str,
+        n: usize
+    ) -> Result<Vec<Market>, ExchangeError> {
+        // 24hr tickers carry no base/quote split or status, so they are joined with exchangeInfo
+        let (exchange_info, tickers) = futures::join!(
+            self.send_public_req(BTreeMap::from([("permissions", "SPOT")]), "coin_list"),
+            self.send_public_req(BTreeMap::new(), "tickers")
+        );
+
+        let markets = parse_markets(&exchange_info?, &tickers?, quote)?;
+        Ok(top_by_volume(markets, n))
+    }
+
+    async fn get_deposit_address(&self, req: Value) -> Result<DepositAddress, ExchangeError> {
+        let timestamp_ = get_request_timestamp(&req, self.client.clock());
+        let mut params = BTreeMap::from([
+            ("coin", req["currency"].as_str().unwrap_or_default()),
+            ("timestamp", &timestamp_),
+        ]);
+        if let Some(network) = req["network"].as_str() {
+            params.insert("network", network);
+        }
+
+        let res = self.send_req_with_sign(params, "deposit_address").await?;
+        parse_deposit_address(&res, req["network"].as_str().unwrap_or_default())
+    }
+
+    async fn cancel_all_orders(&self, req: Value) -> Result<Vec<String>, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_all_orders")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            let timestamp_ = get_request_timestamp(&req, self.client.clock());
+
+            if let Some(symbol) = req["symbol"].as_str() {
+                let symbol = parse_symbol(symbol)?;
+                let params = BTreeMap::from([
+                    ("symbol", symbol.as_str()),
+                    ("timestamp", &timestamp_),
+                ]);
+                let res = self.send_req_with_sign(params, "cancel_all_orders").await?;
+                return parse_order_ids(&res);
+            }
+
+            // The batch endpoint needs a symbol, so without one every open order is canceled
+            // individually
+            let params = BTreeMap::from([("timestamp", timestamp_.as_str())]);
+            let open_orders = self.send_req_with_sign(params, "open_orders").await?;
+            let open_orders = open_orders
+                .as_array()
+                .ok_or(ExchangeError::Parse(open_orders.to_string()))?;
+            let order_ids = open_orders
+                .iter()
+                .map(|order| order["orderId"].to_string())
+                .collect::<Vec<String>>();
+            // The cancels go out one after another, so each is stamped as it is sent to stay
+            // inside recvWindow however long the run takes
+            let cancels = open_orders
+                .iter()
+                .zip(&order_ids)
+                .map(|(order, order_id)| {
+                    let cancel = async move {
+                        let timestamp_ = self.client.clock().now_millis().to_string();
+                        let params = BTreeMap::from([
+                            ("symbol", order["symbol"].as_str().unwrap_or_default()),
+                            ("orderId", order_id.as_str()),
+                            ("timestamp", timestamp_.as_str()),
+                        ]);
+                        self.send_req_with_sign(params, "cancel_order").await
+                    };
+                    (order_id.clone(), cancel)
+                })
+                .collect();
+            cancel_each(cancels).await
+        }).await
+    }
+
+    async fn get_withdrawal_fee(
+        &self,
+        currency: &str,
+        network: &str
+    ) -> Result<WithdrawalFee, ExchangeError> {
+        let timestamp_ = self.client.clock().now_millis().to_string();
+        let params = BTreeMap::from([("timestamp", timestamp_.as_str())]);
+
+        let res = self.send_req_with_sign(params, "withdrawal_config").await?;
+        parse_withdrawal_fee(&res, currency, network)
+    }
+
+    async fn withdraw(&self, req: Value) -> Result<Value, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "withdraw")?;
+        let currency = req["currency"].as_str().unwrap_or_default();
+        let network = req["network"].as_str().unwrap_or(currency);
+        let amount = req["amount"].as_str().unwrap_or_default();
+        self.get_withdrawal_fee(currency, network).await?.check_amount(amount)?;
+
+        let timestamp_ = get_request_timestamp(&req, self.client.clock());
+        let mut params = BTreeMap::from([
+            ("coin", currency),
+            ("network", network),
+            ("address", req["address"].as_str().unwrap_or_default()),
+            ("amount", amount),
+            ("timestamp", &timestamp_),
+        ]);
+        if let Some(memo) = req["memo"].as_str() {
+            params.insert("addressTag", memo);
+        }
+
+        Ok(self.send_req_with_sign(params, "withdraw").await?)
+    }
+
+    // Binance keeps deposits and withdrawals apart and serves at most 90 days of either
+    async fn get_transfer_history(&self, req: Value) -> Result<Vec<Transfer>, ExchangeError> {
+        let timestamp_ = get_request_timestamp(&req, self.client.clock());
+        let start_time = req["start_time"].as_u64().map(|start_time| start_time.to_string());
+        let end_time = req["end_time"].as_u64().map(|end_time| end_time.to_string());
+        let mut params = BTreeMap::from([("timestamp", timestamp_.as_str())]);
+        if let Some(currency) = req["currency"].as_str() {
+            params.insert("coin", currency);
+        }
+        if let Some(start_time) = &start_time {
+            params.insert("startTime", start_time);
+        }
+        if let Some(end_time) = &end_time {
+            params.insert("endTime", end_time);
+        }
+
+        let deposits = self.send_req_with_sign(params.clone(), "deposit_history").await?;
+        let withdrawals = self.send_req_with_sign(params, "withdrawal_history").await?;
+        let mut transfers = parse_deposit_history(&deposits)?;
+        transfers.extend(parse_withdrawal_history(&withdrawals)?);
+        Ok(filter_transfers(transfers, &req))
+    }
+
+    async fn get_all_balances(&self) -> Result<Vec<Balance>, ExchangeError> {
+        let timestamp_ = self.client.clock().now_millis().to_string();
+        let params = BTreeMap::from([("timestamp", timestamp_.as_str())]);
+        let spot_params = BTreeMap::from([
+            ("omitZeroBalances", "true"),
+            ("timestamp", timestamp_.as_str()),
+        ]);
+
+        let (spot, funding, margin) = futures::join!(
+            self.send_req_with_sign(spot_params, "account"),
+            self.send_req_with_sign(params.clone(), "funding_balance"),
+            self.send_req_with_sign(params, "margin_account")
+        );
+
+        let mut balances = parse_balances(&spot?["balances"], Wallet::Spot)?;
+        balances.extend(parse_balances(&funding?, Wallet::Funding)?);
+        // -3003: the account has never opened a margin account
+        match margin {
+            Ok(margin) => balances.extend(parse_balances(&margin["userAssets"], Wallet::Margin)?),
+            Err(ExchangeError::ExchangeRejected { code, .. }) if code == "-3003" => {}
+            Err(e) => return Err(e),
+        }
+        Ok(balances)
+    }
+
+    async fn get_account_info(&self) -> Result<AccountInfo, ExchangeError> {
+        let timestamp_ = self.client.clock().now_millis().to_string();
+        let params = BTreeMap::from([
+            ("omitZeroBalances", "true"),
+            ("timestamp", timestamp_.as_str()),
+        ]);
+
+        let res = self.send_req_with_sign(params, "account").await?;
+        parse_account_info(&res)
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<FeeSchedule, ExchangeError> {
+        let timestamp_ = self.client.clock().now_millis().to_string();
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([
+            ("symbol", market.as_str()),
+            ("timestamp", timestamp_.as_str()),
+        ]);
+
+        let res = self.send_req_with_sign(params, "trade_fee").await?;
+        parse_trading_fees(&res, symbol)
+    }
+
+    async fn stream_bbo(
+        &self,
+        symbol: &str
+    ) -> Result<BoxStream<'static, Result<Bbo, ExchangeError>>, ExchangeError> {
+        let url = format!("{}{}@bookTicker", self.ws_url, parse_symbol(symbol)?.to_lowercase());
+        let messages = WsManager::new(&url)
+            .with_max_reconnect_attempts(self.max_reconnect_attempts)
+            .subscribe().await?;
+
+        let symbol = symbol.to_string();
+        let clock = self.client.clock().clone();
+        Ok(messages.map(move |message| parse_bbo(&message?, &symbol, clock.now_millis())).boxed())
+    }
+
+    // Orders are reported under the SDK's symbols, taken from the coin list, where it has them.
+    // The listen key is kept alive while the stream is open and closed when it is dropped.
+    async fn stream_user_orders(
+        &self
+    ) -> Result<BoxStream<'static, Result<Order, ExchangeError>>, ExchangeError> {
+        let base = self
+            .get_end_point_with_key("user_data_stream")
+            .ok_or("Endpoint not found".to_string())?;
+        let listen_keys: Arc<dyn ListenKeyApi> = Arc::new(ListenKeys {
+            client: self.client.clone(),
+            url: format!("{}{}", self.api_url, base[1]),
+            api_key: self.api_key.clone(),
+        });
+        let (listen_key, messages) = UserStream::connect(
+            &listen_keys,
+            &self.ws_url,
+            self.max_reconnect_attempts
+        ).await?;
+        let keepalive_errors = mpsc::unbounded_channel();
+        let keepalive = Keepalive::spawn(
+            listen_keys.clone(),
+            &listen_key,
+            self.listen_key_keepalive_interval,
+            keepalive_errors.0.clone()
+        );
+
+        let symbols = self
+            .get_coin_list().await
+            .map(|coin_list| {
+                coin_list.coin_list
+                    .into_iter()
+                    .map(|symbol| (symbol.replace('/', ""), symbol))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let user_stream = UserStream {
+            listen_keys,
+            ws_url: self.ws_url.clone(),
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            keepalive_interval: self.listen_key_keepalive_interval,
+            messages,
+            keepalive,
+            keepalive_errors,
+            symbols,
+        };
+        Ok(stream::unfold(user_stream, UserStream::next_order).boxed())
     }
 }
 
-fn parse_symbol(symbol: &str) -> String {
-    let v: Vec<&str> = symbol.split("/").collect();
-    format!("{}{}", v[0], v[1])
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}{}", base, quote))
+}
+
+// Documented spot api weights. sapi endpoints are metered separately and count nothing here.
+fn get_request_weight(endpoint_key: &str, param: &BTreeMap<&str, &str>) -> u32 {
+    let has_symbol = param.contains_key("symbol");
+    match endpoint_key {
+        "order_book" => {
+            match param.get("limit").and_then(|limit| limit.parse::<u32>().ok()).unwrap_or(100) {
+                0..=100 => 5,
+                101..=500 => 25,
+                501..=1000 => 50,
+                _ => 250,
+            }
+        }
+        "current_price" if has_symbol => 2,
+        "current_price" => 4,
+        "tickers" if has_symbol => 2,
+        "tickers" => 80,
+        "book_ticker" if has_symbol => 2,
+        "book_ticker" => 4,
+        "open_orders" if has_symbol => 6,
+        "open_orders" => 80,
+        "coin_list" | "account" => 20,
+        "recent_trades" => 25,
+        "get_order" => 4,
+        "make_order" | "cancel_order" | "cancel_replace" | "cancel_all_orders" => 1,
+        "ping" => 1,
+        _ => 0,
+    }
 }
 
 fn parse_orderbook(orderbook_res: Value, symbol: String) -> Result<OrderBook, String> {
@@ -280,16 +1432,15 @@ fn parse_orderbook(orderbook_res: Value, symbol: String) -> Result<OrderBook, St
     let asks = orderbook_res["asks"].as_array().ok_or("Asks field is not an array")?;
     let bids = orderbook_res["bids"].as_array().ok_or("Bids field is not an array")?;
 
-    // Ensure the lengths of asks and bids are the same
+    // Every level is kept; past the end of the shorter side its price and size are empty
     let mut orderbook_units = Vec::new();
-    let len = asks.len().min(bids.len());
+    let len = asks.len().max(bids.len());
 
     for i in 0..len {
         // Parse ask and bid for each level
-        let ask = &asks[i];
-        let bid = &bids[i];
+        let ask = asks.get(i).unwrap_or(&Value::Null);
+        let bid = bids.get(i).unwrap_or(&Value::Null);
 
-        // Ensure both ask and bid are arrays of size 2
         let ask_price = ask[0].as_str().unwrap_or_default().to_string();
         let ask_size = ask[1].as_str().unwrap_or_default().to_string();
         let bid_price = bid[0].as_str().unwrap_or_default().to_string();
@@ -309,5 +1460,477 @@ fn parse_orderbook(orderbook_res: Value, symbol: String) -> Result<OrderBook, St
         market: symbol,
         exchange: "Binance".to_string(),
         orderbook_unit: orderbook_units,
+        // The depth snapshot carries only a sequence number; the caller stamps it
+        timestamp: None,
+    })
+}
+
+fn parse_order_ids(res: &Value) -> Result<Vec<String>, ExchangeError> {
+    let orders = res.as_array().ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
+    Ok(
+        orders
+            .iter()
+            .map(|order| order["orderId"].to_string())
+            .collect()
+    )
+}
+
+pub(crate) fn parse_mark_price(res: &Value, symbol: &str) -> Result<Price, ExchangeError> {
+    let mark_price = res["markPrice"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("markPrice field is missing: {}", res)))?;
+
+    Ok(Price {
+        exchange: "Binance".to_string(),
+        symbol: symbol.to_string(),
+        price: mark_price.to_string(),
+        source: PriceSource::Mark,
+    })
+}
+
+// Rules are spread over the symbol's filters
+pub(crate) fn parse_symbol_info(res: &Value, symbol: &str) -> Result<SymbolInfo, ExchangeError> {
+    let info = &res["symbols"][0];
+    let (Some(base), Some(quote)) = (info["baseAsset"].as_str(), info["quoteAsset"].as_str()) else {
+        return Err(ExchangeError::Parse(format!("Symbol is missing from exchange info: {}", res)));
+    };
+    let filters = info["filters"].as_array().cloned().unwrap_or_default();
+    let filter = |filter_types: &[&str], field: &str| {
+        filters
+            .iter()
+            .find(|filter| filter_types.contains(&filter["filterType"].as_str().unwrap_or_default()))
+            .and_then(|filter| filter[field].as_str())
+            .map(|value| value.to_string())
+    };
+
+    Ok(SymbolInfo {
+        exchange: "Binance".to_string(),
+        symbol: symbol.to_string(),
+        base: base.to_string(),
+        quote: quote.to_string(),
+        tick_size: filter(&["PRICE_FILTER"], "tickSize"),
+        lot_size: filter(&["LOT_SIZE"], "stepSize"),
+        min_size: filter(&["LOT_SIZE"], "minQty"),
+        min_notional: filter(&["NOTIONAL", "MIN_NOTIONAL"], "minNotional"),
+    })
+}
+
+pub(crate) fn parse_ticker(res: &Value, symbol: &str) -> Result<Ticker, ExchangeError> {
+    let field = |name: &str| res[name].as_str().map(|value| value.to_string());
+    let last = field("lastPrice")
+        .ok_or(ExchangeError::Parse(format!("lastPrice field is missing: {}", res)))?;
+
+    Ok(Ticker {
+        exchange: "Binance".to_string(),
+        symbol: symbol.to_string(),
+        last,
+        bid: field("bidPrice"),
+        ask: field("askPrice"),
+        high_24h: field("highPrice"),
+        low_24h: field("lowPrice"),
+        volume_24h: field("volume"),
+        change_24h: field("priceChange"),
+    })
+}
+
+pub(crate) fn parse_quote(res: &Value) -> Result<Quote, ExchangeError> {
+    let field = |name: &str| {
+        res[name]
+            .as_str()
+            .map(|value| value.to_string())
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", name, res)))
+    };
+
+    Ok(Quote {
+        bid: field("bidPrice")?,
+        bid_size: field("bidQty")?,
+        ask: field("askPrice")?,
+        ask_size: field("askQty")?,
+    })
+}
+
+// `isBuyerMaker` means the resting order was the buy, so the taker sold
+pub(crate) fn parse_recent_trades(res: &Value, symbol: &str) -> Result<Vec<Trade>, ExchangeError> {
+    let trades = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Unexpected trades response: {}", res)))?;
+
+    trades
+        .iter()
+        .map(|trade| {
+            let field = |name: &str| {
+                get_optional_str(&trade[name])
+                    .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", name, trade)))
+            };
+            let side = match trade["isBuyerMaker"].as_bool() {
+                Some(true) => Side::Sell,
+                Some(false) => Side::Buy,
+                None => {
+                    return Err(
+                        ExchangeError::Parse(format!("isBuyerMaker field is missing: {}", trade))
+                    );
+                }
+            };
+            Ok(Trade {
+                exchange: "Binance".to_string(),
+                symbol: symbol.to_string(),
+                trade_id: get_number_string(&trade["id"])
+                    .ok_or(ExchangeError::Parse(format!("id field is missing: {}", trade)))?,
+                price: field("price")?,
+                size: field("qty")?,
+                side,
+                timestamp: trade["time"]
+                    .as_u64()
+                    .ok_or(ExchangeError::Parse(format!("time field is missing: {}", trade)))?,
+                fee: String::new(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn parse_prices(res: &Value, symbols: &[&str]) -> Result<Vec<Price>, ExchangeError> {
+    let tickers = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Unexpected tickers response: {}", res)))?;
+
+    symbols
+        .iter()
+        .map(|symbol| {
+            let market = parse_symbol(symbol)?;
+            let price = tickers
+                .iter()
+                .find(|ticker| ticker["symbol"].as_str() == Some(market.as_str()))
+                .and_then(|ticker| ticker["price"].as_str())
+                .ok_or(ExchangeError::Parse(format!("{} is missing from the tickers", market)))?;
+            Ok(Price {
+                exchange: "Binance".to_string(),
+                symbol: symbol.to_string(),
+                price: price.to_string(),
+                source: PriceSource::Last,
+            })
+        })
+        .collect()
+}
+
+// Errors come back as `{ "code": <negative number>, "msg" }`
+pub(crate) fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let message = || res["msg"].as_str().unwrap_or_default().to_string();
+    match res["code"].as_i64() {
+        // Invalid signature, API key id, API key format and API key/IP/permissions
+        Some(code @ (-1022 | -2008 | -2014 | -2015)) => {
+            Err(ExchangeError::Auth(format!("{}: {}", code, message())))
+        }
+        Some(code) if code < 0 => {
+            Err(ExchangeError::ExchangeRejected { code: code.to_string(), message: message() })
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn parse_markets(
+    exchange_info: &Value,
+    tickers: &Value,
+    quote: &str
+) -> Result<Vec<Market>, ExchangeError> {
+    let symbols = exchange_info["symbols"]
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("symbols field is not an array: {}", exchange_info)))?;
+    let tickers = tickers
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Not an array: {}", tickers)))?
+        .iter()
+        .filter_map(|ticker| ticker["symbol"].as_str().map(|symbol| (symbol, ticker)))
+        .collect::<BTreeMap<&str, &Value>>();
+
+    Ok(
+        symbols
+            .iter()
+            .filter(|symbol| symbol["status"] == "TRADING" && symbol["quoteAsset"] == quote)
+            .filter_map(|symbol| {
+                let ticker = tickers.get(symbol["symbol"].as_str()?)?;
+                let base = symbol["baseAsset"].as_str()?;
+                Some(Market {
+                    exchange: "Binance".to_string(),
+                    symbol: format!("{}/{}", base, quote),
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                    last_price: ticker["lastPrice"].as_str().unwrap_or("0").to_string(),
+                    quote_volume: ticker["quoteVolume"].as_str().unwrap_or("0").to_string(),
+                })
+            })
+            .collect()
+    )
+}
+
+// A failed cancelReplace reports the outcome of both halves under `data`
+fn parse_cancel_replace(res: &Value, symbol: &str) -> Result<Order, ExchangeError> {
+    if let Some(data) = res.get("data") {
+        let message = res["msg"].as_str().unwrap_or_default();
+        if data["cancelResult"] == "SUCCESS" {
+            return Err(replacement_failed(&data["cancelResponse"]["orderId"].to_string(), message));
+        }
+        return Err(ExchangeError::Request(message.to_string()));
+    }
+
+    parse_order(&res["newOrderResponse"], symbol)
+}
+
+// Other user data events, e.g. balance updates, yield `None`. Only the fee of the latest fill
+// is reported, so it is left in `raw` rather than mapped.
+pub(crate) fn parse_execution_report(
+    message: &Value,
+    symbols: &HashMap<String, String>
+) -> Result<Option<Order>, ExchangeError> {
+    if message["e"] != "executionReport" {
+        return Ok(None);
+    }
+    let ord_id = message["i"]
+        .as_u64()
+        .ok_or(ExchangeError::Parse(format!("i field is missing: {}", message)))?;
+    let field = |key: &str| message[key].as_str().unwrap_or_default().to_string();
+    let raw_state = field("X");
+    let symbol = field("s");
+
+    Ok(
+        Some(Order {
+            exchange: "Binance".to_string(),
+            ord_id: ord_id.to_string(),
+            side: Side::from_exchange_str(&field("S"))?,
+            ord_type: field("o"),
+            price: field("p"),
+            state: OrderState::from_exchange_str(&raw_state),
+            raw_state,
+            market: symbols.get(&symbol).cloned().unwrap_or(symbol),
+            volume: field("q"),
+            create_at: message["O"].to_string(),
+            amount: field("z"),
+            fee: String::new(),
+            fee_currency: String::new(),
+            raw: message.clone(),
+        })
+    )
+}
+
+pub(crate) fn parse_order(res: &Value, symbol: &str) -> Result<Order, ExchangeError> {
+    let ord_id = res["orderId"]
+        .as_u64()
+        .ok_or(ExchangeError::Parse(format!("orderId field is missing: {}", res)))?;
+    let raw_state = res["status"].as_str().unwrap_or_default();
+
+    Ok(Order {
+        exchange: "Binance".to_string(),
+        ord_id: ord_id.to_string(),
+        side: Side::from_exchange_str(res["side"].as_str().unwrap_or_default())?,
+        ord_type: res["type"].as_str().unwrap_or_default().to_string(),
+        price: res["price"].as_str().unwrap_or_default().to_string(),
+        state: OrderState::from_exchange_str(raw_state),
+        raw_state: raw_state.to_string(),
+        market: symbol.to_string(),
+        volume: res["origQty"].as_str().unwrap_or_default().to_string(),
+        // Order queries report `time` rather than `transactTime`
+        create_at: res
+            .get("transactTime")
+            .or(res.get("time"))
+            .map(Value::to_string)
+            .unwrap_or_default(),
+        // The filled value in the quote currency; the filled size is `executedQty` in `raw`
+        amount: res["cummulativeQuoteQty"].as_str().unwrap_or_default().to_string(),
+        fee: String::new(),
+        fee_currency: String::new(),
+        raw: res.clone(),
+    })
+}
+
+// Unlike `parse_order`, `amount` is the executed quantity, i.e. how much of the order filled
+// before it was canceled
+pub(crate) fn parse_cancel_order(res: &Value, symbol: &str) -> Result<Order, ExchangeError> {
+    Ok(Order {
+        amount: res["executedQty"].as_str().unwrap_or_default().to_string(),
+        ..parse_order(res, symbol)?
+    })
+}
+
+// Every wallet lists its assets as `{ asset, free, locked }`; empty ones are dropped
+fn parse_balances(assets: &Value, wallet: Wallet) -> Result<Vec<Balance>, ExchangeError> {
+    let assets = assets
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("{:?} balances are not an array: {}", wallet, assets)))?;
+
+    Ok(
+        assets
+            .iter()
+            .map(|asset| Balance {
+                exchange: "Binance".to_string(),
+                wallet,
+                currency: asset["asset"].as_str().unwrap_or_default().to_string(),
+                free: asset["free"].as_str().unwrap_or("0").to_string(),
+                locked: asset["locked"].as_str().unwrap_or("0").to_string(),
+            })
+            .filter(|balance| !is_zero(&balance.free) || !is_zero(&balance.locked))
+            .collect()
+    )
+}
+
+// The VIP level and sub-account status are not part of the spot account response
+pub(crate) fn parse_account_info(res: &Value) -> Result<AccountInfo, ExchangeError> {
+    let flag = |key: &str| -> Result<bool, ExchangeError> {
+        res[key].as_bool().ok_or(ExchangeError::Parse(format!("{} field is missing: {}", key, res)))
+    };
+
+    Ok(AccountInfo {
+        exchange: "Binance".to_string(),
+        can_trade: Some(flag("canTrade")?),
+        can_withdraw: Some(flag("canWithdraw")?),
+        fee_tier: None,
+        is_sub_account: None,
+    })
+}
+
+fn is_zero(amount: &str) -> bool {
+    amount.parse::<f64>().is_ok_and(|amount| amount == 0.0)
+}
+
+pub(crate) fn parse_trading_fees(res: &Value, symbol: &str) -> Result<FeeSchedule, ExchangeError> {
+    let fee = &res[0];
+    let rate = |key: &str| {
+        fee[key]
+            .as_str()
+            .map(|rate| rate.to_string())
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", key, res)))
+    };
+
+    Ok(FeeSchedule {
+        exchange: "Binance".to_string(),
+        symbol: symbol.to_string(),
+        maker: rate("makerCommission")?,
+        taker: rate("takerCommission")?,
+    })
+}
+
+pub(crate) fn parse_withdrawal_fee(
+    res: &Value,
+    currency: &str,
+    network: &str
+) -> Result<WithdrawalFee, ExchangeError> {
+    let coin = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?
+        .iter()
+        .find(|coin| coin["coin"] == currency)
+        .ok_or(ExchangeError::InvalidRequest(format!("Unknown currency {}", currency)))?;
+
+    let network_config = coin["networkList"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|config| config["network"] == network)
+        .ok_or(
+            ExchangeError::InvalidRequest(format!("Unknown network {} for {}", network, currency))
+        )?;
+
+    Ok(WithdrawalFee {
+        fee: network_config["withdrawFee"].as_str().unwrap_or("0").to_string(),
+        min_withdrawal: network_config["withdrawMin"].as_str().unwrap_or("0").to_string(),
+    })
+}
+
+pub(crate) fn parse_deposit_history(res: &Value) -> Result<Vec<Transfer>, ExchangeError> {
+    let deposits = res.as_array().ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
+
+    deposits
+        .iter()
+        .map(|deposit| {
+            let status = deposit["status"]
+                .as_u64()
+                .ok_or(ExchangeError::Parse(format!("status field is missing: {}", deposit)))?;
+            let status = match status {
+                1 | 6 => TransferStatus::Completed,
+                2 | 7 => TransferStatus::Failed,
+                _ => TransferStatus::Pending,
+            };
+            Ok(Transfer {
+                kind: TransferKind::Deposit,
+                currency: deposit["coin"].as_str().unwrap_or_default().to_string(),
+                amount: deposit["amount"].as_str().unwrap_or_default().to_string(),
+                fee: "0".to_string(),
+                tx_id: get_optional_str(&deposit["txId"]),
+                address: get_optional_str(&deposit["address"]),
+                status,
+                timestamp: deposit["insertTime"].as_u64().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+// `applyTime` is a UTC date string, unlike the millisecond times elsewhere
+pub(crate) fn parse_withdrawal_history(res: &Value) -> Result<Vec<Transfer>, ExchangeError> {
+    let withdrawals = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
+
+    withdrawals
+        .iter()
+        .map(|withdrawal| {
+            let status = withdrawal["status"]
+                .as_u64()
+                .ok_or(ExchangeError::Parse(format!("status field is missing: {}", withdrawal)))?;
+            let status = match status {
+                6 => TransferStatus::Completed,
+                3 | 5 => TransferStatus::Failed,
+                1 => TransferStatus::Canceled,
+                _ => TransferStatus::Pending,
+            };
+            let timestamp = withdrawal["applyTime"]
+                .as_str()
+                .and_then(parse_datetime_millis)
+                .ok_or(ExchangeError::Parse(format!("Invalid applyTime: {}", withdrawal)))?;
+            Ok(Transfer {
+                kind: TransferKind::Withdrawal,
+                currency: withdrawal["coin"].as_str().unwrap_or_default().to_string(),
+                amount: withdrawal["amount"].as_str().unwrap_or_default().to_string(),
+                fee: withdrawal["transactionFee"].as_str().unwrap_or("0").to_string(),
+                tx_id: get_optional_str(&withdrawal["txId"]),
+                address: get_optional_str(&withdrawal["address"]),
+                status,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+// bookTicker frames carry no event time, so the caller passes the receive time as `ts`
+pub(crate) fn parse_bbo(message: &Value, symbol: &str, ts: u64) -> Result<Bbo, ExchangeError> {
+    let field = |key: &str| {
+        message[key]
+            .as_str()
+            .map(|value| value.to_string())
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", key, message)))
+    };
+
+    Ok(Bbo {
+        exchange: "Binance".to_string(),
+        symbol: symbol.to_string(),
+        bid: field("b")?,
+        bid_size: field("B")?,
+        ask: field("a")?,
+        ask_size: field("A")?,
+        ts,
+    })
+}
+
+pub(crate) fn parse_deposit_address(
+    res: &Value,
+    network: &str
+) -> Result<DepositAddress, ExchangeError> {
+    let address = res["address"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("address field is missing: {}", res)))?;
+
+    // Binance returns the memo of XRP/XLM style coins as `tag`
+    Ok(DepositAddress {
+        currency: res["coin"].as_str().unwrap_or_default().to_string(),
+        network: network.to_string(),
+        address: address.to_string(),
+        memo: get_optional_str(&res["tag"]),
     })
 }
@@ -1,19 +1,84 @@
 use std::collections::BTreeMap;
 use async_trait::async_trait;
-use serde_json::{ from_slice, Value };
-use http::{ header::{ ACCEPT, AUTHORIZATION, CONTENT_TYPE }, HeaderName, Request };
+use serde_json::{ json, Value };
+use http::header::{ ACCEPT, AUTHORIZATION, CONTENT_TYPE };
 use sha2::{ Digest, Sha512 };
 use uuid::Uuid;
-use hmac::{ Hmac, Mac };
 use jwt::SignWithKey;
 
-use crate::{ get_query_string, send, CoinList, Exchange, OrderBook, OrderBookUnit, Price };
+use crate::http_util::build_request;
+use crate::sign::hmac_key;
+use crate::upbit::{
+    fetch_order_book_json,
+    get_order_books_joined,
+    parse_min_order_notional,
+    parse_orderbook_entry,
+    parse_prices,
+    parse_recent_trades,
+};
+use crate::{
+    cancel_each,
+    cancel_then_place,
+    check_gtc_only,
+    check_min_notional,
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    get_client_order_id,
+    get_number_string,
+    get_order_id_param,
+    get_query_string,
+    parse_json_body,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    supports_method,
+    with_request_timeout,
+    Balance,
+    Capabilities,
+    CoinList,
+    CoinListCache,
+    Decimal,
+    Exchange,
+    ExchangeError,
+    HttpClient,
+    Order,
+    OrderBook,
+    OrderState,
+    Price,
+    PriceSource,
+    Side,
+    Trade,
+    TtlCache,
+    Wallet,
+};
+
+// Optional `Exchange` methods implemented here, reported by `supports`
+const IMPLEMENTED_METHODS: &[&str] = &[
+    "amend_order",
+    "cancel_all_orders",
+    "check_auth",
+    "get_all_balances",
+    "get_order",
+    "get_recent_trades",
+    "min_order_notional",
+    "ping",
+    "place_order_typed",
+];
 
 pub struct Bithumb {
     api_url: String,
     api_key: String,
     secret: String,
     endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    // Minimum order value per market, from order chance
+    min_order_notional_cache: TtlCache<String, Decimal>,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    auto_client_order_id: bool,
+    min_notional_check: bool,
 }
 
 #[allow(dead_code)]
@@ -26,42 +91,62 @@ pub trait BithumbTrait {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> impl std::future::Future<Output = Result<Value, String>> + Send;
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
 }
 
+impl_endpoint_overrides!(Bithumb);
+impl_client_settings!(Bithumb, client);
+impl_public_requests!(Bithumb, check_response);
+impl_exchange_options!(Bithumb);
+impl_auto_client_order_id!(Bithumb);
+
 impl Bithumb {
-    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
-        if api_key.is_empty() || secret.is_empty() {
-            return Err("API key and Secret cannot be empty".to_string());
-        }
-        Ok(())
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
     }
 
-    fn create_hmac_key(&self) -> Result<Hmac<Sha512>, String> {
-        Hmac::new_from_slice(self.secret.as_bytes()).map_err(|e| e.to_string())
+    // place_order_typed checks the order value against min_order_notional before sending it
+    pub fn with_min_notional_check(mut self) -> Self {
+        self.min_notional_check = true;
+        self
     }
 
-    fn build_request<'a>(
-        &'a self,
-        method: &str,
-        uri: &str,
-        headers: Vec<(HeaderName, &str)>,
-        body: BTreeMap<&'a str, &'a str>
-    ) -> Result<Request<BTreeMap<&'a str, &'a str>>, String> {
-        let mut builder = Request::builder().method(method).uri(uri);
-        for (key, value) in headers {
-            builder = builder.header(key, value);
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
         }
-        builder.body(body).map_err(|e| e.to_string())
+        self
     }
 
-    fn get_authorization_header(&self, param: BTreeMap<&str, &str>) -> Result<String, String> {
-        let query = get_query_string(param.clone());
+    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    // SHA512 hex digest of the query string, sent as the JWT's query_hash claim. The exchange
+    // hashes the query before it is percent-encoded, so the raw pairs are joined here.
+    pub(crate) fn get_query_hash(&self, param: &BTreeMap<&str, &str>) -> Result<String, String> {
+        let query = param
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
 
         let mut hasher = Sha512::new();
         hasher.update(query.as_bytes());
-        let query_hash = hex::encode(hasher.finalize());
+        Ok(hex::encode(hasher.finalize()))
+    }
 
+    // JWT signed with the secret. Every call gets a fresh nonce.
+    pub(crate) fn get_json(&self, query_hash: String) -> Result<String, String> {
         let nonce = Uuid::new_v4().to_string();
         let payload = BTreeMap::from([
             ("access_key", self.api_key.clone()),
@@ -70,10 +155,82 @@ impl Bithumb {
             ("query_hash_alg", "SHA512".to_string()),
         ]);
 
-        let key = self.create_hmac_key()?;
-        let jwt_token = payload.sign_with_key(&key).map_err(|e| e.to_string())?;
+        let key = hmac_key::<Sha512>(&self.secret)?;
+        payload.sign_with_key(&key).map_err(|e| e.to_string())
+    }
+
+    fn get_authorization_header(&self, param: BTreeMap<&str, &str>) -> Result<String, String> {
+        let query_hash = self.get_query_hash(&param)?;
+        Ok(format!("Bearer {}", self.get_json(query_hash)?))
+    }
+
+    async fn send_order(&self, req: &Value) -> Result<Value, ExchangeError> {
+        check_quote_amount(req)?;
+        check_gtc_only("Bithumb", req)?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+        let mut params = BTreeMap::from([
+            ("market", symbol.as_str()),
+            ("side", req["side"].as_str().unwrap_or_default()),
+            ("ord_type", req["order_type"].as_str().unwrap_or_default()),
+            ("price", req["price"].as_str().unwrap_or_default()),
+            ("volume", req["amount"].as_str().unwrap_or_default()),
+        ]);
+        let client_order_id = get_client_order_id(req, self.auto_client_order_id);
+        if let Some(client_order_id) = &client_order_id {
+            params.insert("identifier", client_order_id);
+        }
+
+        self.send_req_with_sign(params, "make_order").await
+    }
+
+    // Raw order books of `markets`, a comma separated list
+    async fn fetch_order_books(&self, markets: &str) -> Result<Value, String> {
+        let params = BTreeMap::from([("markets", markets)]);
+
+        let query_string = get_query_string(params);
+        let base = self
+            .get_end_point_with_key("order_book")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
+        fetch_order_book_json(&self.client, &base[0], &uri).await
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let params = BTreeMap::from([("isDetails", "false")]);
+
+        let query_string = get_query_string(params);
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
 
-        Ok(format!("Bearer {}", jwt_token))
+        // Parsing response to create CoinList struct
+        let market = "Bithumb".to_string();
+        let coin_list = res
+            .as_array()
+            .ok_or("Response is not an array".to_string())?
+            .iter()
+            .filter_map(|coin| coin["market"].as_str().map(encode_symbol))
+            .collect::<Vec<String>>();
+
+        let coin_list_struct = CoinList {
+            market,
+            coin_list,
+        };
+
+        Ok(coin_list_struct)
     }
 }
 
@@ -84,9 +241,14 @@ impl BithumbTrait for Bithumb {
         let endpoint = BTreeMap::from([
             ("make_order".to_string(), ["POST".to_string(), "v1/orders".to_string()]),
             ("cancel_order".to_string(), ["DELETE".to_string(), "v1/order".to_string()]),
+            ("get_order".to_string(), ["GET".to_string(), "v1/order".to_string()]),
+            ("open_orders".to_string(), ["GET".to_string(), "v1/orders".to_string()]),
+            ("accounts".to_string(), ["GET".to_string(), "v1/accounts".to_string()]),
             ("order_book".to_string(), ["GET".to_string(), "v1/orderbook".to_string()]),
             ("current_price".to_string(), ["GET".to_string(), "v1/ticker".to_string()]),
             ("coin_list".to_string(), ["GET".to_string(), "v1/market/all".to_string()]),
+            ("recent_trades".to_string(), ["GET".to_string(), "v1/trades/ticks".to_string()]),
+            ("order_chance".to_string(), ["GET".to_string(), "v1/orders/chance".to_string()]),
         ]);
 
         Ok(Self {
@@ -94,6 +256,13 @@ impl BithumbTrait for Bithumb {
             api_key,
             secret,
             endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            min_order_notional_cache: TtlCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            auto_client_order_id: false,
+            min_notional_check: false,
         })
     }
 
@@ -113,77 +282,99 @@ impl BithumbTrait for Bithumb {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> Result<Value, String> {
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
         let authorization = self.get_authorization_header(param.clone())?;
 
         let base = self
             .get_end_point_with_key(endpoint_key)
             .ok_or("Endpoint not found".to_string())?;
 
-        let uri = format!("{}{}", self.api_url, base[1]);
-        let request = self.build_request(
+        // GET and DELETE take their params in the query string, everything else in a JSON body
+        let (uri, body) = if (base[0] == "GET" || base[0] == "DELETE") && !param.is_empty() {
+            (format!("{}{}?{}", self.api_url, base[1], get_query_string(param)), BTreeMap::new())
+        } else {
+            (format!("{}{}", self.api_url, base[1]), param)
+        };
+        let request = build_request(
             base[0].as_str(),
             &uri,
             vec![(AUTHORIZATION, &authorization), (CONTENT_TYPE, "application/json")],
-            param
+            body
         )?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        from_slice(&body).map_err(|e| e.to_string())
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
     }
 }
 
 #[async_trait]
 impl Exchange for Bithumb {
     async fn place_order(&self, req: Value) -> Result<Value, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
-        let params = BTreeMap::from([
-            ("market", symbol.as_str()),
-            ("side", req["side"].as_str().unwrap_or_default()),
-            ("ord_type", req["order_type"].as_str().unwrap_or_default()),
-            ("price", req["price"].as_str().unwrap_or_default()),
-            ("volume", req["amount"].as_str().unwrap_or_default()),
-        ]);
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, self.send_order(&req)).await.map_err(|e| e.to_string())
+    }
 
-        self.send_req_with_sign(params, "make_order").await
+    async fn place_order_typed(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order_typed")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        if self.min_notional_check {
+            let symbol = req["symbol"].as_str().unwrap_or_default();
+            check_min_notional(&req, self.min_order_notional(symbol).await?)?;
+        }
+        let res = with_request_timeout(&req, self.send_order(&req)).await?;
+        parse_order(&res)
     }
 
     async fn cancel_order(&self, req: Value) -> Result<Value, String> {
-        let params = BTreeMap::from([("uuid", req["order_id"].as_str().unwrap_or_default())]);
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, async {
+            let params = BTreeMap::from([get_order_id_param(&req, "uuid", "identifier")?]);
 
-        self.send_req_with_sign(params, "cancel_order").await
+            self.send_req_with_sign(params, "cancel_order").await.map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
     }
 
     async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
-        let params = BTreeMap::from([("markets", symbol.as_str())]);
-
-        let query_string = get_query_string(params);
-        let base = self
-            .get_end_point_with_key("order_book")
-            .ok_or("Endpoint not found".to_string())?;
-
-        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let res = self.fetch_order_books(&symbol).await?;
+        Ok(parse_orderbook_entry(&res[0], "Bithumb")?)
+    }
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).unwrap();
-        Ok(parse_orderbook(res)?)
+    // As on Upbit, one request with comma separated markets serves every symbol
+    async fn get_order_books(&self, symbols: &[&str]) -> Vec<Result<OrderBook, ExchangeError>> {
+        get_order_books_joined(symbols, "Bithumb", |markets| async move {
+            self.fetch_order_books(&markets).await
+        }).await
     }
 
     fn get_name(&self) -> String {
         "Bithumb".to_string()
     }
 
+    fn supports(&self, method: &str) -> bool {
+        supports_method(IMPLEMENTED_METHODS, method)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: false,
+            min_order_notional: Some("5000".to_string()),
+        }
+    }
+
     async fn get_current_price(&self, req: Value) -> Result<Price, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
         let params = BTreeMap::from([("markets", symbol.as_str())]);
 
         let query_string = get_query_string(params);
@@ -192,71 +383,145 @@ impl Exchange for Bithumb {
             .ok_or("Endpoint not found".to_string())?;
 
         let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
+        let request = build_request(
             base[0].as_str(),
             &uri,
             vec![(ACCEPT, "application/json")],
             BTreeMap::new()
         )?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
         let body = response.into_body();
-        let res: Value = from_slice(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
+        let res = parse_json_body(&body)?;
 
         // Parsing response to create Price struct
         let symbol_name = req["symbol"].as_str().unwrap().to_string();
-        let current_price = res[0]["trade_price"].as_f64().unwrap_or(0.0).to_string();
+        let current_price = get_number_string(&res[0]["trade_price"])
+            .unwrap_or_else(|| "0".to_string());
 
         let price = Price {
             exchange: "Bithumb".to_string(),
             symbol: symbol_name,
             price: current_price,
+            source: PriceSource::Last,
         };
 
         Ok(price)
     }
 
+    // The ticker endpoint takes a comma separated list of markets
+    async fn get_prices(&self, symbols: &[&str]) -> Result<Vec<Price>, ExchangeError> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        let markets = symbols
+            .iter()
+            .map(|symbol| parse_symbol(symbol))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(",");
+        let params = BTreeMap::from([("markets", markets.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res)?;
+        parse_prices(&res, symbols, "Bithumb")
+    }
+
+    async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>
+    ) -> Result<Vec<Trade>, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let count = limit.map(|limit| limit.to_string());
+        let mut params = BTreeMap::from([("market", market.as_str())]);
+        if let Some(count) = count.as_deref() {
+            params.insert("count", count);
+        }
+
+        let res = self.send_public_req(params, "recent_trades").await?;
+        check_response(&res)?;
+        parse_recent_trades(&res, symbol, "Bithumb")
+    }
+
     async fn get_coin_list(&self) -> Result<CoinList, String> {
-        let params = BTreeMap::from([("isDetails", "false")]);
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
 
-        let query_string = get_query_string(params);
-        let base = self
-            .get_end_point_with_key("coin_list")
-            .ok_or("Endpoint not found".to_string())?;
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
 
-        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
+    // Bithumb has no native amend, so the order is canceled and placed again
+    async fn amend_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "amend_order")?;
+        cancel_then_place(self, req, parse_order).await
+    }
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
+    // Order chance reports the minimum order value of the market's bid and ask sides
+    // Cached per market, so checked orders do not each cost a lookup
+    async fn min_order_notional(&self, symbol: &str) -> Result<Decimal, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        self.min_order_notional_cache.get_or_fetch(market.clone(), || async {
+            let params = BTreeMap::from([("market", market.as_str())]);
 
-        // Parsing response to create CoinList struct
-        let market = "Bithumb".to_string();
-        let coin_list = res
-            .as_array()
-            .ok_or("Response is not an array".to_string())?
-            .iter()
-            .filter_map(|coin| coin["market"].as_str().map(|s| encode_symbol(s)))
-            .collect::<Vec<String>>();
+            let res = self.send_req_with_sign(params, "order_chance").await?;
+            parse_min_order_notional(&res)
+        }).await
+    }
 
-        let coin_list_struct = CoinList {
-            market,
-            coin_list,
-        };
+    // There is no dedicated ping endpoint, so a single ticker is fetched instead
+    async fn ping(&self) -> Result<(), ExchangeError> {
+        let params = BTreeMap::from([("markets", "KRW-BTC")]);
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res)
+    }
 
-        Ok(coin_list_struct)
+    async fn get_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        let params = BTreeMap::from([get_order_id_param(&req, "uuid", "identifier")?]);
+
+        let res = self.send_req_with_sign(params, "get_order").await?;
+        parse_order(&res)
+    }
+
+    async fn check_auth(&self) -> Result<(), ExchangeError> {
+        self.send_req_with_sign(BTreeMap::new(), "accounts").await?;
+        Ok(())
+    }
+
+    // Bithumb only has a spot wallet
+    async fn get_all_balances(&self) -> Result<Vec<Balance>, ExchangeError> {
+        let res = self.send_req_with_sign(BTreeMap::new(), "accounts").await?;
+        parse_balances(&res)
+    }
+
+    async fn cancel_all_orders(&self, req: Value) -> Result<Vec<String>, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_all_orders")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            // There is no batch cancel endpoint, so every open order is canceled individually
+            let symbol = req["symbol"].as_str().map(parse_symbol).transpose()?;
+            let mut params = BTreeMap::from([("state", "wait")]);
+            if let Some(symbol) = &symbol {
+                params.insert("market", symbol.as_str());
+            }
+
+            let open_orders = self.send_req_with_sign(params, "open_orders").await?;
+            let cancels = open_orders
+                .as_array()
+                .ok_or(ExchangeError::Parse(open_orders.to_string()))?
+                .iter()
+                .map(|order| {
+                    let order_id = order["uuid"].as_str().unwrap_or_default();
+                    (order_id.to_string(), self.cancel_order(json!({ "order_id": order_id })))
+                })
+                .collect();
+            cancel_each(cancels).await
+        }).await
     }
 }
 
-fn parse_symbol(symbol: &str) -> String {
-    let v: Vec<&str> = symbol.split("/").collect();
-    format!("{}-{}", v[1], v[0])
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}-{}", quote, base))
 }
 
 fn encode_symbol(symbol: &str) -> String {
@@ -264,29 +529,78 @@ fn encode_symbol(symbol: &str) -> String {
     format!("{}/{}", v[1], v[0])
 }
 
-fn parse_orderbook(orderbook_res: Value) -> Result<OrderBook, String> {
-    let orderbook_units = orderbook_res[0]["orderbook_units"]
+// Errors come back as `{ "error": { "name", "message" } }`
+fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let Some(error) = res.get("error") else {
+        return Ok(());
+    };
+
+    let name = error["name"].as_str().unwrap_or_default();
+    let message = error["message"].as_str().unwrap_or_default().to_string();
+    match name {
+        "invalid_query_payload"
+        | "jwt_verification"
+        | "expired_access_key"
+        | "nonce_used"
+        | "no_authorization_ip"
+        | "no_authorization_token"
+        | "invalid_access_key"
+        | "out_of_scope" => Err(ExchangeError::Auth(format!("{}: {}", name, message))),
+        _ => Err(ExchangeError::ExchangeRejected { code: name.to_string(), message }),
+    }
+}
+
+fn parse_balances(res: &Value) -> Result<Vec<Balance>, ExchangeError> {
+    let accounts = res
         .as_array()
-        .ok_or("orderbook_units field is not an array")?
-        .iter()
-        .map(|unit| {
-            let ask_price = unit["ask_price"].as_f64().unwrap_or(0.0).to_string();
-            let bid_price = unit["bid_price"].as_f64().unwrap_or(0.0).to_string();
-            let ask_size = unit["ask_size"].as_f64().unwrap_or(0.0).to_string();
-            let bid_size = unit["bid_size"].as_f64().unwrap_or(0.0).to_string();
-            OrderBookUnit {
-                ask_price,
-                bid_price,
-                ask_size,
-                bid_size,
-            }
-        })
-        .collect::<Vec<OrderBookUnit>>();
+        .ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
 
-    let symbol = encode_symbol(orderbook_res[0]["market"].as_str().unwrap_or_default());
-    Ok(OrderBook {
-        market: symbol,
+    Ok(
+        accounts
+            .iter()
+            .map(|account| Balance {
+                exchange: "Bithumb".to_string(),
+                wallet: Wallet::Spot,
+                currency: account["currency"].as_str().unwrap_or_default().to_string(),
+                free: account["balance"].as_str().unwrap_or("0").to_string(),
+                locked: account["locked"].as_str().unwrap_or("0").to_string(),
+            })
+            .collect()
+    )
+}
+
+fn parse_order(order_res: &Value) -> Result<Order, ExchangeError> {
+    if let Some(error) = order_res.get("error") {
+        return Err(ExchangeError::Request(error["message"].as_str().unwrap_or_default().to_string()));
+    }
+    let ord_id = order_res["uuid"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("uuid field is missing: {}", order_res)))?;
+    let market = order_res["market"].as_str().unwrap_or_default();
+    let field = |key: &str| order_res[key].as_str().unwrap_or_default().to_string();
+    let raw_state = field("state");
+    let executed = field("executed_volume").parse::<f64>().unwrap_or_default();
+    let state = match OrderState::from_exchange_str(&raw_state) {
+        OrderState::New if executed > 0.0 => OrderState::PartiallyFilled,
+        state => state,
+    };
+
+    Ok(Order {
         exchange: "Bithumb".to_string(),
-        orderbook_unit: orderbook_units,
+        ord_id: ord_id.to_string(),
+        side: Side::from_exchange_str(order_res["side"].as_str().unwrap_or_default())?,
+        ord_type: field("ord_type"),
+        price: field("price"),
+        state,
+        raw_state,
+        market: encode_symbol(market),
+        volume: field("volume"),
+        create_at: field("created_at"),
+        amount: field("executed_volume"),
+        fee: field("paid_fee"),
+        // Fees are charged in the quote currency, e.g. KRW for KRW-BTC
+        fee_currency: market.split('-').next().unwrap_or_default().to_string(),
+        raw: order_res.clone(),
     })
 }
+
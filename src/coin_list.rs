@@ -0,0 +1,89 @@
+use crate::ttl_cache::TtlCache;
+use crate::{ CoinList, ExchangeError };
+
+// Coin lists keyed by exchange name
+pub type CoinListCache = TtlCache<String, CoinList>;
+
+// Listed symbols are compared without regard to case
+impl CoinList {
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.find(symbol).is_some()
+    }
+
+    // The listed spelling of `symbol`
+    pub fn find(&self, symbol: &str) -> Option<&str> {
+        self.coin_list
+            .iter()
+            .find(|listed| listed.eq_ignore_ascii_case(symbol))
+            .map(|listed| listed.as_str())
+    }
+
+    // Listed symbols within `MAX_SUGGESTION_DISTANCE` edits of `symbol`, closest first
+    pub fn closest(&self, symbol: &str) -> Vec<&str> {
+        let symbol = symbol.to_ascii_uppercase();
+        let mut candidates = self.coin_list
+            .iter()
+            .map(|listed| (edit_distance(&symbol, &listed.to_ascii_uppercase()), listed.as_str()))
+            .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .collect::<Vec<_>>();
+        candidates.sort();
+        candidates.into_iter().map(|(_, listed)| listed).collect()
+    }
+}
+
+// Enough for a dropped or swapped character, e.g. "BTC/USD" for "BTC/USDT"
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+// Levenshtein distance over chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// How many of the closest listings an unmatched symbol's error names
+const MAX_SUGGESTIONS: usize = 3;
+
+// Resolves a symbol as users tend to type it, e.g. "btc/krw" or "btcusdt", to its listed
+// "BASE/QUOTE" form. A symbol without a slash is split before the longest quote currency of
+// the coin list it ends with. Fails with `ExchangeError::InvalidSymbol` naming the closest
+// listings when nothing matches.
+pub fn normalize_user_symbol(input: &str, coin_list: &CoinList) -> Result<String, ExchangeError> {
+    let symbol = input.trim().to_ascii_uppercase();
+    let symbol = if symbol.contains('/') {
+        symbol
+    } else {
+        let mut quotes = coin_list.coin_list
+            .iter()
+            .filter_map(|listed| listed.split_once('/'))
+            .map(|(_, quote)| quote.to_ascii_uppercase())
+            .collect::<Vec<_>>();
+        quotes.sort_by_key(|quote| std::cmp::Reverse(quote.len()));
+        quotes
+            .iter()
+            .find_map(|quote| {
+                let base = symbol.strip_suffix(quote.as_str())?;
+                let candidate = format!("{}/{}", base, quote);
+                (!base.is_empty() && coin_list.contains(&candidate)).then_some(candidate)
+            })
+            .unwrap_or(symbol)
+    };
+    if let Some(listed) = coin_list.find(&symbol) {
+        return Ok(listed.to_string());
+    }
+
+    let candidates = coin_list.closest(&symbol);
+    if candidates.is_empty() {
+        return Err(ExchangeError::InvalidSymbol(format!("{} is not listed", input)));
+    }
+    let candidates = candidates.into_iter().take(MAX_SUGGESTIONS).collect::<Vec<_>>().join(", ");
+    Err(ExchangeError::InvalidSymbol(format!("{} is not listed, closest: {}", input, candidates)))
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExchangeError {
+    Request(String),
+    Parse(String),
+    InvalidRequest(String),
+    // A symbol that is not in "BASE/QUOTE" form, or one the exchange does not list
+    InvalidSymbol(String),
+    // An option of the request the exchange does not offer, e.g. sizing an order by quote amount
+    NotSupported(String),
+    // A trait method the exchange does not implement, see `Exchange::supports`
+    Unsupported { exchange: String, method: String },
+    // The exchange rejected the credentials or signature
+    Auth(String),
+    // The exchange answered with an error of its own, e.g. insufficient balance
+    ExchangeRejected { code: String, message: String },
+    // The call did not finish within the request's `timeout`
+    Timeout(String),
+    // Still rate limited after the retries, with the wait the exchange asked for, if any
+    RateLimited { retry_after: Option<Duration> },
+    // The response body ran past the client's `max_response_bytes`, so reading it was abandoned
+    ResponseTooLarge { limit: usize },
+    // An order or withdrawal refused without a request while the exchange is in safe mode
+    SafeModeBlocked { exchange: String, method: String },
+    // Some of the orders canceled one by one are still open: `canceled` lists the ids that went
+    // through and `failed` holds one "order id: reason" entry per order left on the book
+    CancelFailed { canceled: Vec<String>, failed: Vec<String> },
+    // The exchange accepted the order but reading it back failed, so it is live under
+    // `client_order_id` in a state not known yet
+    Unconfirmed { client_order_id: String, message: String },
+}
+
+impl fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExchangeError::Request(message) => write!(f, "{}", message),
+            ExchangeError::Parse(message) => write!(f, "Failed to parse response: {}", message),
+            ExchangeError::InvalidRequest(message) => write!(f, "Invalid request: {}", message),
+            ExchangeError::InvalidSymbol(symbol) => write!(f, "Invalid symbol: {:?}", symbol),
+            ExchangeError::NotSupported(method) => write!(f, "{} is not supported", method),
+            ExchangeError::Unsupported { exchange, method } => {
+                write!(f, "{} {} is not supported", exchange, method)
+            }
+            ExchangeError::Auth(message) => write!(f, "Authentication failed: {}", message),
+            ExchangeError::ExchangeRejected { code, message } => write!(f, "{}: {}", code, message),
+            ExchangeError::Timeout(message) => write!(f, "Timed out: {}", message),
+            ExchangeError::RateLimited { retry_after: Some(retry_after) } => {
+                write!(f, "Rate limited, retry after {:?}", retry_after)
+            }
+            ExchangeError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            ExchangeError::ResponseTooLarge { limit } => {
+                write!(f, "Response body exceeds the limit of {} bytes", limit)
+            }
+            ExchangeError::SafeModeBlocked { exchange, method } => {
+                write!(f, "{} {} is blocked in safe mode", exchange, method)
+            }
+            ExchangeError::CancelFailed { canceled, failed } => {
+                write!(
+                    f,
+                    "{} of {} cancels failed: {}",
+                    failed.len(),
+                    canceled.len() + failed.len(),
+                    failed.join(", ")
+                )
+            }
+            ExchangeError::Unconfirmed { client_order_id, message } => {
+                write!(f, "Order {} was placed but not confirmed: {}", client_order_id, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExchangeError {}
+
+impl From<String> for ExchangeError {
+    fn from(message: String) -> Self {
+        ExchangeError::Request(message)
+    }
+}
+
+// For the methods that still report errors as strings
+impl From<ExchangeError> for String {
+    fn from(error: ExchangeError) -> Self {
+        error.to_string()
+    }
+}
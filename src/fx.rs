@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+
+use crate::{ parse_decimal, Decimal, ExchangeError, Price };
+
+// Exchange rates between quote currencies, e.g. backed by a forex API or a USDT/KRW market
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    // Units of `to` that one unit of `from` is worth, e.g. about 0.00072 for KRW to USDT
+    async fn fx_rate(&self, from: &str, to: &str) -> Result<Decimal, ExchangeError>;
+}
+
+// Restates `price` in `to_quote` given the value of one unit of its quote currency in
+// `to_quote`. The exchange and base currency are kept, e.g. "BTC/KRW" becomes "BTC/USDT".
+pub fn convert_price(
+    price: &Price,
+    to_quote: &str,
+    fx_rate: Decimal
+) -> Result<Price, ExchangeError> {
+    let (base, _) = price.symbol
+        .split_once('/')
+        .ok_or(ExchangeError::InvalidRequest(format!("Invalid symbol {:?}", price.symbol)))?;
+    let value = parse_decimal(&price.price)
+        .ok_or(ExchangeError::Parse(format!("Invalid price {:?}", price.price)))?;
+
+    Ok(Price {
+        exchange: price.exchange.clone(),
+        symbol: format!("{}/{}", base, to_quote),
+        price: (value * fx_rate).normalize().to_string(),
+        source: price.source,
+    })
+}
+
+// `convert_price` at the provider's rate. A price already in `to_quote` is returned as is.
+pub async fn normalize_price(
+    price: &Price,
+    to_quote: &str,
+    provider: &dyn FxRateProvider
+) -> Result<Price, ExchangeError> {
+    let quote = price.symbol.split_once('/').map(|(_, quote)| quote).unwrap_or_default();
+    if quote == to_quote {
+        return Ok(price.clone());
+    }
+    let fx_rate = provider.fx_rate(quote, to_quote).await?;
+    convert_price(price, to_quote, fx_rate)
+}
@@ -0,0 +1,477 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use serde_json::Value;
+use http::header::{ ACCEPT, CONTENT_TYPE };
+use sha2::{ Digest, Sha512 };
+
+use crate::http_util::build_request;
+use crate::sign::hmac_hex;
+use crate::{
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    get_client_order_id,
+    get_optional_str,
+    get_order_id_param,
+    get_query_string,
+    get_time_in_force,
+    parse_json_body,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    with_request_timeout,
+    Capabilities,
+    Clock,
+    CoinList,
+    CoinListCache,
+    Exchange,
+    ExchangeError,
+    HttpClient,
+    OrderBook,
+    OrderBookUnit,
+    Price,
+    PriceSource,
+    TimeInForce,
+};
+
+pub struct Gateio {
+    api_url: String,
+    api_key: String,
+    secret: String,
+    endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    auto_client_order_id: bool,
+}
+
+#[allow(dead_code)]
+pub trait GateioTrait {
+    fn new(api_key: String, secret: String) -> Result<Self, String> where Self: Sized;
+    fn get_api_url(&self) -> &str;
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]>;
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]>;
+    fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
+}
+
+impl_endpoint_overrides!(Gateio);
+impl_client_settings!(Gateio, client);
+impl_public_requests!(Gateio, check_response);
+impl_exchange_options!(Gateio);
+impl_auto_client_order_id!(Gateio);
+
+impl Gateio {
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.client = self.client.with_clock(clock);
+        self
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
+        }
+        self
+    }
+
+    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    // HMAC-SHA512 hex of `METHOD\nPATH\nQUERY\nSHA512(BODY)\nTIMESTAMP`, where `path` starts
+    // with a slash and `timestamp` is in seconds
+    pub(crate) fn get_signature(
+        &self,
+        method: &str,
+        path: &str,
+        query: &str,
+        body: &str,
+        timestamp: &str
+    ) -> Result<String, String> {
+        let body_hash = hex::encode(Sha512::digest(body.as_bytes()));
+        let payload = format!("{}\n{}\n{}\n{}\n{}", method, path, query, body_hash, timestamp);
+        hmac_hex::<Sha512>(&self.secret, &payload)
+    }
+
+    // GET and DELETE take their params in the query string, everything else in a JSON body.
+    // `path` is relative to the api url, e.g. an order's own path under `api/v4/spot/orders`.
+    async fn send_signed_req(
+        &self,
+        method: &str,
+        path: &str,
+        param: BTreeMap<&str, &str>
+    ) -> Result<Value, ExchangeError> {
+        let (query, body) = if method == "GET" || method == "DELETE" {
+            (get_query_string(param), BTreeMap::new())
+        } else {
+            (String::new(), param)
+        };
+        let body_string = if body.is_empty() {
+            String::new()
+        } else {
+            serde_json::to_string(&body).map_err(|e| e.to_string())?
+        };
+
+        let timestamp = (self.client.clock().now_millis() / 1000).to_string();
+        let signature = self.get_signature(
+            method,
+            &format!("/{}", path),
+            &query,
+            &body_string,
+            &timestamp
+        )?;
+
+        let mut uri = format!("{}{}", self.api_url, path);
+        if !query.is_empty() {
+            uri = format!("{}?{}", uri, query);
+        }
+        let request = build_request(
+            method,
+            &uri,
+            vec![
+                ("KEY".parse().unwrap(), &self.api_key),
+                ("SIGN".parse().unwrap(), &signature),
+                ("Timestamp".parse().unwrap(), &timestamp),
+                (ACCEPT, "application/json"),
+                (CONTENT_TYPE, "application/json")
+            ],
+            body
+        )?;
+
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}", self.api_url, base[1]);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
+
+        let coin_list = res
+            .as_array()
+            .ok_or("Response is not an array".to_string())?
+            .iter()
+            .filter_map(|pair| pair["id"].as_str().map(encode_symbol))
+            .collect::<Vec<String>>();
+
+        Ok(CoinList {
+            market: "Gateio".to_string(),
+            coin_list,
+        })
+    }
+}
+
+impl GateioTrait for Gateio {
+    fn new(api_key: String, secret: String) -> Result<Self, String> {
+        Gateio::validate_api_credentials(&api_key, &secret)?;
+
+        let endpoint = BTreeMap::from([
+            ("make_order".to_string(), ["POST".to_string(), "api/v4/spot/orders".to_string()]),
+            ("cancel_order".to_string(), ["DELETE".to_string(), "api/v4/spot/orders".to_string()]),
+            ("order_book".to_string(), ["GET".to_string(), "api/v4/spot/order_book".to_string()]),
+            ("current_price".to_string(), ["GET".to_string(), "api/v4/spot/tickers".to_string()]),
+            ("coin_list".to_string(), ["GET".to_string(), "api/v4/spot/currency_pairs".to_string()]),
+        ]);
+
+        Ok(Self {
+            api_url: "https://api.gateio.ws/".to_string(),
+            api_key,
+            secret,
+            endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            auto_client_order_id: false,
+        })
+    }
+
+    fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]> {
+        &self.endpoint
+    }
+
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]> {
+        self.endpoint.get(key)
+    }
+
+    async fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        self.send_signed_req(&base[0], &base[1], param).await
+    }
+}
+
+#[async_trait]
+impl Exchange for Gateio {
+    async fn place_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, async {
+            let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+                .map_err(|e| e.to_string())?;
+            let side = req["side"].as_str().unwrap_or_default();
+            let order_type = req["order_type"].as_str().unwrap_or_default();
+            let is_market = order_type == "market";
+            // Gate.io reads a market buy's amount as the total to spend in the quote currency
+            let amount = if is_market && side == "buy" {
+                if get_optional_str(&req["amount"]).is_some() {
+                    return Err(
+                        ExchangeError::InvalidRequest(
+                            "A market buy is sized by quote_amount, not amount".to_string()
+                        ).to_string()
+                    );
+                }
+                get_optional_str(&req["quote_amount"]).ok_or(
+                    ExchangeError::InvalidRequest("A market buy needs a quote_amount".to_string())
+                        .to_string()
+                )?
+            } else {
+                check_quote_amount(&req).map_err(|e| e.to_string())?;
+                req["amount"].as_str().unwrap_or_default().to_string()
+            };
+            let mut params = BTreeMap::from([
+                ("currency_pair", symbol.as_str()),
+                ("side", side),
+                ("type", order_type),
+                ("amount", amount.as_str()),
+            ]);
+            // Post-only is the `poc` (pending or cancelled) time in force. Market orders take
+            // no price and are rejected as gtc, the default, so they go out as ioc.
+            let time_in_force = match get_time_in_force(&req).map_err(|e| e.to_string())? {
+                (_, true) if is_market => {
+                    return Err(
+                        ExchangeError::InvalidRequest("A market order cannot be post-only".to_string())
+                            .to_string()
+                    );
+                }
+                (_, true) => Some("poc"),
+                (Some(TimeInForce::Ioc), _) => Some("ioc"),
+                (Some(TimeInForce::Fok), _) => Some("fok"),
+                (_, _) if is_market => Some("ioc"),
+                (Some(TimeInForce::Gtc), _) => Some("gtc"),
+                (None, _) => None,
+            };
+            if let Some(time_in_force) = time_in_force {
+                params.insert("time_in_force", time_in_force);
+            }
+            if !is_market {
+                params.insert("price", req["price"].as_str().unwrap_or_default());
+            }
+            let text = match get_client_order_id(&req, false) {
+                Some(client_order_id) => Some(to_text(&client_order_id)?),
+                // Generated ids are cut to fit next to the prefix
+                None if self.auto_client_order_id => {
+                    let generated = get_client_order_id(&req, true).unwrap_or_default();
+                    Some(to_text(&generated[..MAX_TEXT_LEN - 2])?)
+                }
+                None => None,
+            };
+            if let Some(text) = &text {
+                params.insert("text", text);
+            }
+
+            self.send_req_with_sign(params, "make_order").await.map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
+    }
+
+    // Orders are canceled through their own path, which also accepts the `t-` prefixed
+    // client order id in place of the order id
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, async {
+            let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+                .map_err(|e| e.to_string())?;
+            let order_id = match get_order_id_param(&req, "order_id", "client_order_id")? {
+                ("order_id", order_id) => order_id.to_string(),
+                (_, client_order_id) => to_text(client_order_id)?,
+            };
+            let base = self
+                .get_end_point_with_key("cancel_order")
+                .ok_or("Endpoint not found".to_string())?;
+            let params = BTreeMap::from([("currency_pair", symbol.as_str())]);
+
+            let path = format!("{}/{}", base[1], order_id);
+            self.send_signed_req(&base[0], &path, params).await.map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
+    }
+
+    async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([
+            ("currency_pair", symbol.as_str()),
+            ("limit", "30"),
+            ("with_id", "true"),
+        ]);
+
+        let res = self.send_public_req(params, "order_book").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+        parse_orderbook(&res, req["symbol"].as_str().unwrap_or_default())
+    }
+
+    fn get_name(&self) -> String {
+        "Gateio".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: false,
+            min_order_notional: None,
+        }
+    }
+
+    async fn get_current_price(&self, req: Value) -> Result<Price, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([("currency_pair", symbol.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        let current_price = res[0]["last"]
+            .as_str()
+            .ok_or(format!("last field is missing: {}", res))?;
+
+        Ok(Price {
+            exchange: "Gateio".to_string(),
+            symbol: req["symbol"].as_str().unwrap_or_default().to_string(),
+            price: current_price.to_string(),
+            source: PriceSource::Last,
+        })
+    }
+
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
+
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
+}
+
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}_{}", base, quote))
+}
+
+fn encode_symbol(symbol: &str) -> String {
+    symbol.replace('_', "/")
+}
+
+// Longest `text` Gate accepts, `t-` prefix included
+const MAX_TEXT_LEN: usize = 30;
+
+// Gate only accepts client order ids carried in `text` with a `t-` prefix
+fn to_text(client_order_id: &str) -> Result<String, ExchangeError> {
+    let text = if client_order_id.starts_with("t-") {
+        client_order_id.to_string()
+    } else {
+        format!("t-{}", client_order_id)
+    };
+    if text.len() > MAX_TEXT_LEN {
+        return Err(
+            ExchangeError::InvalidRequest(
+                format!(
+                    "client_order_id {:?} is longer than {} bytes with its t- prefix",
+                    client_order_id,
+                    MAX_TEXT_LEN
+                )
+            )
+        );
+    }
+    Ok(text)
+}
+
+// Errors come back with a non-2xx status as `{ "label", "message" }`
+pub(crate) fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let Some(label) = res.get("label").and_then(Value::as_str) else {
+        return Ok(());
+    };
+
+    let message = res["message"].as_str().unwrap_or_default().to_string();
+    match label {
+        "INVALID_KEY"
+        | "INVALID_SIGNATURE"
+        | "INVALID_CREDENTIALS"
+        | "MISSING_REQUIRED_HEADER"
+        | "REQUEST_EXPIRED"
+        | "IP_FORBIDDEN"
+        | "READ_ONLY"
+        | "FORBIDDEN" => Err(ExchangeError::Auth(format!("{}: {}", label, message))),
+        _ => Err(ExchangeError::ExchangeRejected { code: label.to_string(), message }),
+    }
+}
+
+// Asks and bids are `[price, size]` pairs; `current` is the snapshot time in milliseconds
+pub(crate) fn parse_orderbook(res: &Value, symbol: &str) -> Result<OrderBook, String> {
+    let asks = res["asks"].as_array().ok_or("Asks field is not an array")?;
+    let bids = res["bids"].as_array().ok_or("Bids field is not an array")?;
+
+    let orderbook_unit = (0..asks.len().max(bids.len()))
+        .map(|i| {
+            let ask = asks.get(i).unwrap_or(&Value::Null);
+            let bid = bids.get(i).unwrap_or(&Value::Null);
+            OrderBookUnit {
+                ask_price: ask[0].as_str().unwrap_or_default().to_string(),
+                bid_price: bid[0].as_str().unwrap_or_default().to_string(),
+                ask_size: ask[1].as_str().unwrap_or_default().to_string(),
+                bid_size: bid[1].as_str().unwrap_or_default().to_string(),
+            }
+        })
+        .collect::<Vec<OrderBookUnit>>();
+
+    Ok(OrderBook {
+        market: symbol.to_string(),
+        exchange: "Gateio".to_string(),
+        orderbook_unit,
+        timestamp: res["current"].as_u64(),
+    })
+}
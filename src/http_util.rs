@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use http::{ header::ACCEPT, HeaderName, Method, Request };
+use serde_json::Value;
+
+use crate::{ get_query_string, parse_response, ExchangeError, HttpClient };
+
+// The request every exchange hands to `HttpClient::send`. `body` holds the params, which the
+// client sends as JSON or form data depending on the Content-Type header.
+pub(crate) fn build_request<'a>(
+    method: &str,
+    uri: &str,
+    headers: Vec<(HeaderName, &str)>,
+    body: BTreeMap<&'a str, &'a str>
+) -> Result<Request<BTreeMap<&'a str, &'a str>>, ExchangeError> {
+    build_request_with_body(method, uri, headers, body)
+}
+
+// For bodies that are signed exactly as sent, e.g. ordered form pairs or a JSON value
+pub(crate) fn build_request_with_body<B>(
+    method: &str,
+    uri: &str,
+    headers: Vec<(HeaderName, &str)>,
+    body: B
+) -> Result<Request<B>, ExchangeError> {
+    let mut builder = Request::builder().method(parse_http_method(method)?).uri(uri);
+    for (key, value) in headers {
+        builder = builder.header(key, value);
+    }
+    builder.body(body).map_err(|e| ExchangeError::InvalidRequest(e.to_string()))
+}
+
+// Unsigned request with the params in the query string, for the public endpoints of the
+// exchanges that share `impl_public_requests!`
+pub(crate) async fn send_public_req(
+    client: &HttpClient,
+    api_url: &str,
+    endpoint: Option<&[String; 2]>,
+    param: BTreeMap<&str, &str>,
+    check: fn(&Value) -> Result<(), ExchangeError>
+) -> Result<Value, String> {
+    let base = endpoint.ok_or("Endpoint not found".to_string())?;
+
+    let mut uri = format!("{}{}", api_url, base[1]);
+    if !param.is_empty() {
+        uri = format!("{}?{}", uri, get_query_string(param));
+    }
+    let request = build_request(
+        base[0].as_str(),
+        &uri,
+        vec![(ACCEPT, "application/json")],
+        BTreeMap::new()
+    )?;
+
+    let response = client.send(request).await.map_err(|e| e.to_string())?;
+    Ok(parse_response(response, check)?)
+}
+
+// `http` takes any token as an extension method, so a typo such as "GETT" in an endpoint map
+// would otherwise go out as is
+pub(crate) fn parse_http_method(method: &str) -> Result<Method, ExchangeError> {
+    match method {
+        "GET" => Ok(Method::GET),
+        "POST" => Ok(Method::POST),
+        "PUT" => Ok(Method::PUT),
+        "DELETE" => Ok(Method::DELETE),
+        "PATCH" => Ok(Method::PATCH),
+        "HEAD" => Ok(Method::HEAD),
+        "OPTIONS" => Ok(Method::OPTIONS),
+        _ => Err(ExchangeError::InvalidRequest(format!("Unknown HTTP method {:?}", method))),
+    }
+}
@@ -0,0 +1,480 @@
+use std::collections::BTreeMap;
+use std::sync::{ Arc, Mutex };
+use async_trait::async_trait;
+use serde_json::Value;
+use http::header::{ ACCEPT, CONTENT_TYPE };
+use sha2::Sha256;
+use url::Url;
+
+use crate::http_util::build_request;
+use crate::sign::hmac_base64;
+use crate::{
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    encode_query_component,
+    format_utc_datetime,
+    get_client_order_id,
+    get_number_string,
+    get_optional_str,
+    get_order_id_param,
+    get_query_string,
+    get_time_in_force,
+    parse_json_body,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    with_request_timeout,
+    Capabilities,
+    Clock,
+    CoinList,
+    CoinListCache,
+    Exchange,
+    ExchangeError,
+    HttpClient,
+    OrderBook,
+    OrderBookUnit,
+    Price,
+    PriceSource,
+    TimeInForce,
+};
+
+pub struct Htx {
+    api_url: String,
+    api_key: String,
+    secret: String,
+    endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    auto_client_order_id: bool,
+    // Spot account orders are placed on, looked up on the first order unless set up front
+    account_id: Mutex<Option<String>>,
+}
+
+#[allow(dead_code)]
+pub trait HtxTrait {
+    fn new(api_key: String, secret: String) -> Result<Self, String> where Self: Sized;
+    fn get_api_url(&self) -> &str;
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]>;
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]>;
+    fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
+}
+
+impl_endpoint_overrides!(Htx);
+impl_client_settings!(Htx, client);
+impl_public_requests!(Htx, check_response);
+impl_exchange_options!(Htx);
+impl_auto_client_order_id!(Htx);
+
+impl Htx {
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
+    // Skips the account lookup before the first order
+    pub fn with_account_id(self, account_id: &str) -> Self {
+        *self.account_id.lock().unwrap() = Some(account_id.to_string());
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.client = self.client.with_clock(clock);
+        self
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
+        }
+        self
+    }
+
+    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    // Base64 HMAC-SHA256 of `METHOD\nHOST\nPATH\nQUERY`, where `host` is lowercase, `path`
+    // starts with a slash and `query` is sorted and urlencoded
+    pub(crate) fn get_signature(
+        &self,
+        method: &str,
+        host: &str,
+        path: &str,
+        query: &str
+    ) -> Result<String, String> {
+        hmac_base64::<Sha256>(&self.secret, &format!("{}\n{}\n{}\n{}", method, host, path, query))
+    }
+
+    // Query string carrying the auth params and their `Signature`. `timestamp` is UTC,
+    // e.g. "2017-05-11T15:19:30".
+    pub(crate) fn get_signed_query(
+        &self,
+        method: &str,
+        path: &str,
+        param: BTreeMap<&str, &str>,
+        timestamp: &str
+    ) -> Result<String, String> {
+        let host = Url::parse(&self.api_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .ok_or(format!("Invalid api url: {}", self.api_url))?;
+
+        let mut param = param;
+        param.insert("AccessKeyId", &self.api_key);
+        param.insert("SignatureMethod", "HmacSHA256");
+        param.insert("SignatureVersion", "2");
+        param.insert("Timestamp", timestamp);
+        let query = get_query_string(param);
+
+        let signature = self.get_signature(method, &host, path, &query)?;
+        Ok(format!("{}&Signature={}", query, encode_query_component(&signature)))
+    }
+
+    // Auth params always go in the query string. GET requests sign their own params along
+    // with them; everything else sends its params unsigned in a JSON body.
+    async fn send_signed_req(
+        &self,
+        method: &str,
+        path: &str,
+        param: BTreeMap<&str, &str>
+    ) -> Result<Value, ExchangeError> {
+        let (query_param, body) = if method == "GET" {
+            (param, BTreeMap::new())
+        } else {
+            (BTreeMap::new(), param)
+        };
+
+        let timestamp = format_utc_datetime(self.client.clock().now_millis());
+        let query = self.get_signed_query(method, &format!("/{}", path), query_param, &timestamp)?;
+
+        let uri = format!("{}{}?{}", self.api_url, path, query);
+        let request = build_request(
+            method,
+            &uri,
+            vec![(ACCEPT, "application/json"), (CONTENT_TYPE, "application/json")],
+            body
+        )?;
+
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
+    }
+
+    // The id of the first spot account, cached for later orders
+    async fn get_account_id(&self) -> Result<String, ExchangeError> {
+        let cached = self.account_id.lock().unwrap().clone();
+        if let Some(account_id) = cached {
+            return Ok(account_id);
+        }
+
+        let res = self.send_req_with_sign(BTreeMap::new(), "accounts").await?;
+        let account_id = res["data"]
+            .as_array()
+            .and_then(|accounts| accounts.iter().find(|account| account["type"] == "spot"))
+            .and_then(|account| get_number_string(&account["id"]))
+            .ok_or(format!("No spot account found: {}", res))?;
+
+        *self.account_id.lock().unwrap() = Some(account_id.clone());
+        Ok(account_id)
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}", self.api_url, base[1]);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        let coin_list = res["data"]
+            .as_array()
+            .ok_or("Data field is not an array".to_string())?
+            .iter()
+            .filter(|pair| pair["state"] == "online")
+            .filter_map(|pair| {
+                let base = pair["base-currency"].as_str()?;
+                let quote = pair["quote-currency"].as_str()?;
+                Some(format!("{}/{}", base.to_uppercase(), quote.to_uppercase()))
+            })
+            .collect::<Vec<String>>();
+
+        Ok(CoinList {
+            market: "Htx".to_string(),
+            coin_list,
+        })
+    }
+}
+
+impl HtxTrait for Htx {
+    fn new(api_key: String, secret: String) -> Result<Self, String> {
+        Htx::validate_api_credentials(&api_key, &secret)?;
+
+        let endpoint = BTreeMap::from([
+            ("make_order".to_string(), ["POST".to_string(), "v1/order/orders/place".to_string()]),
+            ("cancel_order".to_string(), ["POST".to_string(), "v1/order/orders".to_string()]),
+            (
+                "cancel_client_order".to_string(),
+                ["POST".to_string(), "v1/order/orders/submitCancelClientOrder".to_string()],
+            ),
+            ("order_book".to_string(), ["GET".to_string(), "market/depth".to_string()]),
+            ("current_price".to_string(), ["GET".to_string(), "market/detail/merged".to_string()]),
+            ("coin_list".to_string(), ["GET".to_string(), "v1/common/symbols".to_string()]),
+            ("accounts".to_string(), ["GET".to_string(), "v1/account/accounts".to_string()]),
+        ]);
+
+        Ok(Self {
+            api_url: "https://api.huobi.pro/".to_string(),
+            api_key,
+            secret,
+            endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            auto_client_order_id: false,
+            account_id: Mutex::new(None),
+        })
+    }
+
+    fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]> {
+        &self.endpoint
+    }
+
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]> {
+        self.endpoint.get(key)
+    }
+
+    async fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        self.send_signed_req(&base[0], &base[1], param).await
+    }
+}
+
+#[async_trait]
+impl Exchange for Htx {
+    // The order type combines side and type, e.g. `buy-limit`. Market buys are sized in the
+    // quote currency by the exchange, so `amount` is passed through as is.
+    async fn place_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, async {
+            let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+            // IOC, FOK and post-only limit orders are order types of their own
+            let order_type = req["order_type"].as_str().unwrap_or_default();
+            let order_type = match (order_type, get_time_in_force(&req)?) {
+                ("limit", (_, true)) => "limit-maker",
+                ("limit", (Some(TimeInForce::Ioc), _)) => "ioc",
+                ("limit", (Some(TimeInForce::Fok), _)) => "limit-fok",
+                (order_type, (None | Some(TimeInForce::Gtc), false)) => order_type,
+                _ => {
+                    return Err(
+                        ExchangeError::InvalidRequest(
+                            "time_in_force and post_only only apply to limit orders".to_string()
+                        )
+                    );
+                }
+            };
+            let order_type = format!("{}-{}", req["side"].as_str().unwrap_or_default(), order_type);
+            // HTX reads a market buy's amount as the total to spend in the quote currency
+            let amount = if order_type == "buy-market" {
+                if get_optional_str(&req["amount"]).is_some() {
+                    return Err(
+                        ExchangeError::InvalidRequest(
+                            "A market buy is sized by quote_amount, not amount".to_string()
+                        )
+                    );
+                }
+                get_optional_str(&req["quote_amount"]).ok_or(
+                    ExchangeError::InvalidRequest("A market buy needs a quote_amount".to_string())
+                )?
+            } else {
+                check_quote_amount(&req)?;
+                req["amount"].as_str().unwrap_or_default().to_string()
+            };
+            let account_id = self.get_account_id().await?;
+            let mut params = BTreeMap::from([
+                ("account-id", account_id.as_str()),
+                ("symbol", symbol.as_str()),
+                ("type", order_type.as_str()),
+                ("amount", amount.as_str()),
+            ]);
+            let price = get_optional_str(&req["price"]);
+            if let Some(price) = &price {
+                params.insert("price", price);
+            }
+            let client_order_id = get_client_order_id(&req, self.auto_client_order_id);
+            if let Some(client_order_id) = &client_order_id {
+                params.insert("client-order-id", client_order_id);
+            }
+
+            self.send_req_with_sign(params, "make_order").await
+        }).await.map_err(|e| e.to_string())
+    }
+
+    // Orders are canceled through their own path; client order ids have an endpoint of
+    // their own
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, async {
+            match get_order_id_param(&req, "order-id", "client-order-id")? {
+                ("order-id", order_id) => {
+                    let base = self
+                        .get_end_point_with_key("cancel_order")
+                        .ok_or("Endpoint not found".to_string())?;
+
+                    let path = format!("{}/{}/submitcancel", base[1], order_id);
+                    self.send_signed_req(&base[0], &path, BTreeMap::new()).await
+                }
+                (key, client_order_id) => {
+                    let params = BTreeMap::from([(key, client_order_id)]);
+                    self.send_req_with_sign(params, "cancel_client_order").await
+                }
+            }
+        }).await.map_err(|e| e.to_string())
+    }
+
+    async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([
+            ("symbol", symbol.as_str()),
+            ("type", "step0"),
+            ("depth", "20"),
+        ]);
+
+        let res = self.send_public_req(params, "order_book").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+        parse_orderbook(&res, req["symbol"].as_str().unwrap_or_default())
+    }
+
+    fn get_name(&self) -> String {
+        "Htx".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: false,
+            min_order_notional: None,
+        }
+    }
+
+    async fn get_current_price(&self, req: Value) -> Result<Price, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([("symbol", symbol.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        let current_price = get_number_string(&res["tick"]["close"])
+            .ok_or(format!("close field is missing: {}", res))?;
+
+        Ok(Price {
+            exchange: "Htx".to_string(),
+            symbol: req["symbol"].as_str().unwrap_or_default().to_string(),
+            price: current_price,
+            source: PriceSource::Last,
+        })
+    }
+
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
+
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
+}
+
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}{}", base, quote).to_lowercase())
+}
+
+// Errors come back as `{ "status": "error", "err-code", "err-msg" }`, usually with a 200 status
+pub(crate) fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    if res["status"] != "error" {
+        return Ok(());
+    }
+
+    let code = res["err-code"].as_str().unwrap_or_default();
+    let message = res["err-msg"].as_str().unwrap_or_default().to_string();
+    match code {
+        "api-signature-not-valid" | "api-signature-check-failed" | "login-required" => {
+            Err(ExchangeError::Auth(format!("{}: {}", code, message)))
+        }
+        _ => Err(ExchangeError::ExchangeRejected { code: code.to_string(), message }),
+    }
+}
+
+// Asks and bids under `tick` are `[price, size]` number pairs; `ts` is the snapshot time in
+// milliseconds. Every level is kept; past the end of the shorter side its price and size are
+// empty.
+pub(crate) fn parse_orderbook(res: &Value, symbol: &str) -> Result<OrderBook, String> {
+    let tick = &res["tick"];
+    let asks = tick["asks"].as_array().ok_or("Asks field is not an array")?;
+    let bids = tick["bids"].as_array().ok_or("Bids field is not an array")?;
+
+    let orderbook_unit = (0..asks.len().max(bids.len()))
+        .map(|i| {
+            let ask = asks.get(i).unwrap_or(&Value::Null);
+            let bid = bids.get(i).unwrap_or(&Value::Null);
+            OrderBookUnit {
+                ask_price: get_number_string(&ask[0]).unwrap_or_default(),
+                bid_price: get_number_string(&bid[0]).unwrap_or_default(),
+                ask_size: get_number_string(&ask[1]).unwrap_or_default(),
+                bid_size: get_number_string(&bid[1]).unwrap_or_default(),
+            }
+        })
+        .collect::<Vec<OrderBookUnit>>();
+
+    Ok(OrderBook {
+        market: symbol.to_string(),
+        exchange: "Htx".to_string(),
+        orderbook_unit,
+        timestamp: tick["ts"].as_u64().or(res["ts"].as_u64()),
+    })
+}
@@ -0,0 +1,407 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use serde_json::Value;
+use http::header::CONTENT_TYPE;
+use sha2::Sha256;
+
+use crate::http_util::build_request;
+use crate::sign::hmac_base64;
+use crate::{
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    get_client_order_id,
+    get_order_id_param,
+    get_query_string,
+    get_time_in_force,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    with_request_timeout,
+    Capabilities,
+    Clock,
+    CoinList,
+    CoinListCache,
+    Exchange,
+    ExchangeError,
+    HttpClient,
+    OrderBook,
+    OrderBookUnit,
+    Price,
+    PriceSource,
+};
+
+pub struct Kucoin {
+    api_url: String,
+    api_key: String,
+    secret: String,
+    passphrase: String,
+    endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+}
+
+#[allow(dead_code)]
+pub trait KucoinTrait {
+    fn new(api_key: String, secret: String, passphrase: String) -> Result<Self, String>
+        where Self: Sized;
+    fn get_api_url(&self) -> &str;
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]>;
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]>;
+    fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
+}
+
+impl_endpoint_overrides!(Kucoin);
+impl_client_settings!(Kucoin, client);
+impl_public_requests!(Kucoin, check_response);
+impl_exchange_options!(Kucoin);
+
+impl Kucoin {
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.client = self.client.with_clock(clock);
+        self
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
+        }
+        self
+    }
+
+    fn validate_api_credentials(
+        api_key: &str,
+        secret: &str,
+        passphrase: &str
+    ) -> Result<(), String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
+        }
+        if passphrase.is_empty() {
+            return Err("passphrase cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    // Base64 HMAC-SHA256 of `timestamp + method + endpoint + body`. `endpoint` starts with a
+    // slash and includes the query string, if any.
+    pub(crate) fn sign(
+        &self,
+        timestamp: &str,
+        method: &str,
+        endpoint: &str,
+        body: &str
+    ) -> Result<String, String> {
+        self.sign_payload(&format!("{}{}{}{}", timestamp, method, endpoint, body))
+    }
+
+    // Key version 2 sends the passphrase signed with the secret instead of in plain text
+    pub(crate) fn get_signed_passphrase(&self) -> Result<String, String> {
+        self.sign_payload(&self.passphrase)
+    }
+
+    fn sign_payload(&self, payload: &str) -> Result<String, String> {
+        hmac_base64::<Sha256>(&self.secret, payload)
+    }
+
+    // GET and DELETE take their params in the query string, everything else in a JSON body.
+    // `path` is relative to the api url, e.g. an order's own path under `api/v1/orders`.
+    async fn send_signed_req(
+        &self,
+        method: &str,
+        path: &str,
+        param: BTreeMap<&str, &str>
+    ) -> Result<Value, ExchangeError> {
+        let (endpoint, body) = if method == "GET" || method == "DELETE" {
+            if param.is_empty() {
+                (format!("/{}", path), BTreeMap::new())
+            } else {
+                (format!("/{}?{}", path, get_query_string(param)), BTreeMap::new())
+            }
+        } else {
+            (format!("/{}", path), param)
+        };
+        let body_string = if body.is_empty() {
+            String::new()
+        } else {
+            serde_json::to_string(&body).map_err(|e| e.to_string())?
+        };
+
+        let timestamp = self.client.clock().now_millis().to_string();
+        let signature = self.sign(&timestamp, method, &endpoint, &body_string)?;
+        let passphrase = self.get_signed_passphrase()?;
+
+        let uri = format!("{}{}", self.api_url.trim_end_matches('/'), endpoint);
+        let request = build_request(
+            method,
+            &uri,
+            vec![
+                ("KC-API-KEY".parse().unwrap(), &self.api_key),
+                ("KC-API-SIGN".parse().unwrap(), &signature),
+                ("KC-API-TIMESTAMP".parse().unwrap(), &timestamp),
+                ("KC-API-PASSPHRASE".parse().unwrap(), &passphrase),
+                ("KC-API-KEY-VERSION".parse().unwrap(), "2"),
+                (CONTENT_TYPE, "application/json")
+            ],
+            body
+        )?;
+
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let res = self.send_public_req(BTreeMap::new(), "coin_list").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        let coin_list = res["data"]
+            .as_array()
+            .ok_or("Response is not an array".to_string())?
+            .iter()
+            .filter_map(|symbol| symbol["symbol"].as_str().map(encode_symbol))
+            .collect::<Vec<String>>();
+
+        Ok(CoinList {
+            market: "Kucoin".to_string(),
+            coin_list,
+        })
+    }
+}
+
+impl KucoinTrait for Kucoin {
+    fn new(api_key: String, secret: String, passphrase: String) -> Result<Self, String> {
+        Kucoin::validate_api_credentials(&api_key, &secret, &passphrase)?;
+
+        let endpoint = BTreeMap::from([
+            ("make_order".to_string(), ["POST".to_string(), "api/v1/orders".to_string()]),
+            ("cancel_order".to_string(), ["DELETE".to_string(), "api/v1/orders".to_string()]),
+            (
+                "cancel_order_by_client_oid".to_string(),
+                ["DELETE".to_string(), "api/v1/order/client-order".to_string()],
+            ),
+            (
+                "order_book".to_string(),
+                ["GET".to_string(), "api/v1/market/orderbook/level2_20".to_string()],
+            ),
+            (
+                "current_price".to_string(),
+                ["GET".to_string(), "api/v1/market/orderbook/level1".to_string()],
+            ),
+            ("coin_list".to_string(), ["GET".to_string(), "api/v1/symbols".to_string()]),
+        ]);
+
+        Ok(Self {
+            api_url: "https://api.kucoin.com/".to_string(),
+            api_key,
+            secret,
+            passphrase,
+            endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+        })
+    }
+
+    fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]> {
+        &self.endpoint
+    }
+
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]> {
+        self.endpoint.get(key)
+    }
+
+    async fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        self.send_signed_req(&base[0], &base[1], param).await
+    }
+}
+
+#[async_trait]
+impl Exchange for Kucoin {
+    // KuCoin requires a clientOid on every order, so one is generated when missing
+    async fn place_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, async {
+            check_quote_amount(&req).map_err(|e| e.to_string())?;
+            let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+                .map_err(|e| e.to_string())?;
+            let client_order_id = get_client_order_id(&req, true).unwrap_or_default();
+            let mut params = BTreeMap::from([
+                ("clientOid", client_order_id.as_str()),
+                ("symbol", symbol.as_str()),
+                ("side", req["side"].as_str().unwrap_or_default()),
+                ("type", req["order_type"].as_str().unwrap_or_default()),
+                ("price", req["price"].as_str().unwrap_or_default()),
+                ("size", req["amount"].as_str().unwrap_or_default()),
+            ]);
+            let (time_in_force, post_only) = get_time_in_force(&req).map_err(|e| e.to_string())?;
+            if let Some(time_in_force) = time_in_force {
+                params.insert("timeInForce", time_in_force.as_str());
+            }
+            if post_only {
+                params.insert("postOnly", "true");
+            }
+
+            self.send_req_with_sign(params, "make_order").await.map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
+    }
+
+    // Orders are canceled through their own path; client order ids have a separate one
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, async {
+            let (endpoint_key, id) = get_order_id_param(
+                &req,
+                "cancel_order",
+                "cancel_order_by_client_oid"
+            )?;
+            let base = self
+                .get_end_point_with_key(endpoint_key)
+                .ok_or("Endpoint not found".to_string())?;
+
+            // Client order ids are the caller's own strings and may hold reserved characters
+            let path = format!("{}/{}", base[1], urlencoding::encode(id));
+            self.send_signed_req(&base[0], &path, BTreeMap::new()).await.map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
+    }
+
+    async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([("symbol", symbol.as_str())]);
+
+        let res = self.send_public_req(params, "order_book").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+        parse_orderbook(&res, req["symbol"].as_str().unwrap_or_default())
+    }
+
+    fn get_name(&self) -> String {
+        "Kucoin".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: true,
+            min_order_notional: None,
+        }
+    }
+
+    async fn get_current_price(&self, req: Value) -> Result<Price, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([("symbol", symbol.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        let current_price = res["data"]["price"]
+            .as_str()
+            .ok_or(format!("price field is missing: {}", res))?;
+
+        Ok(Price {
+            exchange: "Kucoin".to_string(),
+            symbol: req["symbol"].as_str().unwrap_or_default().to_string(),
+            price: current_price.to_string(),
+            source: PriceSource::Last,
+        })
+    }
+
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
+
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
+}
+
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}-{}", base, quote))
+}
+
+fn encode_symbol(symbol: &str) -> String {
+    symbol.replace('-', "/")
+}
+
+// Every response is wrapped as `{ "code", "msg", "data" }` with code "200000" on success
+pub(crate) fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let code = res["code"].as_str().unwrap_or_default();
+    if code == "200000" {
+        return Ok(());
+    }
+
+    let message = res["msg"].as_str().unwrap_or_default().to_string();
+    match code {
+        // Missing headers, bad timestamp, key, passphrase or signature, IP and permissions
+        "400001" | "400002" | "400003" | "400004" | "400005" | "400006" | "400007" => {
+            Err(ExchangeError::Auth(format!("{}: {}", code, message)))
+        }
+        _ => Err(ExchangeError::ExchangeRejected { code: code.to_string(), message }),
+    }
+}
+
+// Asks and bids are `[price, size]` pairs; `time` is the snapshot time in milliseconds
+pub(crate) fn parse_orderbook(res: &Value, symbol: &str) -> Result<OrderBook, String> {
+    let data = &res["data"];
+    let asks = data["asks"].as_array().ok_or("Asks field is not an array")?;
+    let bids = data["bids"].as_array().ok_or("Bids field is not an array")?;
+
+    let orderbook_unit = (0..asks.len().max(bids.len()))
+        .map(|i| {
+            let ask = asks.get(i).unwrap_or(&Value::Null);
+            let bid = bids.get(i).unwrap_or(&Value::Null);
+            OrderBookUnit {
+                ask_price: ask[0].as_str().unwrap_or_default().to_string(),
+                bid_price: bid[0].as_str().unwrap_or_default().to_string(),
+                ask_size: ask[1].as_str().unwrap_or_default().to_string(),
+                bid_size: bid[1].as_str().unwrap_or_default().to_string(),
+            }
+        })
+        .collect::<Vec<OrderBookUnit>>();
+
+    Ok(OrderBook {
+        market: symbol.to_string(),
+        exchange: "Kucoin".to_string(),
+        orderbook_unit,
+        timestamp: data["time"].as_u64(),
+    })
+}
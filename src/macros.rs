@@ -0,0 +1,156 @@
+// Endpoint overrides for an exchange that keeps its endpoints in an `endpoint` map of
+// key -> [method, path]
+macro_rules! impl_endpoint_overrides {
+    ($exchange:ty) => {
+        impl $exchange {
+            // Overrides the endpoint under `key`, or adds one, e.g. after the exchange moves a path
+            pub fn set_endpoint(
+                &mut self,
+                key: &str,
+                method: &str,
+                path: &str
+            ) -> Result<(), $crate::ExchangeError> {
+                self.endpoint.insert(key.to_string(), $crate::get_endpoint_override(method, path)?);
+                Ok(())
+            }
+
+            pub fn with_endpoint_override(
+                mut self,
+                key: &str,
+                method: &str,
+                path: &str
+            ) -> Result<Self, $crate::ExchangeError> {
+                self.set_endpoint(key, method, path)?;
+                Ok(self)
+            }
+        }
+    };
+}
+
+// Builder methods that pass http settings through to the exchange's `HttpClient`s. The first
+// client owns the request permits, any others share them.
+macro_rules! impl_client_settings {
+    ($exchange:ty, $client:ident $(, $other:ident)*) => {
+        impl $exchange {
+            // Errors of failed requests carry the redacted request and the raw response body
+            pub fn with_debug_errors(mut self) -> Self {
+                self.$client = self.$client.with_debug_errors(true);
+                $(self.$other = self.$other.with_debug_errors(true);)*
+                self
+            }
+
+            // Fails on a url that is not a valid http(s) or socks5 proxy
+            pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self, $crate::ExchangeError> {
+                self.$client = self.$client.with_proxy(proxy_url)?;
+                $(self.$other = self.$other.with_proxy(proxy_url)?;)*
+                Ok(self)
+            }
+
+            pub fn with_interceptor(
+                mut self,
+                interceptor: std::sync::Arc<dyn $crate::Interceptor>
+            ) -> Self {
+                $(self.$other = self.$other.with_interceptor(interceptor.clone());)*
+                self.$client = self.$client.with_interceptor(interceptor);
+                self
+            }
+
+            pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+                self.$client = self.$client.with_max_response_bytes(max_response_bytes);
+                $(self.$other = self.$other.with_max_response_bytes(max_response_bytes);)*
+                self
+            }
+
+            pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+                self.$client = self.$client.with_max_concurrent_requests(max_concurrent_requests);
+                $(self.$other = self.$other.with_request_permits_of(&self.$client);)*
+                self
+            }
+
+            pub fn with_redirect_policy(mut self, redirect_policy: $crate::RedirectPolicy) -> Self {
+                self.$client = self.$client.with_redirect_policy(redirect_policy);
+                $(self.$other = self.$other.with_redirect_policy(redirect_policy);)*
+                self
+            }
+
+            pub fn with_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+                self.$client = self.$client.with_pool_max_idle_per_host(pool_max_idle_per_host);
+                $(self.$other = self.$other.with_pool_max_idle_per_host(pool_max_idle_per_host);)*
+                self
+            }
+
+            pub fn with_pool_idle_timeout(mut self, pool_idle_timeout: std::time::Duration) -> Self {
+                self.$client = self.$client.with_pool_idle_timeout(pool_idle_timeout);
+                $(self.$other = self.$other.with_pool_idle_timeout(pool_idle_timeout);)*
+                self
+            }
+
+            pub fn with_tcp_keepalive(mut self, tcp_keepalive: std::time::Duration) -> Self {
+                self.$client = self.$client.with_tcp_keepalive(tcp_keepalive);
+                $(self.$other = self.$other.with_tcp_keepalive(tcp_keepalive);)*
+                self
+            }
+        }
+    };
+}
+
+// `send_public_req` for an exchange with `api_url`, `client` and `endpoint` fields, whose
+// public responses are checked by `$check`
+macro_rules! impl_public_requests {
+    ($exchange:ty, $check:path) => {
+        impl $exchange {
+            async fn send_public_req(
+                &self,
+                param: std::collections::BTreeMap<&str, &str>,
+                endpoint_key: &str
+            ) -> Result<serde_json::Value, String> {
+                $crate::http_util::send_public_req(
+                    &self.client,
+                    &self.api_url,
+                    self.endpoint.get(endpoint_key),
+                    param,
+                    $check
+                ).await
+            }
+        }
+    };
+}
+
+// Builder methods for options every exchange keeps in `coin_list_cache`, `fuzzy_symbols` and
+// `safe_mode` fields
+macro_rules! impl_exchange_options {
+    ($exchange:ty) => {
+        impl $exchange {
+            // `CoinListCache::new(Duration::ZERO)` fetches the list on every call
+            pub fn with_coin_list_cache(mut self, coin_list_cache: $crate::CoinListCache) -> Self {
+                self.coin_list_cache = coin_list_cache;
+                self
+            }
+
+            // Accepts symbols as users type them, e.g. "btcusdt", see `normalize_user_symbol`
+            pub fn with_fuzzy_symbols(mut self, fuzzy_symbols: bool) -> Self {
+                self.fuzzy_symbols = fuzzy_symbols;
+                self
+            }
+
+            // Orders, cancels and withdrawals fail with `ExchangeError::SafeModeBlocked`, unsent
+            pub fn with_safe_mode(mut self, safe_mode: bool) -> Self {
+                self.safe_mode = safe_mode;
+                self
+            }
+        }
+    };
+}
+
+// For an exchange that takes a client order id on orders, kept in an `auto_client_order_id` field
+macro_rules! impl_auto_client_order_id {
+    ($exchange:ty) => {
+        impl $exchange {
+            // Generates a client order id for every order placed without one
+            pub fn with_auto_client_order_id(mut self) -> Self {
+                self.auto_client_order_id = true;
+                self
+            }
+        }
+    };
+}
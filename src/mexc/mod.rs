@@ -0,0 +1,399 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use serde_json::Value;
+use http::header::{ ACCEPT, CONTENT_TYPE };
+use sha2::Sha256;
+
+use crate::http_util::build_request;
+use crate::sign::hmac_hex;
+use crate::{
+    canonicalize_params,
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    encode_params,
+    get_client_order_id,
+    get_optional_str,
+    get_order_id_param,
+    get_request_timestamp,
+    get_time_in_force,
+    parse_json_body,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    with_request_timeout,
+    Capabilities,
+    Clock,
+    CoinList,
+    CoinListCache,
+    Exchange,
+    ExchangeError,
+    HttpClient,
+    OrderBook,
+    OrderBookUnit,
+    Price,
+    PriceSource,
+    TimeInForce,
+};
+
+pub struct Mexc {
+    api_url: String,
+    api_key: String,
+    secret: String,
+    endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    auto_client_order_id: bool,
+}
+
+#[allow(dead_code)]
+pub trait MexcTrait {
+    fn new(api_key: String, secret: String) -> Result<Self, String> where Self: Sized;
+    fn get_api_url(&self) -> &str;
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]>;
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]>;
+    fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
+}
+
+impl_endpoint_overrides!(Mexc);
+impl_client_settings!(Mexc, client);
+impl_public_requests!(Mexc, check_response);
+impl_exchange_options!(Mexc);
+impl_auto_client_order_id!(Mexc);
+
+impl Mexc {
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.client = self.client.with_clock(clock);
+        self
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
+        }
+        self
+    }
+
+    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    // Hex HMAC-SHA256 of the params as `encode_params` sends them, the same scheme as Binance's
+    // HMAC keys, so they must already be canonical
+    pub(crate) fn get_signature(&self, params: &[(String, String)]) -> Result<String, String> {
+        hmac_hex::<Sha256>(&self.secret, &encode_params(params))
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}", self.api_url, base[1]);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        // Symbols report "1" while online; older responses said "ENABLED" instead
+        let coin_list = res["symbols"]
+            .as_array()
+            .ok_or("Symbols field is not an array".to_string())?
+            .iter()
+            .filter(|coin| coin["status"] == "1" || coin["status"] == "ENABLED")
+            .filter(|coin| coin["isSpotTradingAllowed"].as_bool().unwrap_or(true))
+            .filter_map(|coin| {
+                Some(format!("{}/{}", coin["baseAsset"].as_str()?, coin["quoteAsset"].as_str()?))
+            })
+            .collect::<Vec<String>>();
+
+        Ok(CoinList {
+            market: "Mexc".to_string(),
+            coin_list,
+        })
+    }
+}
+
+impl MexcTrait for Mexc {
+    fn new(api_key: String, secret: String) -> Result<Self, String> {
+        Mexc::validate_api_credentials(&api_key, &secret)?;
+
+        let endpoint = BTreeMap::from([
+            ("make_order".to_string(), ["POST".to_string(), "api/v3/order".to_string()]),
+            ("cancel_order".to_string(), ["DELETE".to_string(), "api/v3/order".to_string()]),
+            ("order_book".to_string(), ["GET".to_string(), "api/v3/depth".to_string()]),
+            ("current_price".to_string(), ["GET".to_string(), "api/v3/ticker/price".to_string()]),
+            ("coin_list".to_string(), ["GET".to_string(), "api/v3/exchangeInfo".to_string()]),
+        ]);
+
+        Ok(Self {
+            api_url: "https://api.mexc.com/".to_string(),
+            api_key,
+            secret,
+            endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            auto_client_order_id: false,
+        })
+    }
+
+    fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn get_end_point(&self) -> &BTreeMap<String, [String; 2]> {
+        &self.endpoint
+    }
+
+    fn get_end_point_with_key(&self, key: &str) -> Option<&[String; 2]> {
+        self.endpoint.get(key)
+    }
+
+    // Every method takes its params in the query string, with the signature last. The body
+    // stays empty: the exchange would sign a JSON `{}` along with the query.
+    async fn send_req_with_sign(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        let mut params = canonicalize_params(param);
+        let signature = self.get_signature(&params)?;
+        params.push(("signature".to_string(), signature));
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], encode_params(&params));
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![
+                ("X-MEXC-APIKEY".parse().unwrap(), &self.api_key),
+                (ACCEPT, "application/json"),
+                (CONTENT_TYPE, "application/x-www-form-urlencoded")
+            ],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
+    }
+}
+
+#[async_trait]
+impl Exchange for Mexc {
+    // A market order with a quote_amount is sized in the quote currency, e.g. "buy 100 USDT
+    // of BTC", as on Binance
+    async fn place_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, async {
+            // IOC, FOK and post-only limit orders are order types of their own
+            let order_type = req["order_type"].as_str().unwrap_or_default();
+            let is_limit = order_type.eq_ignore_ascii_case("LIMIT");
+            let order_type = match (get_time_in_force(&req)?, is_limit) {
+                ((_, true), true) => "LIMIT_MAKER",
+                ((Some(TimeInForce::Ioc), _), true) => "IMMEDIATE_OR_CANCEL",
+                ((Some(TimeInForce::Fok), _), true) => "FILL_OR_KILL",
+                ((None | Some(TimeInForce::Gtc), false), _) => order_type,
+                _ => {
+                    return Err(
+                        ExchangeError::InvalidRequest(
+                            "time_in_force and post_only only apply to limit orders".to_string()
+                        )
+                    );
+                }
+            };
+            let quote_order_qty = get_optional_str(&req["quote_amount"]).filter(|_| {
+                order_type.eq_ignore_ascii_case("MARKET")
+            });
+            if quote_order_qty.is_none() {
+                check_quote_amount(&req)?;
+            }
+
+            let timestamp = get_request_timestamp(&req, self.client.clock());
+            let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+            let mut params = BTreeMap::from([
+                ("symbol", symbol.as_str()),
+                ("side", req["side"].as_str().unwrap_or_default()),
+                ("type", order_type),
+                ("timestamp", &timestamp),
+            ]);
+            match &quote_order_qty {
+                Some(quote_order_qty) => {
+                    params.insert("quoteOrderQty", quote_order_qty);
+                }
+                None => {
+                    params.insert("quantity", req["amount"].as_str().unwrap_or_default());
+                }
+            }
+            let price = get_optional_str(&req["price"]);
+            if let Some(price) = &price {
+                params.insert("price", price);
+            }
+            let client_order_id = get_client_order_id(&req, self.auto_client_order_id);
+            if let Some(client_order_id) = &client_order_id {
+                params.insert("newClientOrderId", client_order_id);
+            }
+
+            self.send_req_with_sign(params, "make_order").await
+        }).await.map_err(|e| e.to_string())
+    }
+
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, async {
+            let timestamp = get_request_timestamp(&req, self.client.clock());
+            let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+            let (id_key, id) = get_order_id_param(&req, "orderId", "origClientOrderId")?;
+            let params = BTreeMap::from([
+                ("symbol", symbol.as_str()),
+                (id_key, id),
+                ("timestamp", &timestamp),
+            ]);
+
+            self.send_req_with_sign(params, "cancel_order").await
+        }).await.map_err(|e| e.to_string())
+    }
+
+    // `limit` is optional; the exchange defaults to 100 levels
+    async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let limit = req["limit"].as_u64().map(|limit| limit.to_string());
+        let mut params = BTreeMap::from([("symbol", symbol.as_str())]);
+        if let Some(limit) = &limit {
+            params.insert("limit", limit);
+        }
+
+        let res = self.send_public_req(params, "order_book").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+        parse_orderbook(&res, req["symbol"].as_str().unwrap_or_default())
+    }
+
+    fn get_name(&self) -> String {
+        "Mexc".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: false,
+            min_order_notional: None,
+        }
+    }
+
+    async fn get_current_price(&self, req: Value) -> Result<Price, String> {
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let params = BTreeMap::from([("symbol", symbol.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res).map_err(|e| e.to_string())?;
+
+        let current_price = res["price"]
+            .as_str()
+            .ok_or(format!("price field is missing: {}", res))?;
+
+        Ok(Price {
+            exchange: "Mexc".to_string(),
+            symbol: req["symbol"].as_str().unwrap_or_default().to_string(),
+            price: current_price.to_string(),
+            source: PriceSource::Last,
+        })
+    }
+
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
+
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
+}
+
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}{}", base, quote))
+}
+
+// Errors come back as `{ "code", "msg" }`. Some successful responses carry code 0 or 200.
+pub(crate) fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let Some(code) = res.get("code").and_then(Value::as_i64) else {
+        return Ok(());
+    };
+    if code == 0 || code == 200 {
+        return Ok(());
+    }
+
+    let message = res["msg"].as_str().unwrap_or_default().to_string();
+    match code {
+        // Invalid API key, invalid signature and API key info invalid
+        700001 | 700002 | 10072 => Err(ExchangeError::Auth(format!("{}: {}", code, message))),
+        _ => Err(ExchangeError::ExchangeRejected { code: code.to_string(), message }),
+    }
+}
+
+// Asks and bids are `[price, size]` string pairs; `timestamp` is the snapshot time in
+// milliseconds
+pub(crate) fn parse_orderbook(res: &Value, symbol: &str) -> Result<OrderBook, String> {
+    let asks = res["asks"].as_array().ok_or("Asks field is not an array")?;
+    let bids = res["bids"].as_array().ok_or("Bids field is not an array")?;
+
+    let orderbook_unit = (0..asks.len().max(bids.len()))
+        .map(|i| {
+            let ask = asks.get(i).unwrap_or(&Value::Null);
+            let bid = bids.get(i).unwrap_or(&Value::Null);
+            OrderBookUnit {
+                ask_price: ask[0].as_str().unwrap_or_default().to_string(),
+                bid_price: bid[0].as_str().unwrap_or_default().to_string(),
+                ask_size: ask[1].as_str().unwrap_or_default().to_string(),
+                bid_size: bid[1].as_str().unwrap_or_default().to_string(),
+            }
+        })
+        .collect::<Vec<OrderBookUnit>>();
+
+    Ok(OrderBook {
+        market: symbol.to_string(),
+        exchange: "Mexc".to_string(),
+        orderbook_unit,
+        timestamp: res["timestamp"].as_u64(),
+    })
+}
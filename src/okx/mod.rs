@@ -1,28 +1,115 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use async_trait::async_trait;
-use http::{ header::{ ACCEPT, CONTENT_TYPE }, Request };
-use serde_json::{ from_slice, Value };
-use sha2::{ Digest, Sha256 };
-use hmac::{ Hmac, Mac };
-use base64::{ Engine as _, engine::general_purpose };
+use futures::stream::{ self, BoxStream, StreamExt };
+use http::{ header::{ ACCEPT, CONTENT_TYPE }, Version };
+use serde_json::{ json, Value };
+use sha2::Sha256;
 
+use crate::stream::{ WsLogin, WsManager };
+use crate::http_util::{ build_request, build_request_with_body };
+use crate::sign::hmac_base64;
 use crate::{
-    get_current_timestamp_in_millis,
+    canonicalize_params,
+    check_close_all_confirmed,
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    check_trigger_price,
+    encode_params,
+    filter_transfers,
+    format_utc_datetime,
+    get_client_order_id,
+    get_optional_str,
+    get_order_id_param,
     get_query_string,
-    send,
+    get_request_timestamp,
+    get_time_in_force,
+    parse_json_body,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    supports_method,
+    top_by_volume,
+    with_request_timeout,
+    AccountInfo,
+    Balance,
+    Bbo,
+    Capabilities,
+    Clock,
+    CloseAllReport,
     CoinList,
+    CoinListCache,
+    DepositAddress,
     Exchange,
+    ExchangeError,
+    FeeSchedule,
+    HttpClient,
+    Market,
+    Order,
     OrderBook,
     OrderBookUnit,
+    OrderState,
     Price,
+    PriceSource,
+    Quote,
+    Side,
+    SymbolInfo,
+    SymbolInfoCache,
+    Ticker,
+    TimeInForce,
+    Trade,
+    Transfer,
+    TransferKind,
+    TransferStatus,
+    TriggerType,
+    Wallet,
 };
 
+// Deposit and withdrawal history pages hold at most 100 records, newest first
+const TRANSFER_PAGE_SIZE: usize = 100;
+
+// Optional `Exchange` methods implemented here, reported by `supports`
+const IMPLEMENTED_METHODS: &[&str] = &[
+    "amend_order",
+    "cancel_all_orders",
+    "check_auth",
+    "close_all",
+    "get_account_info",
+    "get_all_balances",
+    "get_best_quote",
+    "get_deposit_address",
+    "get_full_order_book",
+    "get_mark_price",
+    "get_order",
+    "get_recent_trades",
+    "get_ticker",
+    "get_trading_fees",
+    "get_transfer_history",
+    "ping",
+    "place_conditional_order",
+    "stream_bbo",
+    "stream_user_orders",
+    "symbol_info",
+    "top_markets_by_volume",
+];
+
 pub struct Okx {
     api_url: String,
+    ws_url: String,
+    // Private channels such as order updates need a login, which the public endpoint rejects
+    private_ws_url: String,
+    max_reconnect_attempts: Option<u32>,
     api_key: String,
     secret: String,
     passphrase: String,
-    endpoint: BTreeMap<String, [String; 2]>,
+    pub(crate) endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    symbol_info_cache: SymbolInfoCache,
+    auto_client_order_id: bool,
 }
 
 #[allow(dead_code)]
@@ -36,59 +123,289 @@ pub trait OkxTrait {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> impl std::future::Future<Output = Result<Value, String>> + Send;
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
 }
 
+impl_endpoint_overrides!(Okx);
+impl_client_settings!(Okx, client);
+impl_public_requests!(Okx, check_response);
+impl_exchange_options!(Okx);
+impl_auto_client_order_id!(Okx);
+
 impl Okx {
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
+    pub fn with_ws_url(mut self, ws_url: &str) -> Self {
+        self.ws_url = ws_url.to_string();
+        self
+    }
+
+    pub fn with_private_ws_url(mut self, private_ws_url: &str) -> Self {
+        self.private_ws_url = private_ws_url.to_string();
+        self
+    }
+
+    // Streams end with an error after this many consecutive failed reconnects
+    pub fn with_max_reconnect_attempts(mut self, max_reconnect_attempts: u32) -> Self {
+        self.max_reconnect_attempts = Some(max_reconnect_attempts);
+        self
+    }
+
+    // Replaces the default cache (one hour TTL), e.g. to share one across handles
+    pub fn with_symbol_info_cache(mut self, symbol_info_cache: SymbolInfoCache) -> Self {
+        self.symbol_info_cache = symbol_info_cache;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.client = self.client.with_clock(clock);
+        self
+    }
+
+    // The API hosts speak HTTP/2, which lets concurrent requests share one connection
+    pub fn with_http2_prior_knowledge(mut self, http2_prior_knowledge: bool) -> Self {
+        self.client = self.client.with_http2_prior_knowledge(http2_prior_knowledge);
+        self
+    }
+
+    pub fn negotiated_http_version(&self) -> Option<Version> {
+        self.client.negotiated_version()
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
+        }
+        self
+    }
+
     fn validate_api_credentials(
         api_key: &str,
         secret: &str,
         passphrase: &str
     ) -> Result<(), String> {
-        if api_key.is_empty() || secret.is_empty() || passphrase.is_empty() {
-            return Err("API key, Secret, and Passphrase cannot be empty".to_string());
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
+        }
+        if passphrase.is_empty() {
+            return Err("passphrase cannot be empty".to_string());
         }
         Ok(())
     }
 
-    fn create_hmac_key(&self) -> Result<Hmac<Sha256>, String> {
-        Hmac::new_from_slice(self.secret.as_bytes()).map_err(|e| e.to_string())
+    fn get_signed_headers<'a>(
+        &'a self,
+        authorization: &'a str,
+        timestamp: &'a str
+    ) -> Vec<(http::HeaderName, &'a str)> {
+        vec![
+            ("OK-ACCESS-KEY".parse().unwrap(), &self.api_key),
+            ("OK-ACCESS-SIGN".parse().unwrap(), authorization),
+            ("OK-ACCESS-TIMESTAMP".parse().unwrap(), timestamp),
+            ("OK-ACCESS-PASSPHRASE".parse().unwrap(), &self.passphrase),
+            (CONTENT_TYPE, "application/json")
+        ]
     }
 
-    fn build_request<'a>(
-        &'a self,
+    // Signs the request path and body from `get_signed_payload`, which are sent as they are
+    pub(crate) fn get_signature(
+        &self,
+        timestamp: &str,
         method: &str,
-        uri: &str,
-        headers: Vec<(http::HeaderName, &str)>,
-        body: BTreeMap<&'a str, &'a str>
-    ) -> Result<Request<BTreeMap<&str, &str>>, String> {
-        let mut builder = Request::builder().method(method).uri(uri);
-        for (key, value) in headers {
-            builder = builder.header(key, value);
-        }
-        builder.body(body).map_err(|e| e.to_string())
+        request_path: &str,
+        body: Option<&Value>
+    ) -> Result<String, String> {
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        self.sign(timestamp, method, request_path, &body)
     }
 
-    fn get_signature(
+    fn sign(
         &self,
-        params: &BTreeMap<&str, &str>,
         timestamp: &str,
         method: &str,
-        endpoint: &str
+        request_path: &str,
+        body: &str
     ) -> Result<String, String> {
-        let query_string = params
-            .iter()
-            .map(|(key, value)| format!("{}={}", key, value))
-            .collect::<Vec<String>>()
-            .join("&");
+        hmac_base64::<Sha256>(&self.secret, &(timestamp.to_string() + method + request_path + body))
+    }
 
-        let mut mac = self.create_hmac_key()?;
-        mac.update((timestamp.to_string() + method + endpoint + "?" + &query_string).as_bytes());
+    // Signs with the given timestamp instead of the live clock so a request can be
+    // reproduced exactly.
+    async fn send_req_with_sign_at(
+        &self,
+        param: BTreeMap<&str, &str>,
+        endpoint_key: &str,
+        timestamp: &str
+    ) -> Result<Value, ExchangeError> {
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
 
-        let hmac_bytes = mac.finalize().into_bytes();
-        let b64 = general_purpose::STANDARD.encode(hmac_bytes);
+        // The path and body are signed exactly as they are sent
+        let (request_path, body) = get_signed_payload(&param, &base[0], &format!("/{}", base[1]));
+        let authorization = self.get_signature(timestamp, &base[0], &request_path, body.as_ref())?;
 
-        Ok(b64)
+        let uri = format!("{}{}", self.api_url, request_path.trim_start_matches('/'));
+        let request = build_request_with_body(
+            base[0].as_str(),
+            &uri,
+            self.get_signed_headers(&authorization, timestamp),
+            body.unwrap_or(Value::Null)
+        )?;
+
+        // Failures still come back as HTTP 200, so the code field decides
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
+    }
+
+    // Batch endpoints take a JSON array of orders instead of a single parameter map
+    async fn send_batch_with_sign(
+        &self,
+        orders: Vec<BTreeMap<&str, &str>>,
+        endpoint_key: &str
+    ) -> Result<Value, String> {
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+
+        let timestamp = get_access_timestamp(&Value::Null, self.client.clock());
+        let body = serde_json::to_string(&orders).map_err(|e| e.to_string())?;
+        let authorization = self.sign(&timestamp, &base[0], &format!("/{}", base[1]), &body)?;
+
+        let uri = format!("{}{}", self.api_url, base[1]);
+        let request = build_request_with_body(
+            base[0].as_str(),
+            &uri,
+            self.get_signed_headers(&authorization, &timestamp),
+            orders
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        Ok(parse_response(response, check_response)?)
+    }
+
+    // Pages back through the history, each page `after` the oldest record of the one before,
+    // until a page comes back short
+    async fn fetch_transfer_pages(
+        &self,
+        params: &BTreeMap<&str, &str>,
+        endpoint_key: &str,
+        kind: TransferKind
+    ) -> Result<Vec<Transfer>, ExchangeError> {
+        let mut transfers = Vec::new();
+        let mut after = params.get("after").map(|after| after.to_string());
+        loop {
+            let mut page_params = params.clone();
+            if let Some(after) = &after {
+                page_params.insert("after", after);
+            }
+            let res = self.send_req_with_sign(page_params, endpoint_key).await?;
+            let page = parse_transfer_history(&res, kind)?;
+            let oldest = page.iter().map(|transfer| transfer.timestamp).min();
+            let full = page.len() >= TRANSFER_PAGE_SIZE;
+            transfers.extend(page);
+            match oldest {
+                Some(oldest) if full => {
+                    after = Some(oldest.to_string());
+                }
+                _ => {
+                    return Ok(transfers);
+                }
+            }
+        }
+    }
+
+    // (canceled ids, one "order id: reason" entry per order left open). A failed batch does
+    // not stop the next one; only failing to list the open orders is an error.
+    async fn cancel_open_orders(
+        &self,
+        params: BTreeMap<&str, &str>
+    ) -> Result<(Vec<String>, Vec<String>), ExchangeError> {
+        let open_orders = self.send_req_with_sign(params, "open_orders").await?;
+        let open_orders = open_orders["data"]
+            .as_array()
+            .ok_or(ExchangeError::Parse(open_orders.to_string()))?;
+
+        // cancel-batch-orders accepts at most 20 orders per request
+        let ord_id = |order: &Value| order["ordId"].as_str().unwrap_or_default().to_string();
+        let mut canceled = Vec::new();
+        let mut failed = Vec::new();
+        for chunk in open_orders.chunks(20) {
+            let orders = chunk
+                .iter()
+                .map(|order| {
+                    BTreeMap::from([
+                        ("instId", order["instId"].as_str().unwrap_or_default()),
+                        ("ordId", order["ordId"].as_str().unwrap_or_default()),
+                    ])
+                })
+                .collect::<Vec<BTreeMap<&str, &str>>>();
+
+            let res = match self.send_batch_with_sign(orders, "cancel_batch_orders").await {
+                Ok(res) => res,
+                Err(e) => {
+                    failed.extend(chunk.iter().map(|order| format!("{}: {}", ord_id(order), e)));
+                    continue;
+                }
+            };
+            // Each order reports its own outcome, even when the batch as a whole failed
+            for order in res["data"].as_array().into_iter().flatten() {
+                if order["sCode"] == "0" {
+                    canceled.push(ord_id(order));
+                } else {
+                    let reason = order["sMsg"].as_str().unwrap_or_default();
+                    failed.push(format!("{}: {}", ord_id(order), reason));
+                }
+            }
+        }
+        Ok((canceled, failed))
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let params = BTreeMap::from([("instType", "SPOT")]);
+
+        let query_string = get_query_string(params);
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
+
+        // Parsing response to create CoinList struct
+        let market = "Okx".to_string();
+        let coin_list = res["data"]
+            .as_array()
+            .ok_or("Response is not an array".to_string())?
+            .iter()
+            .filter_map(|coin| coin["instId"].as_str().map(encode_symbol))
+            .collect::<Vec<String>>();
+
+        let coin_list_struct = CoinList {
+            market,
+            coin_list,
+        };
+
+        Ok(coin_list_struct)
     }
 }
 
@@ -105,14 +422,59 @@ impl OkxTrait for Okx {
             ("order_book".to_string(), ["GET".to_string(), "api/v5/market/books-full".to_string()]),
             ("current_price".to_string(), ["GET".to_string(), "api/v5/market/ticker".to_string()]),
             ("coin_list".to_string(), ["GET".to_string(), "api/v5/public/instruments".to_string()]),
+            ("tickers".to_string(), ["GET".to_string(), "api/v5/market/tickers".to_string()]),
+            ("recent_trades".to_string(), ["GET".to_string(), "api/v5/market/trades".to_string()]),
+            ("server_time".to_string(), ["GET".to_string(), "api/v5/public/time".to_string()]),
+            ("mark_price".to_string(), ["GET".to_string(), "api/v5/public/mark-price".to_string()]),
+            (
+                "open_orders".to_string(),
+                ["GET".to_string(), "api/v5/trade/orders-pending".to_string()],
+            ),
+            (
+                "cancel_batch_orders".to_string(),
+                ["POST".to_string(), "api/v5/trade/cancel-batch-orders".to_string()],
+            ),
+            (
+                "deposit_address".to_string(),
+                ["GET".to_string(), "api/v5/asset/deposit-address".to_string()],
+            ),
+            (
+                "deposit_history".to_string(),
+                ["GET".to_string(), "api/v5/asset/deposit-history".to_string()],
+            ),
+            (
+                "withdrawal_history".to_string(),
+                ["GET".to_string(), "api/v5/asset/withdrawal-history".to_string()],
+            ),
+            ("get_order".to_string(), ["GET".to_string(), "api/v5/trade/order".to_string()]),
+            ("amend_order".to_string(), ["POST".to_string(), "api/v5/trade/amend-order".to_string()]),
+            ("algo_order".to_string(), ["POST".to_string(), "api/v5/trade/order-algo".to_string()]),
+            ("account_balance".to_string(), ["GET".to_string(), "api/v5/account/balance".to_string()]),
+            ("funding_balance".to_string(), ["GET".to_string(), "api/v5/asset/balances".to_string()]),
+            ("account_config".to_string(), ["GET".to_string(), "api/v5/account/config".to_string()]),
+            ("trade_fee".to_string(), ["GET".to_string(), "api/v5/account/trade-fee".to_string()]),
+            ("positions".to_string(), ["GET".to_string(), "api/v5/account/positions".to_string()]),
+            (
+                "close_position".to_string(),
+                ["POST".to_string(), "api/v5/trade/close-position".to_string()],
+            ),
         ]);
 
         Ok(Self {
             api_url: "https://www.okx.com/".to_string(),
+            ws_url: "wss://ws.okx.com:8443/ws/v5/public".to_string(),
+            private_ws_url: "wss://ws.okx.com:8443/ws/v5/private".to_string(),
+            max_reconnect_attempts: None,
             api_key,
             secret,
             passphrase,
             endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            symbol_info_cache: SymbolInfoCache::default(),
+            auto_client_order_id: false,
         })
     }
 
@@ -132,65 +494,143 @@ impl OkxTrait for Okx {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> Result<Value, String> {
-        let timestamp = get_current_timestamp_in_millis().to_string();
-        let authorization = self.get_signature(&param, &timestamp, "POST", endpoint_key)?;
-
-        let base = self
-            .get_end_point_with_key(endpoint_key)
-            .ok_or("Endpoint not found".to_string())?;
-
-        let uri = format!("{}{}", self.api_url, base[1]);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![
-                ("OK-ACCESS-KEY".parse().unwrap(), &self.api_key),
-                ("OK-ACCESS-SIGN".parse().unwrap(), &authorization),
-                ("OK-ACCESS-TIMESTAMP".parse().unwrap(), &timestamp),
-                ("OK-ACCESS-PASSPHRASE".parse().unwrap(), &self.passphrase),
-                (CONTENT_TYPE, "application/json")
-            ],
-            param
-        )?;
-
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        from_slice(&body).map_err(|e| e.to_string())
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let timestamp = get_access_timestamp(&Value::Null, self.client.clock());
+        self.send_req_with_sign_at(param, endpoint_key, &timestamp).await
     }
 }
 
 #[async_trait]
 impl Exchange for Okx {
     async fn place_order(&self, req: Value) -> Result<Value, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default()); // 심볼 파싱
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, async {
+            check_quote_amount(&req).map_err(|e| e.to_string())?;
+            let symbol = get_inst_id(&req).map_err(|e| e.to_string())?;
+            let td_mode = get_td_mode(&req).map_err(|e| e.to_string())?;
+            // Post-only, IOC and FOK are order types of their own
+            let ord_type = match get_time_in_force(&req).map_err(|e| e.to_string())? {
+                (_, true) => "post_only",
+                (Some(TimeInForce::Ioc), _) => "ioc",
+                (Some(TimeInForce::Fok), _) => "fok",
+                _ => req["order_type"].as_str().unwrap_or_default(),
+            };
+            let mut params = BTreeMap::from([
+                ("instId", symbol.as_str()),
+                ("side", req["side"].as_str().unwrap_or_default()),
+                ("ordType", ord_type),
+                ("px", req["price"].as_str().unwrap_or_default()),
+                ("sz", req["amount"].as_str().unwrap_or_default()),
+                ("tdMode", td_mode),
+            ]);
+            let client_order_id = get_client_order_id(&req, self.auto_client_order_id);
+            if let Some(client_order_id) = &client_order_id {
+                params.insert("clOrdId", client_order_id);
+            }
+
+            self.send_req_with_sign_at(params, "make_order", &get_access_timestamp(&req, self.client.clock())).await
+                .map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
+    }
+
+    // Conditional orders are algo orders, tracked by their algoId rather than an ordId
+    async fn place_conditional_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_conditional_order")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            let current = self.get_current_price(req.clone()).await?;
+            let trigger_type = check_trigger_price(&req, &current.price)?;
+            let (trigger_key, price_key) = match trigger_type {
+                TriggerType::StopLoss => ("slTriggerPx", "slOrdPx"),
+                TriggerType::TakeProfit => ("tpTriggerPx", "tpOrdPx"),
+            };
+
+            let inst_id = get_inst_id(&req)?;
+            // -1 executes the triggered order at market
+            let order_price = get_optional_str(&req["price"]).unwrap_or("-1".to_string());
+            let mut params = BTreeMap::from([
+                ("instId", inst_id.as_str()),
+                ("tdMode", get_td_mode(&req)?),
+                ("side", req["side"].as_str().unwrap_or_default()),
+                ("ordType", "conditional"),
+                ("sz", req["amount"].as_str().unwrap_or_default()),
+                (trigger_key, req["trigger_price"].as_str().unwrap_or_default()),
+                (price_key, order_price.as_str()),
+            ]);
+            let client_order_id = get_client_order_id(&req, self.auto_client_order_id);
+            if let Some(client_order_id) = &client_order_id {
+                params.insert("algoClOrdId", client_order_id);
+            }
+
+            let res = self.send_req_with_sign_at(
+                params,
+                "algo_order",
+                &get_access_timestamp(&req, self.client.clock())
+            ).await?;
+            parse_algo_order(&res, &req)
+        }).await
+    }
+
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, async {
+            let symbol = get_inst_id(&req).map_err(|e| e.to_string())?;
+            let (id_key, id) = get_order_id_param(&req, "ordId", "clOrdId")?;
+            let params = BTreeMap::from([
+                ("instId", symbol.as_str()),
+                (id_key, id),
+            ]);
+
+            self.send_req_with_sign_at(params, "cancel_order", &get_access_timestamp(&req, self.client.clock())).await
+                .map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())
+    }
+
+    async fn get_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        let symbol = req["symbol"].as_str().unwrap_or_default();
+        let market = get_inst_id(&req)?;
+        let (id_key, id) = get_order_id_param(&req, "ordId", "clOrdId")?;
         let params = BTreeMap::from([
-            ("instId", symbol.as_str()),
-            ("side", req["side"].as_str().unwrap_or_default()),
-            ("ordType", req["order_type"].as_str().unwrap_or_default()),
-            ("px", req["price"].as_str().unwrap_or_default()),
-            ("sz", req["amount"].as_str().unwrap_or_default()),
-            ("tdMode", "cash"),
+            ("instId", market.as_str()),
+            (id_key, id),
         ]);
 
-        self.send_req_with_sign(params, "make_order").await
+        let res = self.send_req_with_sign_at(
+            params,
+            "get_order",
+            &get_access_timestamp(&req, self.client.clock())
+        ).await?;
+        parse_order(&res, symbol)
     }
 
-    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default()); // 심볼 파싱
+    async fn amend_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "amend_order")?;
+        let symbol = get_inst_id(&req)?;
+        let (id_key, id) = get_order_id_param(&req, "ordId", "clOrdId")?;
         let params = BTreeMap::from([
             ("instId", symbol.as_str()),
-            ("ordId", req["order_id"].as_str().unwrap_or_default()),
+            (id_key, id),
+            ("newPx", req["price"].as_str().unwrap_or_default()),
+            ("newSz", req["amount"].as_str().unwrap_or_default()),
         ]);
 
-        self.send_req_with_sign(params, "cancel_order").await
+        let res = self.send_req_with_sign_at(
+            params,
+            "amend_order",
+            &get_access_timestamp(&req, self.client.clock())
+        ).await?;
+        parse_amended_order(&res, &req)
     }
 
+    // `limit` (up to 5000 levels) is optional and defaults to 30
     async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default()); // 심볼 파싱
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = get_inst_id(&req).map_err(|e| e.to_string())?;
+        let limit = req["limit"].as_u64().unwrap_or(30).to_string();
         let params = BTreeMap::from([
             ("instId", symbol.as_str()),
-            ("sz", "30"),
+            ("sz", limit.as_str()),
         ]);
 
         let base = self
@@ -200,17 +640,17 @@ impl Exchange for Okx {
         let query_string = get_query_string(params);
         let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
 
-        let request = self.build_request(
+        let request = build_request(
             base[0].as_str(),
             &uri,
             vec![(ACCEPT, "application/json")],
             BTreeMap::new()
         )?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
         let body = response.into_body();
 
-        let res: Value = from_slice(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
+        let res = parse_json_body(&body)?;
 
         let orderbook = parse_orderbook(res, req["symbol"].as_str().unwrap().to_string())?;
         Ok(orderbook)
@@ -220,8 +660,23 @@ impl Exchange for Okx {
         "Okx".to_string()
     }
 
+    fn supports(&self, method: &str) -> bool {
+        supports_method(IMPLEMENTED_METHODS, method)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: true,
+            min_order_notional: None,
+        }
+    }
+
     async fn get_current_price(&self, req: Value) -> Result<Price, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default()); // 심볼 파싱
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = get_inst_id(&req).map_err(|e| e.to_string())?;
         let params = BTreeMap::from([
             ("instId", symbol.as_str()),
             ("sz", "30"),
@@ -234,17 +689,17 @@ impl Exchange for Okx {
         let query_string = get_query_string(params);
         let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
 
-        let request = self.build_request(
+        let request = build_request(
             base[0].as_str(),
             &uri,
             vec![(ACCEPT, "application/json")],
             BTreeMap::new()
         )?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
         let body = response.into_body();
 
-        let res: Value = from_slice(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
+        let res = parse_json_body(&body)?;
 
         println!(">>>>>>>>>>>>>>>>>>>>> {:?}", res);
 
@@ -256,72 +711,878 @@ impl Exchange for Okx {
             exchange: "Okx".to_string(),
             symbol: symbol_name,
             price: current_price,
+            source: PriceSource::Last,
         };
 
         Ok(price)
     }
 
     async fn get_coin_list(&self) -> Result<CoinList, String> {
-        let params = BTreeMap::from([("instType", "SPOT")]);
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
 
-        let query_string = get_query_string(params);
-        let base = self
-            .get_end_point_with_key("coin_list")
-            .ok_or("Endpoint not found".to_string())?;
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
 
-        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
+    async fn get_mark_price(&self, symbol: &str) -> Result<Price, ExchangeError> {
+        let inst_id = get_inst_id(&json!({ "symbol": symbol, "inst_type": "SWAP" }))?;
+        let params = BTreeMap::from([
+            ("instType", "SWAP"),
+            ("instId", inst_id.as_str()),
+        ]);
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
+        let res = self.send_public_req(params, "mark_price").await?;
+        parse_mark_price(&res, symbol)
+    }
 
-        // Parsing response to create CoinList struct
-        let market = "Okx".to_string();
-        let coin_list = res["data"]
-            .as_array()
-            .ok_or("Response is not an array".to_string())?
-            .iter()
-            .filter_map(|coin| coin["instId"].as_str().map(|s| encode_symbol(s)))
-            .collect::<Vec<String>>();
+    // 5000 levels a side. books-full is limited to 5 requests per 2 seconds per IP.
+    async fn get_full_order_book(&self, symbol: &str) -> Result<OrderBook, ExchangeError> {
+        Ok(self.get_order_book(json!({ "symbol": symbol, "limit": 5000 })).await?)
+    }
 
-        let coin_list_struct = CoinList {
-            market,
-            coin_list,
-        };
+    async fn symbol_info(&self, symbol: &str) -> Result<SymbolInfo, ExchangeError> {
+        let key = ("Okx".to_string(), symbol.to_string());
+        self.symbol_info_cache.get_or_fetch(key, || async {
+            let inst_id = parse_symbol(symbol)?;
+            let params = BTreeMap::from([
+                ("instType", "SPOT"),
+                ("instId", inst_id.as_str()),
+            ]);
 
-        Ok(coin_list_struct)
+            let res = self.send_public_req(params, "coin_list").await?;
+            parse_symbol_info(&res, symbol)
+        }).await
+    }
+
+    // The ticker carries the best bid and ask with their sizes
+    async fn get_best_quote(&self, symbol: &str) -> Result<Quote, ExchangeError> {
+        let inst_id = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("instId", inst_id.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        parse_quote(&res)
+    }
+
+    async fn get_ticker(&self, symbol: &str) -> Result<Ticker, ExchangeError> {
+        let inst_id = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("instId", inst_id.as_str())]);
+
+        let res = self.send_public_req(params, "current_price").await?;
+        parse_ticker(&res, symbol)
+    }
+
+    async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>
+    ) -> Result<Vec<Trade>, ExchangeError> {
+        let inst_id = parse_symbol(symbol)?;
+        let limit = limit.map(|limit| limit.to_string());
+        let mut params = BTreeMap::from([("instId", inst_id.as_str())]);
+        if let Some(limit) = limit.as_deref() {
+            params.insert("limit", limit);
+        }
+
+        let res = self.send_public_req(params, "recent_trades").await?;
+        parse_recent_trades(&res, symbol)
+    }
+
+    async fn ping(&self) -> Result<(), ExchangeError> {
+        let res = self.send_public_req(BTreeMap::new(), "server_time").await?;
+        check_response(&res)
+    }
+
+    async fn check_auth(&self) -> Result<(), ExchangeError> {
+        self.send_req_with_sign(BTreeMap::new(), "account_balance").await?;
+        Ok(())
+    }
+
+    async fn top_markets_by_volume(
+        &self,
+        quote: &str,
+        n: usize
+    ) -> Result<Vec<Market>, ExchangeError> {
+        let res = self.send_public_req(BTreeMap::from([("instType", "SPOT")]), "tickers").await?;
+
+        let markets = parse_markets(&res, quote)?;
+        Ok(top_by_volume(markets, n))
+    }
+
+    async fn get_deposit_address(&self, req: Value) -> Result<DepositAddress, ExchangeError> {
+        let params = BTreeMap::from([("ccy", req["currency"].as_str().unwrap_or_default())]);
+
+        let res = self.send_req_with_sign(params, "deposit_address").await?;
+        parse_deposit_address(&res, req["network"].as_str())
+    }
+
+    // OKX pages newest first, 100 records per page. `before`/`after` are exclusive, so the
+    // range is widened by a millisecond on each side.
+    async fn get_transfer_history(&self, req: Value) -> Result<Vec<Transfer>, ExchangeError> {
+        let before = req["start_time"]
+            .as_u64()
+            .map(|start_time| start_time.saturating_sub(1).to_string());
+        let after = req["end_time"].as_u64().map(|end_time| end_time.saturating_add(1).to_string());
+        let mut params = BTreeMap::new();
+        if let Some(currency) = req["currency"].as_str() {
+            params.insert("ccy", currency);
+        }
+        if let Some(before) = &before {
+            params.insert("before", before);
+        }
+        if let Some(after) = &after {
+            params.insert("after", after);
+        }
+
+        let mut transfers = self.fetch_transfer_pages(
+            &params,
+            "deposit_history",
+            TransferKind::Deposit
+        ).await?;
+        transfers.extend(
+            self.fetch_transfer_pages(&params, "withdrawal_history", TransferKind::Withdrawal).await?
+        );
+        Ok(filter_transfers(transfers, &req))
+    }
+
+    async fn cancel_all_orders(&self, req: Value) -> Result<Vec<String>, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_all_orders")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            let symbol = req["symbol"].as_str().map(parse_symbol).transpose()?;
+            let mut params = BTreeMap::from([("instType", "SPOT")]);
+            if let Some(symbol) = &symbol {
+                params.insert("instId", symbol.as_str());
+            }
+
+            let (canceled, failed) = self.cancel_open_orders(params).await?;
+            if failed.is_empty() {
+                Ok(canceled)
+            } else {
+                Err(ExchangeError::CancelFailed { canceled, failed })
+            }
+        }).await
+    }
+
+    async fn close_all(&self, req: Value) -> Result<CloseAllReport, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "close_all")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            check_close_all_confirmed(&req)?;
+
+            // No instType filter, so margin and derivative orders are canceled as well. Orders
+            // that stay open are reported, and the positions are closed regardless.
+            let canceled = self.cancel_open_orders(BTreeMap::new()).await;
+            let (canceled_orders, mut failed) = match canceled {
+                Ok(canceled) => canceled,
+                Err(e) => (Vec::new(), vec![format!("open orders: {}", e)]),
+            };
+
+            let positions = match self.send_req_with_sign(BTreeMap::new(), "positions").await {
+                Ok(positions) => positions,
+                Err(e) => {
+                    failed.push(format!("positions: {}", e));
+                    Value::Null
+                }
+            };
+
+            // Keeps going after a failed close so one bad position does not leave the rest open
+            let mut closed_positions = Vec::new();
+            let positions = positions["data"].as_array().into_iter().flatten();
+            for position in positions.filter(|position| is_open_position(position)) {
+                let inst_id = position["instId"].as_str().unwrap_or_default();
+                let pos_side = position["posSide"].as_str().unwrap_or("net");
+                let mut params = BTreeMap::from([
+                    ("instId", inst_id),
+                    ("mgnMode", position["mgnMode"].as_str().unwrap_or_default()),
+                ]);
+                // posSide is only accepted in long/short position mode
+                if pos_side != "net" {
+                    params.insert("posSide", pos_side);
+                }
+
+                match self.send_req_with_sign(params, "close_position").await {
+                    Ok(_) => closed_positions.push(inst_id.to_string()),
+                    Err(e) => failed.push(format!("{}: {}", inst_id, e)),
+                }
+            }
+
+            Ok(CloseAllReport {
+                exchange: "Okx".to_string(),
+                canceled_orders,
+                closed_positions,
+                failed,
+            })
+        }).await
+    }
+
+    async fn get_all_balances(&self) -> Result<Vec<Balance>, ExchangeError> {
+        let (trading, funding) = futures::join!(
+            self.send_req_with_sign(BTreeMap::new(), "account_balance"),
+            self.send_req_with_sign(BTreeMap::new(), "funding_balance")
+        );
+
+        // The trading account is the unified spot/margin account
+        let mut balances = parse_balances(&trading?["data"][0]["details"], Wallet::Spot)?;
+        balances.extend(parse_balances(&funding?["data"], Wallet::Funding)?);
+        Ok(balances)
+    }
+
+    async fn get_account_info(&self) -> Result<AccountInfo, ExchangeError> {
+        let res = self.send_req_with_sign(BTreeMap::new(), "account_config").await?;
+        parse_account_info(&res)
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<FeeSchedule, ExchangeError> {
+        let inst_id = parse_symbol(symbol)?;
+        let params = BTreeMap::from([
+            ("instType", "SPOT"),
+            ("instId", inst_id.as_str()),
+        ]);
+
+        let res = self.send_req_with_sign(params, "trade_fee").await?;
+        parse_trading_fees(&res, symbol)
+    }
+
+    // Updates of every instrument type, each pushed message carrying one or more orders
+    async fn stream_user_orders(
+        &self
+    ) -> Result<BoxStream<'static, Result<Order, ExchangeError>>, ExchangeError> {
+        let (api_key, secret, passphrase) = (
+            self.api_key.clone(),
+            self.secret.clone(),
+            self.passphrase.clone(),
+        );
+        let clock = self.client.clock().clone();
+        let login: WsLogin = Arc::new(move || {
+            let timestamp = (clock.now_millis() / 1000).to_string();
+            get_login_message(&api_key, &secret, &passphrase, &timestamp)
+        });
+        let subscription = json!({
+            "op": "subscribe",
+            "args": [{ "channel": "orders", "instType": "ANY" }]
+        });
+        let messages = WsManager::new(&self.private_ws_url)
+            .with_login(login)
+            .with_subscription(subscription)
+            .with_max_reconnect_attempts(self.max_reconnect_attempts)
+            .subscribe().await?;
+
+        Ok(
+            messages
+                .flat_map(|message| {
+                    let orders = match message.and_then(|message| parse_order_updates(&message)) {
+                        Ok(orders) => orders.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    };
+                    stream::iter(orders)
+                })
+                .boxed()
+        )
+    }
+
+    async fn stream_bbo(
+        &self,
+        symbol: &str
+    ) -> Result<BoxStream<'static, Result<Bbo, ExchangeError>>, ExchangeError> {
+        let subscription = json!({
+            "op": "subscribe",
+            "args": [{ "channel": "bbo-tbt", "instId": parse_symbol(symbol)? }]
+        });
+        let messages = WsManager::new(&self.ws_url)
+            .with_subscription(subscription)
+            .with_max_reconnect_attempts(self.max_reconnect_attempts)
+            .subscribe().await?;
+
+        let symbol = symbol.to_string();
+        Ok(
+            messages
+                .filter_map(move |message| {
+                    let bbo = message.and_then(|message| parse_bbo(&message, &symbol)).transpose();
+                    async move { bbo }
+                })
+                .boxed()
+        )
     }
 }
 
-fn parse_symbol(symbol: &str) -> String {
-    let v: Vec<&str> = symbol.split("/").collect();
-    format!("{}-{}", v[0], v[1])
+// Request path and JSON body as signed and sent. GET requests take their params in the query
+// string, everything else in the body; either way in their canonical order.
+pub(crate) fn get_signed_payload(
+    params: &BTreeMap<&str, &str>,
+    method: &str,
+    endpoint: &str
+) -> (String, Option<Value>) {
+    let params = canonicalize_params(params.iter().map(|(key, value)| (*key, *value)));
+    if method != "GET" {
+        let body = params.into_iter().map(|(key, value)| (key, Value::String(value))).collect();
+        (endpoint.to_string(), Some(Value::Object(body)))
+    } else if params.is_empty() {
+        (endpoint.to_string(), None)
+    } else {
+        (format!("{}?{}", endpoint, encode_params(&params)), None)
+    }
 }
 
+// OK-ACCESS-TIMESTAMP is ISO 8601 in UTC with milliseconds, e.g. "2020-12-08T09:08:57.715Z".
+// A caller-provided timestamp in milliseconds is converted; one already in that form is kept
+pub(crate) fn get_access_timestamp(req: &Value, clock: &Arc<dyn Clock>) -> String {
+    let timestamp = get_request_timestamp(req, clock);
+    match timestamp.parse::<u64>() {
+        Ok(millis) => format!("{}.{:03}Z", format_utc_datetime(millis), millis % 1000),
+        Err(_) => timestamp,
+    }
+}
+
+// Symbols already given as an instId, e.g. "BTC-USDT-SWAP", pass through as they are
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let parts = symbol.split('-').collect::<Vec<_>>();
+    if !symbol.contains('/') && parts.len() > 1 && !parts.iter().any(|part| part.is_empty()) {
+        return Ok(symbol.to_string());
+    }
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}-{}", base, quote))
+}
+
+// `inst_type` is "SPOT" (the default), "MARGIN" or "SWAP". An instId ending in "-SWAP" is a swap
+// without it being asked for.
+fn get_inst_type(req: &Value) -> Result<&'static str, ExchangeError> {
+    let Some(inst_type) = req["inst_type"].as_str() else {
+        let symbol = req["symbol"].as_str().unwrap_or_default();
+        return Ok(if symbol.ends_with("-SWAP") { "SWAP" } else { "SPOT" });
+    };
+    match inst_type.to_uppercase().as_str() {
+        "SPOT" => Ok("SPOT"),
+        "MARGIN" => Ok("MARGIN"),
+        "SWAP" => Ok("SWAP"),
+        _ => Err(ExchangeError::InvalidRequest(format!("Unsupported inst_type: {}", inst_type))),
+    }
+}
+
+// Margin and swap instruments share the spot pair's base and quote, swaps adding a "-SWAP" suffix
+fn get_inst_id(req: &Value) -> Result<String, ExchangeError> {
+    let inst_id = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+    match get_inst_type(req)? {
+        "SWAP" if !inst_id.ends_with("-SWAP") => Ok(format!("{}-SWAP", inst_id)),
+        _ => Ok(inst_id),
+    }
+}
+
+// Spot trades in cash; margin and swap orders default to cross margin unless `td_mode` asks for
+// "isolated"
+fn get_td_mode(req: &Value) -> Result<&'static str, ExchangeError> {
+    let inst_type = get_inst_type(req)?;
+    match req["td_mode"].as_str() {
+        None if inst_type == "SPOT" => Ok("cash"),
+        None => Ok("cross"),
+        Some("cash") if inst_type == "SPOT" => Ok("cash"),
+        Some("cross") => Ok("cross"),
+        Some("isolated") => Ok("isolated"),
+        Some(td_mode) =>
+            Err(
+                ExchangeError::InvalidRequest(
+                    format!("td_mode {} is not valid for {} orders", td_mode, inst_type)
+                )
+            ),
+    }
+}
+
+// Spot and margin instIds become "BASE/QUOTE"; others, e.g. "BTC-USDT-SWAP", are kept as they are
 fn encode_symbol(symbol: &str) -> String {
-    let v: Vec<&str> = symbol.split("-").collect();
-    format!("{}/{}", v[0], v[1])
+    match symbol.split('-').collect::<Vec<&str>>()[..] {
+        [base, quote] => format!("{}/{}", base, quote),
+        _ => symbol.to_string(),
+    }
 }
 
-fn parse_orderbook(orderbook_res: Value, symbol: String) -> Result<OrderBook, String> {
-    let orderbook_unit: Vec<OrderBookUnit> = orderbook_res["data"][0]["bids"]
+// Private channels log in with the signature of `GET /users/self/verify`, timestamped in seconds
+pub(crate) fn get_login_message(
+    api_key: &str,
+    secret: &str,
+    passphrase: &str,
+    timestamp: &str
+) -> Result<Value, ExchangeError> {
+    let sign = hmac_base64::<Sha256>(secret, &format!("{}GET/users/self/verify", timestamp))?;
+
+    Ok(
+        json!({
+            "op": "login",
+            "args": [{
+                "apiKey": api_key,
+                "passphrase": passphrase,
+                "timestamp": timestamp,
+                "sign": sign,
+            }]
+        })
+    )
+}
+
+// Subscription acknowledgements and other event frames carry no orders
+pub(crate) fn parse_order_updates(message: &Value) -> Result<Vec<Order>, ExchangeError> {
+    if let Some(event) = message["event"].as_str() {
+        return match event {
+            "error" => Err(ExchangeError::Request(format!("{}", message["msg"]))),
+            _ => Ok(Vec::new()),
+        };
+    }
+
+    message["data"]
         .as_array()
-        .ok_or("Failed to parse orderbook bids")?
+        .ok_or(ExchangeError::Parse(format!("data field is missing: {}", message)))?
         .iter()
-        .map(|unit| {
-            let ask_price = unit[0].as_str().unwrap_or_default().to_string();
-            let ask_size = unit[1].as_str().unwrap_or_default().to_string();
+        .map(|order| {
+            let symbol = encode_symbol(order["instId"].as_str().unwrap_or_default());
+            parse_order_entry(order, &symbol)
+        })
+        .collect()
+}
+
+// Subscription acknowledgements and other event frames carry no data and yield `None`
+pub(crate) fn parse_bbo(message: &Value, symbol: &str) -> Result<Option<Bbo>, ExchangeError> {
+    if let Some(event) = message["event"].as_str() {
+        return match event {
+            "error" => Err(ExchangeError::Request(format!("{}", message["msg"]))),
+            _ => Ok(None),
+        };
+    }
+
+    let data = &message["data"][0];
+    let level = |side: &str, index: usize| {
+        data[side][0][index]
+            .as_str()
+            .map(|value| value.to_string())
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", side, message)))
+    };
+
+    Ok(
+        Some(Bbo {
+            exchange: "Okx".to_string(),
+            symbol: symbol.to_string(),
+            bid: level("bids", 0)?,
+            bid_size: level("bids", 1)?,
+            ask: level("asks", 0)?,
+            ask_size: level("asks", 1)?,
+            ts: data["ts"]
+                .as_str()
+                .and_then(|ts| ts.parse().ok())
+                .unwrap_or_default(),
+        })
+    )
+}
+
+pub(crate) fn parse_mark_price(res: &Value, symbol: &str) -> Result<Price, ExchangeError> {
+    check_response(res)?;
+    let mark_price = res["data"][0]["markPx"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("markPx field is missing: {}", res)))?;
+
+    Ok(Price {
+        exchange: "Okx".to_string(),
+        symbol: symbol.to_string(),
+        price: mark_price.to_string(),
+        source: PriceSource::Mark,
+    })
+}
+
+// OKX publishes no minimum order value
+pub(crate) fn parse_symbol_info(res: &Value, symbol: &str) -> Result<SymbolInfo, ExchangeError> {
+    check_response(res)?;
+    let instrument = &res["data"][0];
+    let field = |name: &str| {
+        instrument[name].as_str().filter(|value| !value.is_empty()).map(|value| value.to_string())
+    };
+    let (Some(base), Some(quote)) = (field("baseCcy"), field("quoteCcy")) else {
+        return Err(ExchangeError::Parse(format!("Instrument is missing: {}", res)));
+    };
+
+    Ok(SymbolInfo {
+        exchange: "Okx".to_string(),
+        symbol: symbol.to_string(),
+        base,
+        quote,
+        tick_size: field("tickSz"),
+        lot_size: field("lotSz"),
+        min_size: field("minSz"),
+        min_notional: None,
+    })
+}
+
+// OKX reports the 24h open rather than the change, so the change is worked out from it
+pub(crate) fn parse_ticker(res: &Value, symbol: &str) -> Result<Ticker, ExchangeError> {
+    check_response(res)?;
+    let ticker = &res["data"][0];
+    let field = |name: &str| {
+        ticker[name].as_str().filter(|value| !value.is_empty()).map(|value| value.to_string())
+    };
+    let last = field("last").ok_or(ExchangeError::Parse(format!("last field is missing: {}", res)))?;
+
+    let change_24h = field("open24h").and_then(|open| {
+        let change = last.parse::<f64>().ok()? - open.parse::<f64>().ok()?;
+        let decimals = last.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+        Some(format!("{:.*}", decimals, change))
+    });
+
+    Ok(Ticker {
+        exchange: "Okx".to_string(),
+        symbol: symbol.to_string(),
+        bid: field("bidPx"),
+        ask: field("askPx"),
+        high_24h: field("high24h"),
+        low_24h: field("low24h"),
+        volume_24h: field("vol24h"),
+        change_24h,
+        last,
+    })
+}
+
+// `side` is the taker's side and `ts` a millisecond timestamp sent as a string
+pub(crate) fn parse_recent_trades(res: &Value, symbol: &str) -> Result<Vec<Trade>, ExchangeError> {
+    check_response(res)?;
+    let trades = res["data"]
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Unexpected trades response: {}", res)))?;
+
+    trades
+        .iter()
+        .map(|trade| {
+            let field = |name: &str| {
+                get_optional_str(&trade[name])
+                    .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", name, trade)))
+            };
+            Ok(Trade {
+                exchange: "Okx".to_string(),
+                symbol: symbol.to_string(),
+                trade_id: field("tradeId")?,
+                price: field("px")?,
+                size: field("sz")?,
+                side: Side::from_exchange_str(&field("side")?)?,
+                timestamp: field("ts")?
+                    .parse::<u64>()
+                    .map_err(|e| ExchangeError::Parse(format!("Invalid ts: {}", e)))?,
+                fee: String::new(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn parse_quote(res: &Value) -> Result<Quote, ExchangeError> {
+    check_response(res)?;
+    let ticker = &res["data"][0];
+    let field = |name: &str| {
+        ticker[name]
+            .as_str()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", name, res)))
+    };
+
+    Ok(Quote {
+        bid: field("bidPx")?,
+        bid_size: field("bidSz")?,
+        ask: field("askPx")?,
+        ask_size: field("askSz")?,
+    })
+}
+
+// `pos` is signed in net mode, and an empty or zero size is a closed position
+fn is_open_position(position: &Value) -> bool {
+    position["pos"]
+        .as_str()
+        .and_then(|pos| pos.parse::<f64>().ok())
+        .is_some_and(|pos| pos != 0.0)
+}
+
+// 501xx codes are the API key, passphrase, timestamp and signature errors. Order endpoints
+// put the specific reason in `data[0].sCode` / `sMsg` under a generic top level code.
+pub(crate) fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let code = res["code"].as_str().unwrap_or_default();
+    let msg = res["msg"].as_str().unwrap_or_default();
+    match code {
+        "0" => Ok(()),
+        code if code.starts_with("501") => {
+            Err(ExchangeError::Auth(format!("{}: {}", code, msg)))
+        }
+        _ => {
+            let data = &res["data"][0];
+            let (code, message) = match data["sCode"].as_str() {
+                Some(s_code) if s_code != "0" => {
+                    let s_msg = data["sMsg"].as_str().unwrap_or_default();
+                    let message = if msg.is_empty() {
+                        s_msg.to_string()
+                    } else {
+                        format!("{}: {}", msg, s_msg)
+                    };
+                    (s_code, message)
+                }
+                _ => (code, msg.to_string()),
+            };
+            Err(
+                ExchangeError::ExchangeRejected {
+                    code: code.to_string(),
+                    message,
+                }
+            )
+        }
+    }
+}
+
+// Only live instruments are listed by the tickers endpoint. For spot, `volCcy24h` is the
+// volume in the quote currency.
+pub(crate) fn parse_markets(res: &Value, quote: &str) -> Result<Vec<Market>, ExchangeError> {
+    let tickers = res["data"]
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("data field is not an array: {}", res)))?;
+
+    Ok(
+        tickers
+            .iter()
+            .filter_map(|ticker| {
+                let (base, ticker_quote) = ticker["instId"].as_str()?.split_once('-')?;
+                (ticker_quote == quote).then(|| Market {
+                    exchange: "Okx".to_string(),
+                    symbol: format!("{}/{}", base, quote),
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                    last_price: ticker["last"].as_str().unwrap_or("0").to_string(),
+                    quote_volume: ticker["volCcy24h"].as_str().unwrap_or("0").to_string(),
+                })
+            })
+            .collect()
+    )
+}
+
+// amend-order only acknowledges the request, so the order fields come from `req`
+fn parse_amended_order(res: &Value, req: &Value) -> Result<Order, ExchangeError> {
+    let data = &res["data"][0];
+    if res["code"] != "0" || data["sCode"] != "0" {
+        let message = data["sMsg"].as_str().or(res["msg"].as_str()).unwrap_or_default();
+        return Err(ExchangeError::Request(message.to_string()));
+    }
+
+    let field = |key: &str| req[key].as_str().unwrap_or_default().to_string();
+    Ok(Order {
+        exchange: "Okx".to_string(),
+        ord_id: data["ordId"].as_str().unwrap_or_default().to_string(),
+        side: Side::from_exchange_str(req["side"].as_str().unwrap_or_default())?,
+        ord_type: field("order_type"),
+        price: field("price"),
+        // An amended order is still on the book
+        state: OrderState::New,
+        raw_state: "live".to_string(),
+        market: field("symbol"),
+        volume: field("amount"),
+        create_at: String::new(),
+        amount: String::new(),
+        fee: String::new(),
+        fee_currency: String::new(),
+        raw: data.clone(),
+    })
+}
+
+fn parse_order(res: &Value, symbol: &str) -> Result<Order, ExchangeError> {
+    parse_order_entry(&res["data"][0], symbol)
+}
+
+// One order of a REST response or an orders channel push, which share their fields
+fn parse_order_entry(order: &Value, symbol: &str) -> Result<Order, ExchangeError> {
+    let ord_id = order["ordId"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("ordId field is missing: {}", order)))?;
+    let field = |key: &str| order[key].as_str().unwrap_or_default().to_string();
+    let raw_state = field("state");
+    // OKX reports a charged fee as negative
+    let fee = field("fee");
+    let fee = match fee.strip_prefix('-') {
+        Some(charged) => charged.to_string(),
+        None if fee.parse::<f64>().is_ok_and(|fee| fee != 0.0) => format!("-{}", fee),
+        None => fee,
+    };
+
+    Ok(Order {
+        exchange: "Okx".to_string(),
+        ord_id: ord_id.to_string(),
+        side: Side::from_exchange_str(order["side"].as_str().unwrap_or_default())?,
+        ord_type: field("ordType"),
+        price: field("px"),
+        state: OrderState::from_exchange_str(&raw_state),
+        raw_state,
+        market: symbol.to_string(),
+        volume: field("sz"),
+        create_at: field("cTime"),
+        amount: field("accFillSz"),
+        fee,
+        fee_currency: field("feeCcy"),
+        raw: order.clone(),
+    })
+}
+
+// The algo order response only acknowledges the order, so the rest is taken from the request
+fn parse_algo_order(res: &Value, req: &Value) -> Result<Order, ExchangeError> {
+    let algo_id = res["data"][0]["algoId"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("algoId field is missing: {}", res)))?;
+    let field = |key: &str| req[key].as_str().unwrap_or_default().to_string();
+
+    Ok(Order {
+        exchange: "Okx".to_string(),
+        ord_id: algo_id.to_string(),
+        side: Side::from_exchange_str(req["side"].as_str().unwrap_or_default())?,
+        ord_type: "conditional".to_string(),
+        price: field("price"),
+        state: OrderState::New,
+        raw_state: "live".to_string(),
+        market: field("symbol"),
+        volume: field("amount"),
+        create_at: String::new(),
+        amount: "0".to_string(),
+        fee: String::new(),
+        fee_currency: String::new(),
+        raw: res["data"][0].clone(),
+    })
+}
+
+// Both accounts list currencies as `{ ccy, availBal, frozenBal }`
+fn parse_balances(details: &Value, wallet: Wallet) -> Result<Vec<Balance>, ExchangeError> {
+    let details = details
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("{:?} balances are not an array: {}", wallet, details)))?;
+
+    Ok(
+        details
+            .iter()
+            .map(|detail| Balance {
+                exchange: "Okx".to_string(),
+                wallet,
+                currency: detail["ccy"].as_str().unwrap_or_default().to_string(),
+                free: detail["availBal"].as_str().unwrap_or("0").to_string(),
+                locked: detail["frozenBal"].as_str().unwrap_or("0").to_string(),
+            })
+            .collect()
+    )
+}
+
+// `perm` is a comma separated list such as "read_only,trade", and a sub-account's `uid` differs
+// from its `mainUid`
+pub(crate) fn parse_account_info(res: &Value) -> Result<AccountInfo, ExchangeError> {
+    let config = &res["data"][0];
+    let perm = config["perm"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("perm field is missing: {}", res)))?
+        .split(',')
+        .collect::<Vec<&str>>();
+
+    Ok(AccountInfo {
+        exchange: "Okx".to_string(),
+        can_trade: Some(perm.contains(&"trade")),
+        can_withdraw: Some(perm.contains(&"withdraw")),
+        fee_tier: get_optional_str(&config["level"]),
+        is_sub_account: match (config["uid"].as_str(), config["mainUid"].as_str()) {
+            (Some(uid), Some(main_uid)) => Some(uid != main_uid),
+            _ => None,
+        },
+    })
+}
+
+// Okx reports fees charged as negative numbers and rebates as positive ones, so the sign is
+// flipped to match the other exchanges
+pub(crate) fn parse_trading_fees(res: &Value, symbol: &str) -> Result<FeeSchedule, ExchangeError> {
+    let fee = &res["data"][0];
+    let rate = |key: &str| -> Result<String, ExchangeError> {
+        let rate = fee[key]
+            .as_str()
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", key, res)))?;
+        Ok(match rate.strip_prefix('-') {
+            Some(charged) => charged.to_string(),
+            None if rate.parse::<f64>().is_ok_and(|rate| rate != 0.0) => format!("-{}", rate),
+            None => rate.to_string(),
+        })
+    };
+
+    Ok(FeeSchedule {
+        exchange: "Okx".to_string(),
+        symbol: symbol.to_string(),
+        maker: rate("maker")?,
+        taker: rate("taker")?,
+    })
+}
+
+pub(crate) fn parse_deposit_address(
+    res: &Value,
+    network: Option<&str>
+) -> Result<DepositAddress, ExchangeError> {
+    let addresses = res["data"]
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("data field is not an array: {}", res)))?;
+
+    // One entry is returned per chain; prefer the requested one, then the default
+    let address = addresses
+        .iter()
+        .find(|address| network.is_some_and(|network| address["chain"] == network))
+        .or_else(|| addresses.iter().find(|address| address["selected"] == true))
+        .or(addresses.first())
+        .ok_or(ExchangeError::Parse("No deposit address returned".to_string()))?;
+
+    // XRP style coins carry a `tag`, XLM/EOS style coins a `memo`
+    Ok(DepositAddress {
+        currency: address["ccy"].as_str().unwrap_or_default().to_string(),
+        network: address["chain"].as_str().unwrap_or_default().to_string(),
+        address: address["addr"].as_str().unwrap_or_default().to_string(),
+        memo: get_optional_str(&address["tag"]).or(get_optional_str(&address["memo"])),
+    })
+}
+
+// Deposit and withdrawal records share their field names; deposits carry no `fee`
+pub(crate) fn parse_transfer_history(
+    res: &Value,
+    kind: TransferKind
+) -> Result<Vec<Transfer>, ExchangeError> {
+    check_response(res)?;
+    let records = res["data"]
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("data field is not an array: {}", res)))?;
+
+    records
+        .iter()
+        .map(|record| {
+            let status = match (kind, record["state"].as_str().unwrap_or_default()) {
+                (TransferKind::Deposit, "1" | "2") => TransferStatus::Completed,
+                (TransferKind::Withdrawal, "2") => TransferStatus::Completed,
+                (TransferKind::Withdrawal, "-1") => TransferStatus::Failed,
+                (TransferKind::Withdrawal, "-2") => TransferStatus::Canceled,
+                _ => TransferStatus::Pending,
+            };
+            let timestamp = record["ts"]
+                .as_str()
+                .and_then(|ts| ts.parse::<u64>().ok())
+                .ok_or(ExchangeError::Parse(format!("Invalid ts: {}", record)))?;
+            Ok(Transfer {
+                kind,
+                currency: record["ccy"].as_str().unwrap_or_default().to_string(),
+                amount: record["amt"].as_str().unwrap_or_default().to_string(),
+                fee: get_optional_str(&record["fee"]).unwrap_or_else(|| "0".to_string()),
+                tx_id: get_optional_str(&record["txId"]),
+                address: get_optional_str(&record["to"]),
+                status,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+// Levels are `[price, size, 0, order count]`. Every level is kept; past the end of the shorter
+// side its price and size are empty.
+fn parse_orderbook(orderbook_res: Value, symbol: String) -> Result<OrderBook, String> {
+    let book = &orderbook_res["data"][0];
+    let asks = book["asks"].as_array().ok_or("Failed to parse orderbook asks")?;
+    let bids = book["bids"].as_array().ok_or("Failed to parse orderbook bids")?;
+
+    let orderbook_unit = (0..asks.len().max(bids.len()))
+        .map(|i| {
+            let ask = asks.get(i).unwrap_or(&Value::Null);
+            let bid = bids.get(i).unwrap_or(&Value::Null);
             OrderBookUnit {
-                ask_price: ask_price.clone(),
-                bid_price: ask_price.clone(), // Assuming a symmetrical book
-                ask_size: ask_size.clone(),
-                bid_size: ask_size.clone(),
+                ask_price: ask[0].as_str().unwrap_or_default().to_string(),
+                bid_price: bid[0].as_str().unwrap_or_default().to_string(),
+                ask_size: ask[1].as_str().unwrap_or_default().to_string(),
+                bid_size: bid[1].as_str().unwrap_or_default().to_string(),
             }
         })
         .collect::<Vec<OrderBookUnit>>();
@@ -330,5 +1591,6 @@ fn parse_orderbook(orderbook_res: Value, symbol: String) -> Result<OrderBook, St
         market: symbol, // encode_symbol을 사용하여 심볼을 반환
         exchange: "Okx".to_string(),
         orderbook_unit,
+        timestamp: orderbook_res["data"][0]["ts"].as_str().and_then(|ts| ts.parse().ok()),
     })
 }
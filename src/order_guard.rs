@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use serde_json::{ json, Value };
+
+use crate::{ Exchange, ExchangeError, Order };
+
+// An order placed through `place_order_guarded` that warns when it is dropped while still
+// open, as when a bot exits without cleaning up. With `cancel_on_drop` the order is also
+// canceled then. Both are best effort: the state is the last one seen, so call `refresh`
+// to pick up a fill, and the cancel needs a running tokio runtime.
+pub struct OrderGuard {
+    exchange: Arc<dyn Exchange>,
+    symbol: String,
+    order: Order,
+    cancel_on_drop: bool,
+    released: bool,
+}
+
+// Places the order with `place_order_typed` and guards it
+pub async fn place_order_guarded(
+    exchange: Arc<dyn Exchange>,
+    req: Value,
+    cancel_on_drop: bool
+) -> Result<OrderGuard, ExchangeError> {
+    let symbol = req["symbol"].as_str().unwrap_or_default().to_string();
+    let order = exchange.place_order_typed(req).await?;
+    Ok(OrderGuard { exchange, symbol, order, cancel_on_drop, released: false })
+}
+
+impl OrderGuard {
+    pub fn order(&self) -> &Order {
+        &self.order
+    }
+
+    // Looks the order up again, so a guard whose order has since filled drops quietly
+    pub async fn refresh(&mut self) -> Result<&Order, ExchangeError> {
+        let lookup = json!({ "symbol": self.symbol, "order_id": self.order.ord_id });
+        self.order = self.exchange.get_order(lookup).await?;
+        Ok(&self.order)
+    }
+
+    // Takes responsibility for the order back, e.g. to leave it resting on purpose
+    pub fn release(mut self) -> Order {
+        self.released = true;
+        self.order.clone()
+    }
+}
+
+impl Drop for OrderGuard {
+    fn drop(&mut self) {
+        if self.released || !self.order.state.is_open() {
+            return;
+        }
+        log::warn!(
+            "{} order {} on {} was dropped while still open{}",
+            self.exchange.get_name(),
+            self.order.ord_id,
+            self.symbol,
+            if self.cancel_on_drop { ", canceling it" } else { "" }
+        );
+        if !self.cancel_on_drop {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            log::warn!("No tokio runtime to cancel order {}", self.order.ord_id);
+            return;
+        };
+        let exchange = self.exchange.clone();
+        let req = json!({ "symbol": self.symbol, "order_id": self.order.ord_id });
+        runtime.spawn(async move {
+            if let Err(e) = exchange.cancel_order(req).await {
+                let name = exchange.get_name();
+                log::warn!("Canceling a dropped {} order failed: {}", name, e);
+            }
+        });
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::Exchange;
+
+// Exchanges keyed by their lowercased `get_name()`, so lookups from config strings such as
+// "binance" or "OKX" resolve regardless of case.
+#[derive(Default, Clone)]
+pub struct ExchangeRegistry {
+    exchanges: HashMap<String, Arc<dyn Exchange>>,
+}
+
+impl ExchangeRegistry {
+    pub fn new() -> Self {
+        ExchangeRegistry::default()
+    }
+
+    // Returns the exchange previously registered under the same name, if any
+    pub fn register(&mut self, exchange: Arc<dyn Exchange>) -> Option<Arc<dyn Exchange>> {
+        self.exchanges.insert(exchange.get_name().to_lowercase(), exchange)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Exchange>> {
+        self.exchanges.get(&name.to_lowercase()).cloned()
+    }
+
+    // Sorted by name so iteration order is stable
+    pub fn all(&self) -> Vec<Arc<dyn Exchange>> {
+        let mut names = self.exchanges.keys().collect::<Vec<&String>>();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| self.exchanges[name].clone())
+            .collect()
+    }
+}
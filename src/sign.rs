@@ -0,0 +1,36 @@
+use base64::{ Engine as _, engine::general_purpose };
+use hmac::{ digest::KeyInit, Hmac, Mac };
+use sha2::{ Sha256, Sha512 };
+
+// Ties a digest to its HMAC type. `Hmac<D>` only exists for digests that meet a long list of
+// core bounds, so the helpers below name the digest and look the MAC up here instead.
+pub(crate) trait HmacDigest {
+    type Mac: Mac + KeyInit;
+}
+
+impl HmacDigest for Sha256 {
+    type Mac = Hmac<Sha256>;
+}
+
+impl HmacDigest for Sha512 {
+    type Mac = Hmac<Sha512>;
+}
+
+// Keyed MAC for callers that sign with it directly, e.g. the JWT-based exchanges
+pub(crate) fn hmac_key<D: HmacDigest>(secret: &str) -> Result<D::Mac, String> {
+    <D::Mac as KeyInit>::new_from_slice(secret.as_bytes()).map_err(|e| e.to_string())
+}
+
+pub(crate) fn hmac_hex<D: HmacDigest>(secret: &str, message: &str) -> Result<String, String> {
+    Ok(hex::encode(hmac_bytes::<D>(secret, message)?))
+}
+
+pub(crate) fn hmac_base64<D: HmacDigest>(secret: &str, message: &str) -> Result<String, String> {
+    Ok(general_purpose::STANDARD.encode(hmac_bytes::<D>(secret, message)?))
+}
+
+fn hmac_bytes<D: HmacDigest>(secret: &str, message: &str) -> Result<Vec<u8>, String> {
+    let mut mac = hmac_key::<D>(secret)?;
+    mac.update(message.as_bytes());
+    Ok(mac.finalize().into_bytes().to_vec())
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::{ self, BoxStream, StreamExt };
+use futures::SinkExt;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{ connect_async, MaybeTlsStream, WebSocketStream };
+
+use crate::ExchangeError;
+
+type WsSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+// Builds the login message of a private channel. It is called on every (re)connect, as login
+// messages are usually signed with the current time.
+pub(crate) type WsLogin = Arc<dyn Fn() -> Result<Value, ExchangeError> + Send + Sync>;
+
+#[derive(Deserialize, Debug, Clone, Serialize)]
+pub struct Bbo {
+    pub exchange: String,
+    pub symbol: String,
+    pub bid: String,
+    pub bid_size: String,
+    pub ask: String,
+    pub ask_size: String,
+    pub ts: u64,
+}
+
+// Keeps a WebSocket subscription alive, reconnecting and re-subscribing whenever the socket
+// drops. A reconnect only counts as recovered once a message arrives, so a server that accepts
+// and immediately closes keeps adding to the consecutive failure count.
+pub(crate) struct WsManager {
+    url: String,
+    login: Option<WsLogin>,
+    subscription: Option<Value>,
+    // None reconnects forever
+    max_reconnect_attempts: Option<u32>,
+    reconnect_delay: Duration,
+}
+
+struct WsState {
+    manager: WsManager,
+    socket: Option<WsSocket>,
+    failures: u32,
+    terminated: bool,
+}
+
+impl WsManager {
+    pub(crate) fn new(url: &str) -> Self {
+        WsManager {
+            url: url.to_string(),
+            login: None,
+            subscription: None,
+            max_reconnect_attempts: None,
+            reconnect_delay: Duration::from_secs(1),
+        }
+    }
+
+    // The login is sent before the subscription, which waits for the login to be acknowledged
+    pub(crate) fn with_login(mut self, login: WsLogin) -> Self {
+        self.login = Some(login);
+        self
+    }
+
+    pub(crate) fn with_subscription(mut self, subscription: Value) -> Self {
+        self.subscription = Some(subscription);
+        self
+    }
+
+    pub(crate) fn with_max_reconnect_attempts(mut self, max_reconnect_attempts: Option<u32>) -> Self {
+        self.max_reconnect_attempts = max_reconnect_attempts;
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_reconnect_delay(mut self, reconnect_delay: Duration) -> Self {
+        self.reconnect_delay = reconnect_delay;
+        self
+    }
+
+    async fn connect(&self) -> Result<WsSocket, ExchangeError> {
+        let (mut socket, _) = connect_async(self.url.as_str()).await.map_err(|e|
+            ExchangeError::Request(e.to_string())
+        )?;
+
+        if let Some(login) = &self.login {
+            socket
+                .send(Message::text(login()?.to_string())).await
+                .map_err(|e| ExchangeError::Request(e.to_string()))?;
+            let reply = next_text(&mut socket).await?;
+            // OKX style: `{ "event": "error", "code", "msg" }` rejects the login
+            if reply["event"] == "error" {
+                let code = reply["code"].as_str().unwrap_or_default();
+                let message = reply["msg"].as_str().unwrap_or_default();
+                return Err(ExchangeError::Auth(format!("{}: {}", code, message)));
+            }
+        }
+
+        if let Some(subscription) = &self.subscription {
+            socket
+                .send(Message::text(subscription.to_string())).await
+                .map_err(|e| ExchangeError::Request(e.to_string()))?;
+        }
+        Ok(socket)
+    }
+
+    // The first connection is made eagerly so a bad url or handshake fails immediately; later
+    // drops are retried with a linearly growing delay. Every text frame is yielded as JSON.
+    pub(crate) async fn subscribe(
+        self
+    ) -> Result<BoxStream<'static, Result<Value, ExchangeError>>, ExchangeError> {
+        let socket = self.connect().await?;
+        let state = WsState { manager: self, socket: Some(socket), failures: 0, terminated: false };
+
+        Ok(stream::unfold(state, next_message).boxed())
+    }
+}
+
+// The first text frame, e.g. the reply to a login
+async fn next_text(socket: &mut WsSocket) -> Result<Value, ExchangeError> {
+    loop {
+        match socket.next().await {
+            Some(Ok(Message::Text(text))) => {
+                return serde_json::from_str(&text).map_err(|e| ExchangeError::Parse(e.to_string()));
+            }
+            Some(Ok(Message::Close(_))) | None => {
+                return Err(ExchangeError::Request("Socket closed before replying".to_string()));
+            }
+            Some(Ok(_)) => {
+                continue;
+            }
+            Some(Err(e)) => {
+                return Err(ExchangeError::Request(e.to_string()));
+            }
+        }
+    }
+}
+
+async fn next_message(mut state: WsState) -> Option<(Result<Value, ExchangeError>, WsState)> {
+    if state.terminated {
+        return None;
+    }
+
+    loop {
+        if let Some(socket) = state.socket.as_mut() {
+            match socket.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    state.failures = 0;
+                    let message = serde_json
+                        ::from_str::<Value>(&text)
+                        .map_err(|e| ExchangeError::Parse(e.to_string()));
+                    return Some((message, state));
+                }
+                // Ping/pong and other control frames are handled by the socket
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                    state.socket = None;
+                }
+                Some(Ok(_)) => {
+                    continue;
+                }
+            }
+        }
+
+        let manager = &state.manager;
+        if manager.max_reconnect_attempts.is_some_and(|max| state.failures >= max) {
+            let error = ExchangeError::Request(
+                format!("{} stream gave up after {} reconnect attempts", manager.url, state.failures)
+            );
+            state.terminated = true;
+            return Some((Err(error), state));
+        }
+
+        state.failures += 1;
+        tokio::time::sleep(manager.reconnect_delay * state.failures).await;
+        state.socket = manager.connect().await.ok();
+    }
+}
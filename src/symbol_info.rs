@@ -0,0 +1,23 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::ttl_cache::TtlCache;
+
+// Trading rules of one instrument. Sizes are in the base currency and prices in the quote
+// currency; a rule the exchange does not publish is `None`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolInfo {
+    pub exchange: String,
+    pub symbol: String,
+    pub base: String,
+    pub quote: String,
+    // Smallest price increment
+    pub tick_size: Option<String>,
+    // Smallest size increment
+    pub lot_size: Option<String>,
+    pub min_size: Option<String>,
+    // Smallest order value in the quote currency
+    pub min_notional: Option<String>,
+}
+
+// Symbol info keyed by (exchange, symbol)
+pub type SymbolInfoCache = TtlCache<(String, String), SymbolInfo>;
@@ -1,5 +1,11 @@
 use std::collections::BTreeMap;
+use base64::{ Engine as _, engine::general_purpose };
+use hmac::{ Hmac, Mac };
+use serde_json::{ json, Value };
+use sha2::Sha512;
 use crate::bithumb::{Bithumb, BithumbTrait};
+use crate::test::mock::{MockResponse, MockServer};
+use crate::{ Exchange, ExchangeError, OrderState, Side };
 
 // 헬퍼 함수: Bithumb 객체 생성
 fn create_test_bithumb() -> Bithumb {
@@ -34,18 +40,6 @@ fn test_new_bithumb_with_empty_credentials() {
     assert_bithumb_creation_error("", "", "API key cannot be empty");
 }
 
-#[test]
-fn test_get_end_point() {
-    let bithumb = create_test_bithumb();
-    let endpoints = bithumb.get_end_point();
-    let expected_endpoints = BTreeMap::from([
-        ("make_order".to_string(), ["POST".to_string(), "v1/orders".to_string()]),
-        ("cancel_order".to_string(), ["DELETE".to_string(), "v1/order".to_string()]),
-    ]);
-
-    assert_eq!(endpoints, &expected_endpoints);
-}
-
 #[test]
 fn test_get_end_point_with_key_existing() {
     let bithumb = create_test_bithumb();
@@ -77,8 +71,11 @@ fn test_get_query_hash() {
     ]);
 
     let query_hash = bithumb.get_query_hash(&params);
-    assert!(query_hash.is_ok());
-    // 해시값이 정확한지 확인하는 부분은 테스트 환경에 맞게 추가할 수 있습니다.
+    // SHA512 of "market=BTC-USD&ord_type=limit&price=50000&side=buy&volume=0.01"
+    assert_eq!(
+        query_hash.unwrap(),
+        "9e04eb338c3b7429a416c2cb7d46d25158afccf8ef696f9b422b3247c50c740d2238b4212793288f0c099b467b407f2457d270dddde776e3ee5457955d5aea5b"
+    );
 }
 
 #[test]
@@ -86,6 +83,178 @@ fn test_get_json_with_valid_query_hash() {
     let bithumb = create_test_bithumb();
     let query_hash = "valid_query_hash".to_string();
     let json_result = bithumb.get_json(query_hash);
-    assert!(json_result.is_ok());
-    // 결과가 유효한지 추가로 확인하는 부분은 테스트 환경에 맞게 구현할 수 있습니다.
+    // header.payload.signature
+    assert_eq!(json_result.unwrap().split('.').count(), 3);
+}
+
+#[test]
+fn test_jwt_is_signed_over_known_query_hash() {
+    let bithumb = create_test_bithumb();
+    let params = BTreeMap::from([
+        ("market", "KRW-BTC"),
+        ("side", "bid"),
+        ("ord_type", "limit"),
+        ("price", "140000000"),
+        ("volume", "0.001"),
+    ]);
+    // SHA512 of "market=KRW-BTC&ord_type=limit&price=140000000&side=bid&volume=0.001"
+    let expected_hash = "4d68bcf675ef9260d07a97eff1bd2c6ce869fc1abe7a79cdca7c7239e1221593bed45b604f652c4114e43f9161d864d8a831d9911e691b5c480690e63bf76904";
+    let query_hash = bithumb.get_query_hash(&params).unwrap();
+    assert_eq!(query_hash, expected_hash);
+
+    let token = bithumb.get_json(query_hash).unwrap();
+    let [header, payload, signature] = token.split('.').collect::<Vec<_>>()[..] else {
+        panic!("not a JWT: {}", token);
+    };
+    // {"alg":"HS512"}
+    assert_eq!(header, "eyJhbGciOiJIUzUxMiJ9");
+    let claims: Value = serde_json::from_slice(&general_purpose::URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+    assert_eq!(claims["access_key"], "test_api_key");
+    assert_eq!(claims["query_hash"], expected_hash);
+    assert_eq!(claims["query_hash_alg"], "SHA512");
+
+    // nonce가 매번 달라지므로 서명은 secret으로 직접 계산한 HMAC과 비교
+    let mut mac = Hmac::<Sha512>::new_from_slice(b"test_secret").unwrap();
+    mac.update(format!("{}.{}", header, payload).as_bytes());
+    assert_eq!(signature, general_purpose::URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()));
+}
+
+#[tokio::test]
+async fn test_cancel_all_orders_cancels_open_orders_individually() {
+    let server = MockServer::start(|request| {
+        if request.method == "GET" {
+            MockResponse::json(r#"[{"uuid":"order-1","market":"KRW-BTC"},{"uuid":"order-2","market":"KRW-BTC"}]"#)
+        } else {
+            MockResponse::json(r#"{"uuid":"canceled"}"#)
+        }
+    }).await;
+    let bithumb = create_test_bithumb().with_api_url(&server.url);
+
+    let canceled = bithumb.cancel_all_orders(json!({ "symbol": "BTC/KRW" })).await.unwrap();
+    assert_eq!(canceled, vec!["order-1".to_string(), "order-2".to_string()]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("GET", "/v1/orders?market=KRW-BTC&state=wait"));
+    for (request, order_id) in requests[1..].iter().zip(["order-1", "order-2"]) {
+        assert_eq!(request.method, "DELETE");
+        assert_eq!(request.path, format!("/v1/order?uuid={}", order_id));
+        assert!(request.body.is_empty());
+    }
 }
+
+#[tokio::test]
+async fn test_cancel_all_orders_keeps_going_after_a_failed_cancel() {
+    let server = MockServer::start(|request| {
+        if request.method == "GET" {
+            MockResponse::json(r#"[{"uuid":"order-1","market":"KRW-BTC"},{"uuid":"order-2","market":"KRW-BTC"}]"#)
+        } else if request.path.contains("order-1") {
+            MockResponse {
+                status: 400,
+                ..MockResponse::json(r#"{"error":{"name":"order_not_found","message":"주문을 찾지 못했습니다."}}"#)
+            }
+        } else {
+            MockResponse::json(r#"{"uuid":"order-2"}"#)
+        }
+    }).await;
+    let bithumb = create_test_bithumb().with_api_url(&server.url);
+
+    let error = bithumb.cancel_all_orders(json!({ "symbol": "BTC/KRW" })).await.unwrap_err();
+    let ExchangeError::CancelFailed { canceled, failed } = error else {
+        panic!("unexpected error: {:?}", error);
+    };
+    // 첫 번째 취소가 실패해도 두 번째 주문은 취소되어야 함
+    assert_eq!(canceled, vec!["order-2".to_string()]);
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].starts_with("order-1: "));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_error_envelope_is_returned_as_exchange_rejected() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 400,
+        ..MockResponse::json(r#"{"error":{"name":"insufficient_funds_bid","message":"매수 가능 금액이 부족합니다."}}"#)
+    }).await;
+    let bithumb = create_test_bithumb().with_api_url(&server.url);
+
+    let error = bithumb.get_all_balances().await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::ExchangeRejected {
+            code: "insufficient_funds_bid".to_string(),
+            message: "매수 가능 금액이 부족합니다.".to_string(),
+        }
+    );
+
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "90000000",
+        "amount": "1"
+    });
+    let error = bithumb.place_order(order).await.unwrap_err();
+    assert_eq!(error, "insufficient_funds_bid: 매수 가능 금액이 부족합니다.");
+}
+
+#[tokio::test]
+async fn test_post_only_order_is_unsupported() {
+    let bithumb = create_test_bithumb();
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "90000000",
+        "amount": "1",
+        "post_only": true
+    });
+
+    let error = bithumb.place_order_typed(order).await.unwrap_err();
+    assert_eq!(error, ExchangeError::Unsupported {
+        exchange: "Bithumb".to_string(),
+        method: "post_only".to_string(),
+    });
+}
+
+#[tokio::test]
+async fn test_supports_matches_implemented_methods() {
+    let bithumb = create_test_bithumb();
+    assert!(bithumb.supports("get_order"));
+    // Built on get_order
+    assert!(bithumb.supports("place_order_confirmed"));
+    assert!(!bithumb.supports("withdraw"));
+    assert!(!bithumb.supports("no_such_method"));
+
+    let error = bithumb.withdraw(json!({})).await.unwrap_err();
+    assert!(matches!(error, ExchangeError::Unsupported { method, .. } if method == "withdraw"));
+}
+
+#[tokio::test]
+async fn test_place_order_typed_parses_order_response() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"uuid":"C0106000032400700021","side":"ask","ord_type":"limit","price":"92850000","state":"wait","market":"KRW-BTC","created_at":"2024-08-20T10:00:00+09:00","volume":"0.002","remaining_volume":"0.002","reserved_fee":"0","remaining_fee":"0","paid_fee":"0","locked":"0.002","executed_volume":"0","trades_count":0}"#
+        )
+    }).await;
+    let bithumb = create_test_bithumb().with_api_url(&server.url);
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "ask",
+        "order_type": "limit",
+        "price": "92850000",
+        "amount": "0.002"
+    });
+
+    let order = bithumb.place_order_typed(order).await.unwrap();
+    assert_eq!(order.exchange, "Bithumb");
+    assert_eq!(order.ord_id, "C0106000032400700021");
+    assert_eq!(order.side, Side::Sell);
+    assert_eq!(order.state, OrderState::New);
+    assert_eq!(order.market, "BTC/KRW");
+    assert_eq!(order.price, "92850000");
+    assert_eq!(order.volume, "0.002");
+    assert_eq!(order.create_at, "2024-08-20T10:00:00+09:00");
+    assert_eq!(order.amount, "0");
+}
+
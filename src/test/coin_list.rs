@@ -0,0 +1,122 @@
+use serde_json::json;
+
+use crate::test::mock::{ MockExchange, MockResponse, MockServer };
+use crate::upbit::{ Upbit, UpbitTrait };
+use crate::{ normalize_user_symbol, split_symbol, validate_symbol, CoinList, Exchange, ExchangeError };
+
+// 헬퍼 함수: 상장 심볼 목록을 가진 MockExchange 생성
+fn create_listed_exchange() -> MockExchange {
+    MockExchange::new("Mock", 0, Ok("100")).with_coin_list(&["BTC/USDT", "ETH/USDT", "BTC/KRW"])
+}
+
+// 헬퍼 함수: 테스트용 CoinList 생성
+fn create_coin_list() -> CoinList {
+    CoinList {
+        market: "Mock".to_string(),
+        coin_list: ["BTC/USDT", "ETH/USDT", "BTC/KRW", "USDT/KRW"].map(str::to_string).to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn test_supports_symbol_checks_coin_list() {
+    let exchange = create_listed_exchange();
+
+    assert!(exchange.supports_symbol("BTC/USDT").await.unwrap());
+    assert!(exchange.supports_symbol("eth/usdt").await.unwrap());
+    assert!(!exchange.supports_symbol("DOGE/USDT").await.unwrap());
+    assert!(exchange.supports("supports_symbol"));
+
+    let error = exchange.supports_symbol("BTCUSDT").await.unwrap_err();
+    assert_eq!(error, ExchangeError::InvalidSymbol("BTCUSDT".to_string()));
+}
+
+#[tokio::test]
+async fn test_validate_symbol_suggests_closest_listing() {
+    let exchange = create_listed_exchange();
+
+    validate_symbol(&exchange, "BTC/KRW").await.unwrap();
+
+    let error = validate_symbol(&exchange, "BTC/USD").await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::InvalidSymbol(
+            "BTC/USD is not listed on Mock, did you mean BTC/USDT?".to_string()
+        )
+    );
+
+    let error = validate_symbol(&exchange, "DOGE/EUR").await.unwrap_err();
+    assert_eq!(error, ExchangeError::InvalidSymbol("DOGE/EUR is not listed on Mock".to_string()));
+}
+
+#[test]
+fn test_normalize_user_symbol_inserts_slash() {
+    let coin_list = create_coin_list();
+
+    assert_eq!(normalize_user_symbol("btcusdt", &coin_list).unwrap(), "BTC/USDT");
+    assert_eq!(normalize_user_symbol("BTCKRW", &coin_list).unwrap(), "BTC/KRW");
+    assert_eq!(normalize_user_symbol("usdtkrw", &coin_list).unwrap(), "USDT/KRW");
+}
+
+#[test]
+fn test_normalize_user_symbol_uppercases() {
+    let coin_list = create_coin_list();
+
+    assert_eq!(normalize_user_symbol("btc/krw", &coin_list).unwrap(), "BTC/KRW");
+    assert_eq!(normalize_user_symbol(" Eth/Usdt ", &coin_list).unwrap(), "ETH/USDT");
+}
+
+#[test]
+fn test_normalize_user_symbol_lists_closest_candidates() {
+    let coin_list = create_coin_list();
+
+    let error = normalize_user_symbol("btcusd", &coin_list).unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::InvalidSymbol("btcusd is not listed, closest: BTC/USDT".to_string())
+    );
+
+    let error = normalize_user_symbol("dogeeur", &coin_list).unwrap_err();
+    assert_eq!(error, ExchangeError::InvalidSymbol("dogeeur is not listed".to_string()));
+}
+
+#[tokio::test]
+async fn test_fuzzy_symbols_are_resolved_before_requests() {
+    let server = MockServer::start(|req| {
+        if req.path.starts_with("/v1/market/all") {
+            MockResponse::json(r#"[{"market":"KRW-BTC"},{"market":"KRW-ETH"}]"#)
+        } else {
+            MockResponse::json(r#"[{"market":"KRW-BTC","trade_price":95000000}]"#)
+        }
+    }).await;
+    let upbit = Upbit::new("test_api_key".to_string(), "test_secret".to_string())
+        .unwrap()
+        .with_api_url(&server.url)
+        .with_fuzzy_symbols(true);
+
+    let price = upbit.get_current_price(json!({ "symbol": "btckrw" })).await.unwrap();
+    assert_eq!(price.symbol, "BTC/KRW");
+    assert_eq!(server.requests()[1].path, "/v1/ticker?level=0&markets=KRW-BTC");
+
+    // 목록에 없는 심볼은 요청 없이 실패
+    let error = upbit.get_current_price(json!({ "symbol": "dogekrw" })).await.unwrap_err();
+    assert!(error.contains("dogekrw is not listed"));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_split_symbol_rejects_malformed_symbols() {
+    let cases = [
+        ("BTC/USDT", Some(("BTC", "USDT"))),
+        ("btc/krw", Some(("btc", "krw"))),
+        ("BTCUSDT", None),
+        ("", None),
+        ("/", None),
+        ("BTC/", None),
+        ("/USDT", None),
+        ("BTC/USDT/KRW", None),
+    ];
+    for (symbol, expected) in cases {
+        let expected = expected.ok_or(ExchangeError::InvalidSymbol(symbol.to_string()));
+        assert_eq!(split_symbol(symbol), expected, "{}", symbol);
+    }
+}
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+
+use crate::{
+    convert_price,
+    normalize_price,
+    Decimal,
+    ExchangeError,
+    FxRateProvider,
+    Price,
+    PriceSource,
+};
+
+// 헬퍼 구조체: 고정 환율을 돌려주는 FxRateProvider
+struct FixedRate(&'static str);
+
+#[async_trait]
+impl FxRateProvider for FixedRate {
+    async fn fx_rate(&self, from: &str, to: &str) -> Result<Decimal, ExchangeError> {
+        assert_eq!((from, to), ("KRW", "USD"));
+        Ok(Decimal::from_str(self.0).unwrap())
+    }
+}
+
+// 헬퍼 함수: Upbit BTC/KRW 가격 생성
+fn krw_price() -> Price {
+    Price {
+        exchange: "Upbit".to_string(),
+        symbol: "BTC/KRW".to_string(),
+        price: "140000000.0".to_string(),
+        source: PriceSource::Last,
+    }
+}
+
+#[test]
+fn test_convert_price_to_usd() {
+    let price = convert_price(&krw_price(), "USD", Decimal::from_str("0.00072").unwrap()).unwrap();
+    assert_eq!(price.exchange, "Upbit");
+    assert_eq!(price.symbol, "BTC/USD");
+    assert_eq!(price.price, "100800");
+    assert_eq!(price.source, PriceSource::Last);
+
+    let invalid = Price { price: "n/a".to_string(), ..krw_price() };
+    assert!(convert_price(&invalid, "USD", Decimal::ONE).is_err());
+}
+
+#[tokio::test]
+async fn test_normalize_price_uses_provider_rate() {
+    let price = normalize_price(&krw_price(), "USD", &FixedRate("0.00075")).await.unwrap();
+    assert_eq!(price.price, "105000");
+
+    // Already in the target quote, so the provider is not asked
+    let usd = Price { symbol: "BTC/USD".to_string(), ..krw_price() };
+    let price = normalize_price(&usd, "USD", &FixedRate("0")).await.unwrap();
+    assert_eq!(price.price, "140000000.0");
+}
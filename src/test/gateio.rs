@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use serde_json::json;
+use crate::gateio::{parse_orderbook, Gateio, GateioTrait};
+use crate::test::mock::{MockResponse, MockServer};
+use crate::{ Exchange, ExchangeError };
+
+// 헬퍼 함수: Gateio 객체 생성
+fn create_test_gateio() -> Gateio {
+    Gateio::new("test_api_key".to_string(), "test_secret".to_string()).unwrap()
+}
+
+// 헬퍼 함수: 에러 메시지 검증
+fn assert_gateio_creation_error(api_key: &str, secret: &str, expected_error: &str) {
+    let result = Gateio::new(api_key.to_string(), secret.to_string());
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), expected_error.to_string());
+}
+
+#[test]
+fn test_new_gateio_with_valid_credentials() {
+    let gateio = create_test_gateio();
+    assert_eq!(gateio.get_api_url(), "https://api.gateio.ws/");
+}
+
+#[test]
+fn test_new_gateio_with_empty_api_key() {
+    assert_gateio_creation_error("", "test_secret", "API key cannot be empty");
+}
+
+#[test]
+fn test_new_gateio_with_empty_secret() {
+    assert_gateio_creation_error("test_api_key", "", "Secret cannot be empty");
+}
+
+#[test]
+fn test_new_gateio_with_empty_credentials() {
+    assert_gateio_creation_error("", "", "API key cannot be empty");
+}
+
+#[test]
+fn test_get_end_point_with_key_existing() {
+    let gateio = create_test_gateio();
+    let endpoint = gateio.get_end_point_with_key("make_order");
+    assert!(endpoint.is_some());
+    assert_eq!(endpoint.unwrap(), &["POST".to_string(), "api/v4/spot/orders".to_string()]);
+
+    let endpoint = gateio.get_end_point_with_key("cancel_order");
+    assert!(endpoint.is_some());
+    assert_eq!(endpoint.unwrap(), &["DELETE".to_string(), "api/v4/spot/orders".to_string()]);
+}
+
+#[test]
+fn test_get_end_point_with_key_non_existing() {
+    let gateio = create_test_gateio();
+    let endpoint = gateio.get_end_point_with_key("non_existing");
+    assert!(endpoint.is_none());
+}
+
+#[test]
+fn test_get_signature() {
+    let gateio = create_test_gateio();
+    let signature = gateio.get_signature(
+        "GET",
+        "/api/v4/spot/orders",
+        "currency_pair=BTC_USDT&status=open",
+        "",
+        "1700000000"
+    );
+    // HMAC-SHA512 of "GET\n/api/v4/spot/orders\ncurrency_pair=BTC_USDT&status=open\n<SHA512 of "">\n1700000000"
+    assert_eq!(
+        signature.unwrap(),
+        "930db15ff60ffe9e7cac7e9415756efb78f2514ccf4f9dc5615bc58e2bec20ecdd9e24c0c66721e64f425260dcf3aa84df018cba0abbcd58130b81d9477eed57"
+    );
+}
+
+#[tokio::test]
+async fn test_place_order_sends_text_with_prefix() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"id":"12332324","text":"t-my-order-1"}"#)).await;
+    let gateio = create_test_gateio().with_api_url(&server.url);
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001",
+        "client_order_id": "my-order-1"
+    });
+    gateio.place_order(order).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("POST", "/api/v4/spot/orders"));
+    assert!(requests[0].body.contains(r#""currency_pair":"BTC_USDT""#));
+    assert!(requests[0].body.contains(r#""text":"t-my-order-1""#));
+}
+
+#[tokio::test]
+async fn test_place_order_maps_time_in_force() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"id":"12332324"}"#)).await;
+    let gateio = create_test_gateio().with_api_url(&server.url);
+    let order = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "limit", "price": "50000", "amount": "0.001" });
+
+    let mut post_only = order.clone();
+    post_only["post_only"] = json!(true);
+    gateio.place_order(post_only).await.unwrap();
+    let mut ioc = order.clone();
+    ioc["time_in_force"] = json!("IOC");
+    gateio.place_order(ioc).await.unwrap();
+    gateio.place_order(order).await.unwrap();
+
+    // post-only는 poc, 지정하지 않으면 기본값(gtc)에 맡김
+    let requests = server.requests();
+    assert!(requests[0].body.contains(r#""time_in_force":"poc""#));
+    assert!(requests[1].body.contains(r#""time_in_force":"ioc""#));
+    assert!(!requests[2].body.contains("time_in_force"));
+}
+
+#[tokio::test]
+async fn test_market_orders_omit_price_and_buy_by_quote_amount() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"id":"12332324"}"#)).await;
+    let gateio = create_test_gateio().with_api_url(&server.url);
+
+    let order = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "market", "amount": "0.001" });
+    let error = gateio.place_order(order).await.unwrap_err();
+    assert_eq!(error, "Invalid request: A market buy is sized by quote_amount, not amount");
+    assert!(server.requests().is_empty());
+
+    let buy = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "market", "quote_amount": "100" });
+    gateio.place_order(buy).await.unwrap();
+    let sell = json!({ "symbol": "BTC/USDT", "side": "sell", "order_type": "market", "amount": "0.001" });
+    gateio.place_order(sell).await.unwrap();
+
+    // 시장가 매수의 amount는 지불할 USDT 금액이고, 시장가 주문은 gtc를 받지 않음
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].body,
+        r#"{"amount":"100","currency_pair":"BTC_USDT","side":"buy","time_in_force":"ioc","type":"market"}"#
+    );
+    assert_eq!(
+        requests[1].body,
+        r#"{"amount":"0.001","currency_pair":"BTC_USDT","side":"sell","time_in_force":"ioc","type":"market"}"#
+    );
+}
+
+#[tokio::test]
+async fn test_generated_text_fits_the_length_limit() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"id":"12332324"}"#)).await;
+    let gateio = create_test_gateio().with_api_url(&server.url).with_auto_client_order_id();
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001"
+    });
+    gateio.place_order(order.clone()).await.unwrap();
+
+    let body: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+    let text = body["text"].as_str().unwrap();
+    assert!(text.starts_with("t-"));
+    assert_eq!(text.len(), 30);
+
+    // 호출자가 준 id가 너무 길면 요청 전에 거부
+    let mut order = order;
+    order["client_order_id"] = json!("a".repeat(29));
+    assert!(gateio.place_order(order).await.unwrap_err().contains("longer than 30 bytes"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_cancel_order_by_client_order_id() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"id":"12332324","status":"cancelled"}"#)).await;
+    let gateio = create_test_gateio().with_api_url(&server.url);
+
+    gateio.cancel_order(json!({ "symbol": "BTC/USDT", "client_order_id": "my-order-1" })).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[0].path, "/api/v4/spot/orders/t-my-order-1?currency_pair=BTC_USDT");
+    assert_eq!(requests[0].body, "");
+}
+
+#[tokio::test]
+async fn test_get_order_book() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"id":123456,"current":1623898993123,"update":1623898993121,"asks":[["1.52","1.151"],["1.53","1.218"]],"bids":[["1.17","201.863"],["1.16","725.464"]]}"#
+        )
+    }).await;
+    let gateio = create_test_gateio().with_api_url(&server.url);
+
+    let orderbook = gateio.get_order_book(json!({ "symbol": "BTC/USDT" })).await.unwrap();
+    assert_eq!(orderbook.market, "BTC/USDT");
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[0].ask_price, "1.52");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "725.464");
+    assert_eq!(orderbook.timestamp, Some(1623898993123));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/api/v4/spot/order_book?currency_pair=BTC_USDT&limit=30&with_id=true");
+}
+
+#[tokio::test]
+async fn test_invalid_key_is_returned_as_auth_error() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 401,
+        ..MockResponse::json(r#"{"label":"INVALID_KEY","message":"Invalid key provided"}"#)
+    }).await;
+    let gateio = create_test_gateio().with_api_url(&server.url);
+
+    let error = gateio.send_req_with_sign(BTreeMap::new(), "make_order").await.unwrap_err();
+    assert!(matches!(error, ExchangeError::Auth(message) if message == "INVALID_KEY: Invalid key provided"));
+}
+
+#[tokio::test]
+async fn test_missing_method_is_unsupported() {
+    let gateio = create_test_gateio();
+    assert!(gateio.supports("get_coin_list"));
+    assert!(!gateio.supports("get_ticker"));
+    assert!(!gateio.supports("place_order_confirmed"));
+
+    let error = gateio.get_ticker("BTC/USDT").await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::Unsupported { exchange: "Gateio".to_string(), method: "get_ticker".to_string() }
+    );
+    assert_eq!(error.to_string(), "Gateio get_ticker is not supported");
+}
+
+#[test]
+fn test_parse_orderbook_keeps_every_level_of_a_lopsided_book() {
+    let res = json!({ "current": 1623898993123u64, "bids": [["46000.5", "0.1"]], "asks": [["46001.25", "0.3"], ["46002", "1.5"]] });
+
+    let orderbook = parse_orderbook(&res, "BTC/USDT").unwrap();
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[1].ask_price, "46002");
+    // 짧은 쪽은 빈 값으로 채움
+    assert_eq!(orderbook.orderbook_unit[1].bid_price, "");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "");
+}
@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+use http::header::{ ACCEPT, AUTHORIZATION, CONTENT_TYPE };
+use http::Method;
+
+use crate::http_util::{ build_request, parse_http_method };
+use crate::ExchangeError;
+
+#[test]
+fn test_build_request_keeps_headers_and_body() {
+    let body = BTreeMap::from([("market", "KRW-BTC"), ("side", "bid")]);
+    let request = build_request(
+        "POST",
+        "https://api.upbit.com/v1/orders",
+        vec![(AUTHORIZATION, "Bearer token"), (CONTENT_TYPE, "application/json")],
+        body.clone()
+    ).unwrap();
+
+    assert_eq!(request.method(), Method::POST);
+    assert_eq!(request.uri(), "https://api.upbit.com/v1/orders");
+    assert_eq!(request.headers()[AUTHORIZATION], "Bearer token");
+    assert_eq!(request.headers()[CONTENT_TYPE], "application/json");
+    assert_eq!(*request.body(), body);
+}
+
+#[test]
+fn test_build_request_rejects_unknown_method() {
+    // http 크레이트는 "GETT"도 확장 메서드로 받아들임
+    let error = build_request("GETT", "https://api.binance.com/api/v3/ping", vec![], BTreeMap::new())
+        .unwrap_err();
+    assert_eq!(error, ExchangeError::InvalidRequest("Unknown HTTP method \"GETT\"".to_string()));
+
+    assert!(parse_http_method("get").is_err());
+    assert_eq!(parse_http_method("DELETE"), Ok(Method::DELETE));
+}
+
+#[test]
+fn test_build_request_rejects_invalid_uri() {
+    let result = build_request("GET", "not a uri", vec![(ACCEPT, "application/json")], BTreeMap::new());
+    assert!(matches!(result, Err(ExchangeError::InvalidRequest(_))));
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use serde_json::json;
+use crate::htx::{ parse_orderbook, parse_symbol, Htx, HtxTrait };
+use crate::test::mock::{ MockResponse, MockServer };
+use crate::{ Exchange, ExchangeError, MockClock };
+
+// 헬퍼 함수: Htx 객체 생성
+fn create_test_htx() -> Htx {
+    Htx::new("test_api_key".to_string(), "test_secret".to_string()).unwrap()
+}
+
+// 헬퍼 함수: 에러 메시지 검증
+fn assert_htx_creation_error(api_key: &str, secret: &str, expected_error: &str) {
+    let result = Htx::new(api_key.to_string(), secret.to_string());
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), expected_error.to_string());
+}
+
+#[test]
+fn test_new_htx_with_valid_credentials() {
+    let htx = create_test_htx();
+    assert_eq!(htx.get_api_url(), "https://api.huobi.pro/");
+}
+
+#[test]
+fn test_new_htx_with_empty_api_key() {
+    assert_htx_creation_error("", "test_secret", "API key cannot be empty");
+}
+
+#[test]
+fn test_new_htx_with_empty_secret() {
+    assert_htx_creation_error("test_api_key", "", "Secret cannot be empty");
+}
+
+#[test]
+fn test_get_end_point_with_key_non_existing() {
+    let htx = create_test_htx();
+    assert!(htx.get_end_point_with_key("non_existing").is_none());
+}
+
+#[test]
+fn test_get_signed_query() {
+    let htx = create_test_htx();
+    let query = htx.get_signed_query(
+        "GET",
+        "/v1/order/openOrders",
+        BTreeMap::from([("symbol", "btcusdt")]),
+        "2023-11-14T22:13:20"
+    );
+    // HMAC-SHA256 of "GET\napi.huobi.pro\n/v1/order/openOrders\n<정렬된 쿼리>"의 base64
+    assert_eq!(
+        query.unwrap(),
+        "AccessKeyId=test_api_key&SignatureMethod=HmacSHA256&SignatureVersion=2\
+         &Timestamp=2023-11-14T22%3A13%3A20&symbol=btcusdt\
+         &Signature=eY7p6gaJFH7Ry4RIG%2FHxPnyNQ9zyvO89l9ujA8z3%2F5g%3D"
+    );
+}
+
+#[tokio::test]
+async fn test_place_order_looks_up_spot_account() {
+    let server = MockServer::start(|request| {
+        if request.path.starts_with("/v1/account/accounts") {
+            MockResponse::json(
+                r#"{"status":"ok","data":[{"id":1001,"type":"margin"},{"id":1002,"type":"spot"}]}"#
+            )
+        } else {
+            MockResponse::json(r#"{"status":"ok","data":"59378"}"#)
+        }
+    }).await;
+    let htx = create_test_htx()
+        .with_api_url(&server.url)
+        .with_clock(Arc::new(MockClock::new(1_700_000_000_000)));
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001",
+        "client_order_id": "my-order-1"
+    });
+    htx.place_order(order.clone()).await.unwrap();
+    htx.place_order(order).await.unwrap();
+
+    // 계정 조회는 첫 주문에서 한 번만 일어나야 함
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].path.starts_with("/v1/account/accounts?AccessKeyId=test_api_key"));
+    assert_eq!(requests[1].method, "POST");
+    assert!(requests[1].path.starts_with("/v1/order/orders/place?AccessKeyId=test_api_key"));
+    assert!(requests[1].path.contains("&Timestamp=2023-11-14T22%3A13%3A20&Signature="));
+    assert!(requests[1].body.contains(r#""account-id":"1002""#));
+    assert!(requests[1].body.contains(r#""symbol":"btcusdt""#));
+    assert!(requests[1].body.contains(r#""type":"buy-limit""#));
+    assert!(requests[1].body.contains(r#""client-order-id":"my-order-1""#));
+}
+
+#[tokio::test]
+async fn test_market_buy_is_sized_by_quote_amount() {
+    let server = MockServer::start(|request| {
+        if request.path.starts_with("/v1/account/accounts") {
+            MockResponse::json(r#"{"status":"ok","data":[{"id":1002,"type":"spot"}]}"#)
+        } else {
+            MockResponse::json(r#"{"status":"ok","data":"59378"}"#)
+        }
+    }).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+
+    let order = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "market", "amount": "0.001" });
+    let error = htx.place_order(order).await.unwrap_err();
+    assert_eq!(error, "Invalid request: A market buy is sized by quote_amount, not amount");
+    assert!(server.requests().is_empty());
+
+    let order = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "market", "quote_amount": "100" });
+    htx.place_order(order).await.unwrap();
+
+    // 시장가 매수의 amount는 지불할 USDT 금액
+    let requests = server.requests();
+    assert!(requests[1].body.contains(r#""type":"buy-market""#));
+    assert!(requests[1].body.contains(r#""amount":"100""#));
+}
+
+#[tokio::test]
+async fn test_place_order_maps_time_in_force_to_order_type() {
+    let server = MockServer::start(|request| {
+        if request.path.starts_with("/v1/account/accounts") {
+            MockResponse::json(r#"{"status":"ok","data":[{"id":1002,"type":"spot"}]}"#)
+        } else {
+            MockResponse::json(r#"{"status":"ok","data":"59378"}"#)
+        }
+    }).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+    let order = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "limit", "price": "50000", "amount": "0.001" });
+
+    let mut post_only = order.clone();
+    post_only["post_only"] = json!(true);
+    htx.place_order(post_only).await.unwrap();
+    let mut ioc = order.clone();
+    ioc["time_in_force"] = json!("IOC");
+    htx.place_order(ioc).await.unwrap();
+    let mut fok = order;
+    fok["time_in_force"] = json!("FOK");
+    htx.place_order(fok).await.unwrap();
+
+    let requests = server.requests();
+    assert!(requests[1].body.contains(r#""type":"buy-limit-maker""#));
+    assert!(requests[2].body.contains(r#""type":"buy-ioc""#));
+    assert!(requests[3].body.contains(r#""type":"buy-limit-fok""#));
+
+    // 시장가 주문에는 적용되지 않음
+    let market = json!({ "symbol": "BTC/USDT", "side": "sell", "order_type": "market", "amount": "0.001", "time_in_force": "FOK" });
+    let error = htx.place_order(market).await.unwrap_err();
+    assert_eq!(error, "Invalid request: time_in_force and post_only only apply to limit orders");
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_cancel_order_by_order_id_and_client_order_id() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"status":"ok","data":"59378"}"#)).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+
+    htx.cancel_order(json!({ "symbol": "BTC/USDT", "order_id": "59378" })).await.unwrap();
+    htx.cancel_order(json!({ "symbol": "BTC/USDT", "client_order_id": "my-order-1" })).await.unwrap();
+
+    let requests = server.requests();
+    assert!(requests[0].path.starts_with("/v1/order/orders/59378/submitcancel?"));
+    assert!(requests[1].path.starts_with("/v1/order/orders/submitCancelClientOrder?"));
+    assert_eq!(requests[1].body, r#"{"client-order-id":"my-order-1"}"#);
+}
+
+#[tokio::test]
+async fn test_get_order_book() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"status":"ok","ch":"market.btcusdt.depth.step0","ts":1630000000123,"tick":{"ts":1630000000100,"version":1,"bids":[[46000.5,0.1],[45999.1,2]],"asks":[[46001.25,0.3],[46002,1.5]]}}"#
+        )
+    }).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+
+    let orderbook = htx.get_order_book(json!({ "symbol": "BTC/USDT" })).await.unwrap();
+    assert_eq!(orderbook.market, "BTC/USDT");
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[0].ask_price, "46001.25");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "2");
+    assert_eq!(orderbook.timestamp, Some(1630000000100));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/market/depth?depth=20&symbol=btcusdt&type=step0");
+}
+
+#[test]
+fn test_parse_orderbook_keeps_every_level_of_a_lopsided_book() {
+    let res = json!({
+        "status": "ok",
+        "tick": { "ts": 1630000000100u64, "bids": [[46000.5, 0.1]], "asks": [[46001.25, 0.3], [46002, 1.5]] }
+    });
+
+    let orderbook = parse_orderbook(&res, "BTC/USDT").unwrap();
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[1].ask_price, "46002");
+    // 짧은 쪽은 빈 값으로 채움
+    assert_eq!(orderbook.orderbook_unit[1].bid_price, "");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "");
+}
+
+#[tokio::test]
+async fn test_get_current_price() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"status":"ok","ch":"market.btcusdt.detail.merged","tick":{"close":46000.10,"bid":[46000.1,0.5],"ask":[46000.2,0.3]}}"#
+        )
+    }).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+
+    let price = htx.get_current_price(json!({ "symbol": "BTC/USDT" })).await.unwrap();
+    assert_eq!(price.price, "46000.10");
+    assert_eq!(server.requests()[0].path, "/market/detail/merged?symbol=btcusdt");
+}
+
+#[tokio::test]
+async fn test_get_coin_list_skips_offline_symbols() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"status":"ok","data":[{"base-currency":"btc","quote-currency":"usdt","symbol":"btcusdt","state":"online"},{"base-currency":"lun","quote-currency":"btc","symbol":"lunbtc","state":"offline"}]}"#
+        )
+    }).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+
+    let coin_list = htx.get_coin_list().await.unwrap();
+    assert_eq!(coin_list.market, "Htx");
+    assert_eq!(coin_list.coin_list, vec!["BTC/USDT".to_string()]);
+}
+
+#[tokio::test]
+async fn test_invalid_signature_is_returned_as_auth_error() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"status":"error","err-code":"api-signature-not-valid","err-msg":"Signature not valid","data":null}"#
+        )
+    }).await;
+    let htx = create_test_htx().with_api_url(&server.url);
+
+    let error = htx.send_req_with_sign(BTreeMap::new(), "accounts").await.unwrap_err();
+    assert!(matches!(
+        error,
+        ExchangeError::Auth(message) if message == "api-signature-not-valid: Signature not valid"
+    ));
+}
+
+#[test]
+fn test_parse_symbol() {
+    assert_eq!(parse_symbol("BTC/USDT").unwrap(), "btcusdt");
+    for symbol in ["BTCUSDT", "", "BTC/USDT/KRW", "BTC/"] {
+        assert_eq!(parse_symbol(symbol), Err(ExchangeError::InvalidSymbol(symbol.to_string())));
+    }
+}
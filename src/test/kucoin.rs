@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use serde_json::json;
+use crate::kucoin::{parse_orderbook, Kucoin, KucoinTrait};
+use crate::test::mock::{MockResponse, MockServer};
+use crate::{ Exchange, ExchangeError };
+
+// Helper function: Create a test Kucoin object
+fn create_test_kucoin() -> Kucoin {
+    Kucoin::new("test_api_key".to_string(), "test_secret".to_string(), "test_passphrase".to_string()).unwrap()
+}
+
+// Helper function: Assert error on Kucoin creation
+fn assert_kucoin_creation_error(api_key: &str, secret: &str, passphrase: &str, expected_error: &str) {
+    let result = Kucoin::new(api_key.to_string(), secret.to_string(), passphrase.to_string());
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), expected_error.to_string());
+}
+
+#[test]
+fn test_new_kucoin_with_valid_credentials() {
+    let kucoin = create_test_kucoin();
+    assert_eq!(kucoin.get_api_url(), "https://api.kucoin.com/");
+}
+
+#[test]
+fn test_new_kucoin_with_empty_api_key() {
+    assert_kucoin_creation_error("", "test_secret", "test_passphrase", "API key cannot be empty");
+}
+
+#[test]
+fn test_new_kucoin_with_empty_secret() {
+    assert_kucoin_creation_error("test_api_key", "", "test_passphrase", "Secret cannot be empty");
+}
+
+#[test]
+fn test_new_kucoin_with_empty_passphrase() {
+    assert_kucoin_creation_error("test_api_key", "test_secret", "", "passphrase cannot be empty");
+}
+
+#[test]
+fn test_new_kucoin_with_empty_credentials() {
+    assert_kucoin_creation_error("", "", "", "API key cannot be empty");
+}
+
+#[test]
+fn test_get_end_point_with_key_existing() {
+    let kucoin = create_test_kucoin();
+    let endpoint = kucoin.get_end_point_with_key("make_order");
+    assert!(endpoint.is_some());
+    assert_eq!(endpoint.unwrap(), &["POST".to_string(), "api/v1/orders".to_string()]);
+
+    let endpoint = kucoin.get_end_point_with_key("cancel_order");
+    assert!(endpoint.is_some());
+    assert_eq!(endpoint.unwrap(), &["DELETE".to_string(), "api/v1/orders".to_string()]);
+}
+
+#[test]
+fn test_get_end_point_with_key_non_existing() {
+    let kucoin = create_test_kucoin();
+    let endpoint = kucoin.get_end_point_with_key("non_existing");
+    assert!(endpoint.is_none());
+}
+
+#[test]
+fn test_sign() {
+    let kucoin = create_test_kucoin();
+
+    let signature = kucoin.sign("1700000000000", "GET", "/api/v1/orders?status=active", "");
+    assert_eq!(signature.unwrap(), "XAc6AUhXra4I9luYsBBeOZAzSmDhKNcgPpVbfRloT0Y=");
+
+    let signature = kucoin.sign(
+        "1700000000000",
+        "POST",
+        "/api/v1/orders",
+        r#"{"clientOid":"my-order-1","side":"buy"}"#
+    );
+    assert_eq!(signature.unwrap(), "iEwbBSzJ5Qi73LyNsFNpflEd6WaC24G+aJTL45nhn18=");
+}
+
+#[test]
+fn test_get_signed_passphrase() {
+    let kucoin = create_test_kucoin();
+    assert_eq!(
+        kucoin.get_signed_passphrase().unwrap(),
+        "NflEcbioji3+mKmkkA9aVOD/+/hBtQO6xdOtJaq5NX4="
+    );
+}
+
+#[tokio::test]
+async fn test_place_order_always_sends_client_oid() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"code":"200000","data":{"orderId":"5bd6e9286d99522a52e458de"}}"#)).await;
+    let kucoin = create_test_kucoin().with_api_url(&server.url);
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001"
+    });
+    kucoin.place_order(order).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("POST", "/api/v1/orders"));
+    assert!(requests[0].body.contains(r#""symbol":"BTC-USDT""#));
+    assert!(requests[0].body.contains(r#""clientOid":""#));
+}
+
+#[tokio::test]
+async fn test_place_order_sends_time_in_force_and_post_only() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"code":"200000","data":{"orderId":"5bd6e9286d99522a52e458de"}}"#)).await;
+    let kucoin = create_test_kucoin().with_api_url(&server.url);
+    let order = json!({ "symbol": "BTC/USDT", "side": "buy", "order_type": "limit", "price": "50000", "amount": "0.001" });
+
+    let mut post_only = order.clone();
+    post_only["post_only"] = json!(true);
+    kucoin.place_order(post_only).await.unwrap();
+    let mut fok = order;
+    fok["time_in_force"] = json!("FOK");
+    kucoin.place_order(fok).await.unwrap();
+
+    let requests = server.requests();
+    assert!(requests[0].body.contains(r#""postOnly":"true""#));
+    assert!(!requests[0].body.contains("timeInForce"));
+    assert!(requests[1].body.contains(r#""timeInForce":"FOK""#));
+    assert!(!requests[1].body.contains("postOnly"));
+}
+
+#[tokio::test]
+async fn test_cancel_order_by_client_order_id() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"code":"200000","data":{"clientOid":"my-order-1"}}"#)).await;
+    let kucoin = create_test_kucoin().with_api_url(&server.url);
+
+    kucoin.cancel_order(json!({ "symbol": "BTC/USDT", "client_order_id": "my-order-1" })).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[0].path, "/api/v1/order/client-order/my-order-1");
+
+    // 예약 문자가 든 id는 경로를 바꾸지 않도록 인코딩
+    kucoin.cancel_order(json!({ "symbol": "BTC/USDT", "client_order_id": "a/b?c d" })).await.unwrap();
+    assert_eq!(server.requests()[1].path, "/api/v1/order/client-order/a%2Fb%3Fc%20d");
+}
+
+#[tokio::test]
+async fn test_get_order_book() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"code":"200000","data":{"time":1550653727731,"sequence":"27","bids":[["6500.12","0.45054140"],["6500.11","0.45054140"]],"asks":[["6500.16","0.57753524"],["6500.15","0.57753524"]]}}"#
+        )
+    }).await;
+    let kucoin = create_test_kucoin().with_api_url(&server.url);
+
+    let orderbook = kucoin.get_order_book(json!({ "symbol": "BTC/USDT" })).await.unwrap();
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[0].ask_price, "6500.16");
+    assert_eq!(orderbook.orderbook_unit[0].bid_price, "6500.12");
+    assert_eq!(orderbook.timestamp, Some(1550653727731));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/api/v1/market/orderbook/level2_20?symbol=BTC-USDT");
+}
+
+#[tokio::test]
+async fn test_invalid_passphrase_is_returned_as_auth_error() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 401,
+        ..MockResponse::json(r#"{"code":"400004","msg":"Invalid KC-API-PASSPHRASE"}"#)
+    }).await;
+    let kucoin = create_test_kucoin().with_api_url(&server.url);
+
+    let error = kucoin.send_req_with_sign(BTreeMap::new(), "make_order").await.unwrap_err();
+    assert!(matches!(error, ExchangeError::Auth(message) if message == "400004: Invalid KC-API-PASSPHRASE"));
+}
+
+#[test]
+fn test_parse_orderbook_keeps_every_level_of_a_lopsided_book() {
+    let res = json!({
+        "code": "200000",
+        "data": { "time": 1630000000100u64, "bids": [["46000.5", "0.1"]], "asks": [["46001.25", "0.3"], ["46002", "1.5"]] }
+    });
+
+    let orderbook = parse_orderbook(&res, "BTC/USDT").unwrap();
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[1].ask_price, "46002");
+    // 짧은 쪽은 빈 값으로 채움
+    assert_eq!(orderbook.orderbook_unit[1].bid_price, "");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "");
+}
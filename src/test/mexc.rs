@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use serde_json::json;
+use crate::mexc::{ parse_orderbook, parse_symbol, Mexc, MexcTrait };
+use crate::test::mock::{ MockResponse, MockServer };
+use crate::{ canonicalize_params, Exchange, ExchangeError };
+
+// 헬퍼 함수: Mexc 객체 생성
+fn create_test_mexc() -> Mexc {
+    Mexc::new("test_api_key".to_string(), "test_secret".to_string()).unwrap()
+}
+
+// 헬퍼 함수: 에러 메시지 검증
+fn assert_mexc_creation_error(api_key: &str, secret: &str, expected_error: &str) {
+    let result = Mexc::new(api_key.to_string(), secret.to_string());
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), expected_error.to_string());
+}
+
+#[test]
+fn test_new_mexc_with_valid_credentials() {
+    let mexc = create_test_mexc();
+    assert_eq!(mexc.get_api_url(), "https://api.mexc.com/");
+}
+
+#[test]
+fn test_new_mexc_with_empty_api_key() {
+    assert_mexc_creation_error("", "test_secret", "API key cannot be empty");
+}
+
+#[test]
+fn test_new_mexc_with_empty_secret() {
+    assert_mexc_creation_error("test_api_key", "", "Secret cannot be empty");
+}
+
+#[test]
+fn test_get_end_point_with_key_existing() {
+    let mexc = create_test_mexc();
+    let endpoint = mexc.get_end_point_with_key("make_order");
+    assert_eq!(endpoint, Some(&["POST".to_string(), "api/v3/order".to_string()]));
+
+    let endpoint = mexc.get_end_point_with_key("cancel_order");
+    assert_eq!(endpoint, Some(&["DELETE".to_string(), "api/v3/order".to_string()]));
+}
+
+#[test]
+fn test_get_end_point_with_key_non_existing() {
+    let mexc = create_test_mexc();
+    assert!(mexc.get_end_point_with_key("non_existing").is_none());
+}
+
+#[test]
+fn test_get_signature() {
+    let mexc = create_test_mexc();
+    let params = canonicalize_params([
+        ("symbol", "BTCUSDT"),
+        ("side", "BUY"),
+        ("type", "LIMIT"),
+        ("quantity", "0.001"),
+        ("price", "50000"),
+        ("newClientOrderId", "my-order-1"),
+        ("timestamp", "1700000000000"),
+    ]);
+    // 정렬된 쿼리 문자열의 HMAC-SHA256 hex
+    assert_eq!(
+        mexc.get_signature(&params).unwrap(),
+        "6cd78e0d973cf6a6645b561850a1e0875dc877ce2c435a67d4227cd559566007"
+    );
+}
+
+#[tokio::test]
+async fn test_place_order_signs_query_string() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"symbol":"BTCUSDT","orderId":"C02__443776347957968896","orderListId":-1}"#)
+    }).await;
+    let mexc = create_test_mexc().with_api_url(&server.url);
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "BUY",
+        "order_type": "LIMIT",
+        "price": "50000",
+        "amount": "0.001",
+        "client_order_id": "my-order-1",
+        "timestamp": 1700000000000u64
+    });
+    mexc.place_order(order).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].path,
+        "/api/v3/order?newClientOrderId=my-order-1&price=50000&quantity=0.001&side=BUY\
+         &symbol=BTCUSDT&timestamp=1700000000000&type=LIMIT\
+         &signature=6cd78e0d973cf6a6645b561850a1e0875dc877ce2c435a67d4227cd559566007"
+    );
+    assert_eq!(requests[0].body, "");
+}
+
+#[tokio::test]
+async fn test_place_order_maps_time_in_force_to_order_type() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"symbol":"BTCUSDT","orderId":"C02__443776347957968896","orderListId":-1}"#)
+    }).await;
+    let mexc = create_test_mexc().with_api_url(&server.url);
+    let order = json!({ "symbol": "BTC/USDT", "side": "BUY", "order_type": "LIMIT", "price": "50000", "amount": "0.001" });
+
+    let mut post_only = order.clone();
+    post_only["post_only"] = json!(true);
+    mexc.place_order(post_only).await.unwrap();
+    let mut ioc = order.clone();
+    ioc["time_in_force"] = json!("IOC");
+    mexc.place_order(ioc).await.unwrap();
+    let mut fok = order;
+    fok["time_in_force"] = json!("FOK");
+    mexc.place_order(fok).await.unwrap();
+
+    let requests = server.requests();
+    assert!(requests[0].path.contains("&type=LIMIT_MAKER&"));
+    assert!(requests[1].path.contains("&type=IMMEDIATE_OR_CANCEL&"));
+    assert!(requests[2].path.contains("&type=FILL_OR_KILL&"));
+}
+
+#[tokio::test]
+async fn test_cancel_order_by_client_order_id() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"symbol":"BTCUSDT","status":"CANCELED"}"#)).await;
+    let mexc = create_test_mexc().with_api_url(&server.url);
+
+    mexc.cancel_order(json!({ "symbol": "BTC/USDT", "client_order_id": "my-order-1" })).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert!(requests[0].path.starts_with("/api/v3/order?origClientOrderId=my-order-1&symbol=BTCUSDT&"));
+}
+
+#[tokio::test]
+async fn test_get_order_book() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"lastUpdateId":1377043928,"bids":[["46000.50","0.1"],["45999.10","2"]],"asks":[["46001.25","0.3"],["46002.00","1.5"]],"timestamp":1630000000123}"#
+        )
+    }).await;
+    let mexc = create_test_mexc().with_api_url(&server.url);
+
+    let orderbook = mexc.get_order_book(json!({ "symbol": "BTC/USDT" })).await.unwrap();
+    assert_eq!(orderbook.market, "BTC/USDT");
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[0].ask_price, "46001.25");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "2");
+    assert_eq!(orderbook.timestamp, Some(1630000000123));
+    assert_eq!(server.requests()[0].path, "/api/v3/depth?symbol=BTCUSDT");
+}
+
+#[tokio::test]
+async fn test_invalid_signature_is_returned_as_auth_error() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 400,
+        ..MockResponse::json(r#"{"code":700002,"msg":"Signature for this request is not valid."}"#)
+    }).await;
+    let mexc = create_test_mexc().with_api_url(&server.url);
+
+    let error = mexc.send_req_with_sign(BTreeMap::new(), "make_order").await.unwrap_err();
+    assert!(matches!(
+        error,
+        ExchangeError::Auth(message) if message == "700002: Signature for this request is not valid."
+    ));
+}
+
+#[test]
+fn test_parse_symbol() {
+    assert_eq!(parse_symbol("BTC/USDT").unwrap(), "BTCUSDT");
+    for symbol in ["BTCUSDT", "", "BTC/USDT/KRW", "BTC/"] {
+        assert_eq!(parse_symbol(symbol), Err(ExchangeError::InvalidSymbol(symbol.to_string())));
+    }
+}
+
+#[test]
+fn test_parse_orderbook_keeps_every_level_of_a_lopsided_book() {
+    let res = json!({ "lastUpdateId": 1, "timestamp": 1630000000100u64, "bids": [["46000.5", "0.1"]], "asks": [["46001.25", "0.3"], ["46002", "1.5"]] });
+
+    let orderbook = parse_orderbook(&res, "BTC/USDT").unwrap();
+    assert_eq!(orderbook.orderbook_unit.len(), 2);
+    assert_eq!(orderbook.orderbook_unit[1].ask_price, "46002");
+    // 짧은 쪽은 빈 값으로 채움
+    assert_eq!(orderbook.orderbook_unit[1].bid_price, "");
+    assert_eq!(orderbook.orderbook_unit[1].bid_size, "");
+}
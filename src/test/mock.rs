@@ -0,0 +1,359 @@
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::{ SinkExt, StreamExt };
+use serde_json::Value;
+use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+use tokio::net::{ TcpListener, TcpStream };
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{ CoinList, Exchange, Interceptor, OrderBook, OrderBookUnit, Price, PriceSource };
+
+// 테스트용 HTTP 서버가 수신한 요청
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+// 테스트용 HTTP 서버의 응답
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn json(body: &str) -> Self {
+        MockResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+}
+
+// 헬퍼 구조체: 로컬 포트에서 미리 정해진 응답을 돌려주고 요청을 기록하는 HTTP 서버
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub async fn start<F>(handler: F) -> MockServer
+        where F: Fn(&RecordedRequest) -> MockResponse + Send + Sync + 'static
+    {
+        MockServer::start_delayed(Duration::ZERO, handler).await
+    }
+
+    // 모든 응답을 `delay`만큼 늦게 보내는 서버
+    pub async fn start_delayed<F>(delay: Duration, handler: F) -> MockServer
+        where F: Fn(&RecordedRequest) -> MockResponse + Send + Sync + 'static
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let handler = Arc::new(handler);
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    serve(stream, handler.as_ref(), recorded, delay).await;
+                });
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve<F>(
+    mut stream: TcpStream,
+    handler: &F,
+    recorded: Arc<Mutex<Vec<RecordedRequest>>>,
+    delay: Duration
+)
+    where F: Fn(&RecordedRequest) -> MockResponse
+{
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    recorded.lock().unwrap().push(request.clone());
+
+    let response = handler(&request);
+    tokio::time::sleep(delay).await;
+    let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+    for (key, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", key, value));
+    }
+    head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", response.body.len()));
+
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(&response.body).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position + 4;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<Vec<(String, String)>>();
+
+    let content_length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let body = String::from_utf8_lossy(&buffer[header_end..]).to_string();
+
+    Some(RecordedRequest { method, path, body })
+}
+
+// 헬퍼 구조체: 접속한 클라이언트에게 정해진 텍스트 프레임을 보내고 수신 메시지를 기록하는 WebSocket 서버
+pub struct MockWsServer {
+    pub url: String,
+    received: Arc<Mutex<Vec<String>>>,
+    connections: Arc<Mutex<usize>>,
+}
+
+impl MockWsServer {
+    pub async fn start(frames: Vec<String>) -> MockWsServer {
+        MockWsServer::listen(vec![frames], false).await
+    }
+
+    // 연결마다 다른 프레임을 보내는 서버. 준비된 세션이 끝나면 더 이상 접속을 받지 않음
+    pub async fn start_sessions(sessions: Vec<Vec<String>>) -> MockWsServer {
+        MockWsServer::listen(sessions, false).await
+    }
+
+    // 핸드셰이크 직후 매번 연결을 끊는 서버
+    pub async fn start_dropping() -> MockWsServer {
+        MockWsServer::listen(Vec::new(), true).await
+    }
+
+    async fn listen(sessions: Vec<Vec<String>>, drop_connections: bool) -> MockWsServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let connections = Arc::new(Mutex::new(0));
+
+        let recorded = received.clone();
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            let mut sessions = sessions.into_iter();
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let Ok(mut socket) = accept_async(stream).await else {
+                    continue;
+                };
+                *accepted.lock().unwrap() += 1;
+                if drop_connections {
+                    let _ = socket.close(None).await;
+                    continue;
+                }
+                let Some(frames) = sessions.next() else {
+                    return;
+                };
+
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    for frame in frames {
+                        if socket.send(Message::text(frame)).await.is_err() {
+                            return;
+                        }
+                    }
+                    while let Some(Ok(message)) = socket.next().await {
+                        if let Message::Text(text) = message {
+                            recorded.lock().unwrap().push(text.to_string());
+                        }
+                    }
+                });
+            }
+        });
+
+        MockWsServer { url, received, connections }
+    }
+
+    pub fn connections(&self) -> usize {
+        *self.connections.lock().unwrap()
+    }
+
+    // 서버 태스크가 메시지를 기록할 때까지 최대 1초 대기
+    pub async fn received(&self, count: usize) -> Vec<String> {
+        for _ in 0..100 {
+            if self.received.lock().unwrap().len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        self.received.lock().unwrap().clone()
+    }
+}
+
+// 헬퍼 구조체: 관찰한 요청과 응답을 기록하는 Interceptor
+#[derive(Default)]
+pub struct RecordingInterceptor {
+    // (method, uri, params)
+    pub requests: Mutex<Vec<(String, String, String)>>,
+    // (status, body_len)
+    pub responses: Mutex<Vec<(u16, usize)>>,
+}
+
+impl Interceptor for RecordingInterceptor {
+    fn on_request(&self, method: &str, uri: &str, params: &str) {
+        self.requests.lock().unwrap().push((method.to_string(), uri.to_string(), params.to_string()));
+    }
+
+    fn on_response(&self, status: u16, body_len: usize, _elapsed: Duration) {
+        self.responses.lock().unwrap().push((status, body_len));
+    }
+}
+
+// 헬퍼 구조체: 지연 시간과 응답을 지정할 수 있는 Exchange 구현
+pub struct MockExchange {
+    pub name: String,
+    pub delay: Duration,
+    pub price: Result<String, String>,
+    pub orderbook_unit: Vec<OrderBookUnit>,
+    pub coin_list: Option<Vec<String>>,
+}
+
+impl MockExchange {
+    pub fn new(name: &str, delay_millis: u64, price: Result<&str, &str>) -> Self {
+        MockExchange {
+            name: name.to_string(),
+            delay: Duration::from_millis(delay_millis),
+            price: price.map(|p| p.to_string()).map_err(|e| e.to_string()),
+            orderbook_unit: Vec::new(),
+            coin_list: None,
+        }
+    }
+
+    // (ask_price, bid_price) 목록으로 호가 설정
+    pub fn with_order_book(mut self, levels: &[(&str, &str)]) -> Self {
+        self.orderbook_unit = levels
+            .iter()
+            .map(|(ask_price, bid_price)| OrderBookUnit {
+                ask_price: ask_price.to_string(),
+                bid_price: bid_price.to_string(),
+                ask_size: "1".to_string(),
+                bid_size: "1".to_string(),
+            })
+            .collect();
+        self
+    }
+
+    // 상장된 심볼 목록 설정
+    pub fn with_coin_list(mut self, symbols: &[&str]) -> Self {
+        self.coin_list = Some(symbols.iter().map(|symbol| symbol.to_string()).collect());
+        self
+    }
+}
+
+#[async_trait]
+impl Exchange for MockExchange {
+    async fn place_order(&self, _req: Value) -> Result<Value, String> {
+        Err("Not implemented".to_string())
+    }
+
+    async fn cancel_order(&self, _req: Value) -> Result<Value, String> {
+        Err("Not implemented".to_string())
+    }
+
+    async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
+        tokio::time::sleep(self.delay).await;
+        self.price.clone()?;
+        Ok(OrderBook {
+            market: req["symbol"].as_str().unwrap_or_default().to_string(),
+            exchange: self.name.clone(),
+            orderbook_unit: self.orderbook_unit.clone(),
+            timestamp: None,
+        })
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn get_current_price(&self, req: Value) -> Result<Price, String> {
+        tokio::time::sleep(self.delay).await;
+        let price = self.price.clone()?;
+        Ok(Price {
+            exchange: self.name.clone(),
+            symbol: req["symbol"].as_str().unwrap_or_default().to_string(),
+            price,
+            source: PriceSource::Last,
+        })
+    }
+
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        let coin_list = self.coin_list.clone().ok_or("Not implemented".to_string())?;
+        Ok(CoinList { market: self.name.clone(), coin_list })
+    }
+}
+
+// 헬퍼 구조체: 시세 API 없이 호가만 제공하는 거래소. get_current_price는 트레이트 기본 구현을 사용
+pub struct OrderBookOnlyExchange(pub MockExchange);
+
+#[async_trait]
+impl Exchange for OrderBookOnlyExchange {
+    async fn place_order(&self, req: Value) -> Result<Value, String> {
+        self.0.place_order(req).await
+    }
+
+    async fn cancel_order(&self, req: Value) -> Result<Value, String> {
+        self.0.cancel_order(req).await
+    }
+
+    async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
+        self.0.get_order_book(req).await
+    }
+
+    fn get_name(&self) -> String {
+        self.0.get_name()
+    }
+
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        self.0.get_coin_list().await
+    }
+}
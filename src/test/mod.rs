@@ -1,4 +1,111 @@
 mod binance;
 mod bithumb;
+mod coin_list;
+mod fx;
+mod gateio;
+mod htx;
+mod http_util;
+mod kucoin;
+mod mexc;
+mod mock;
+mod multi_exchange;
 mod okx;
+mod order;
+mod order_guard;
+mod orderbook;
+mod pnl;
+mod rate_limit;
+mod registry;
+mod sign;
+mod stream;
+mod symbol_info;
 mod upbit;
+
+use std::collections::BTreeMap;
+
+use crate::binance::{ Binance, BinanceTrait };
+use crate::bithumb::{ Bithumb, BithumbTrait };
+use crate::gateio::{ Gateio, GateioTrait };
+use crate::htx::{ Htx, HtxTrait };
+use crate::kucoin::{ Kucoin, KucoinTrait };
+use crate::mexc::{ Mexc, MexcTrait };
+use crate::okx::{ Okx, OkxTrait };
+use crate::upbit::{ Upbit, UpbitTrait };
+
+#[test]
+fn test_every_exchange_has_core_endpoints() {
+    let key = || "test_api_key".to_string();
+    let secret = || "test_secret".to_string();
+    let passphrase = || "test_passphrase".to_string();
+    let exchanges: Vec<(&str, BTreeMap<String, [String; 2]>, [(&str, &str, &str); 5])> = vec![
+        ("Binance", Binance::new(key(), secret()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "api/v3/order"),
+            ("cancel_order", "DELETE", "api/v3/order"),
+            ("order_book", "GET", "api/v3/depth"),
+            ("current_price", "GET", "api/v3/ticker/price"),
+            ("coin_list", "GET", "api/v3/exchangeInfo"),
+        ]),
+        ("Bithumb", Bithumb::new(key(), secret()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "v1/orders"),
+            ("cancel_order", "DELETE", "v1/order"),
+            ("order_book", "GET", "v1/orderbook"),
+            ("current_price", "GET", "v1/ticker"),
+            ("coin_list", "GET", "v1/market/all"),
+        ]),
+        ("Gateio", Gateio::new(key(), secret()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "api/v4/spot/orders"),
+            ("cancel_order", "DELETE", "api/v4/spot/orders"),
+            ("order_book", "GET", "api/v4/spot/order_book"),
+            ("current_price", "GET", "api/v4/spot/tickers"),
+            ("coin_list", "GET", "api/v4/spot/currency_pairs"),
+        ]),
+        ("Htx", Htx::new(key(), secret()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "v1/order/orders/place"),
+            ("cancel_order", "POST", "v1/order/orders"),
+            ("order_book", "GET", "market/depth"),
+            ("current_price", "GET", "market/detail/merged"),
+            ("coin_list", "GET", "v1/common/symbols"),
+        ]),
+        ("Kucoin", Kucoin::new(key(), secret(), passphrase()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "api/v1/orders"),
+            ("cancel_order", "DELETE", "api/v1/orders"),
+            ("order_book", "GET", "api/v1/market/orderbook/level2_20"),
+            ("current_price", "GET", "api/v1/market/orderbook/level1"),
+            ("coin_list", "GET", "api/v1/symbols"),
+        ]),
+        ("Mexc", Mexc::new(key(), secret()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "api/v3/order"),
+            ("cancel_order", "DELETE", "api/v3/order"),
+            ("order_book", "GET", "api/v3/depth"),
+            ("current_price", "GET", "api/v3/ticker/price"),
+            ("coin_list", "GET", "api/v3/exchangeInfo"),
+        ]),
+        ("Okx", Okx::new(key(), secret(), passphrase()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "api/v5/trade/order"),
+            ("cancel_order", "POST", "api/v5/trade/cancel-order"),
+            ("order_book", "GET", "api/v5/market/books-full"),
+            ("current_price", "GET", "api/v5/market/ticker"),
+            ("coin_list", "GET", "api/v5/public/instruments"),
+        ]),
+        ("Upbit", Upbit::new(key(), secret()).unwrap().get_end_point().clone(), [
+            ("make_order", "POST", "v1/orders"),
+            ("cancel_order", "DELETE", "v1/order"),
+            ("order_book", "GET", "v1/orderbook"),
+            ("current_price", "GET", "v1/ticker"),
+            ("coin_list", "GET", "v1/market/all"),
+        ]),
+    ];
+
+    // 필수 엔드포인트가 모두 존재하는지 확인
+    for (exchange, endpoints, expected) in &exchanges {
+        for (key, method, path) in expected {
+            assert_eq!(
+                endpoints.get(*key),
+                Some(&[method.to_string(), path.to_string()]),
+                "{} is missing endpoint {}",
+                exchange,
+                key
+            );
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::{ Duration, Instant };
+
+use crate::test::mock::MockExchange;
+use crate::{ best_bid_ask, get_order_books_with_limit, get_prices_all, Exchange, ExchangeError };
+
+#[tokio::test]
+async fn test_get_prices_all_preserves_order() {
+    let exchanges: Vec<Box<dyn Exchange>> = vec![
+        Box::new(MockExchange::new("Slow", 200, Ok("50000"))),
+        Box::new(MockExchange::new("Failing", 0, Err("connection reset"))),
+        Box::new(MockExchange::new("Fast", 10, Ok("50010"))),
+    ];
+
+    let started = Instant::now();
+    let prices = get_prices_all(&exchanges, "BTC/USDT").await;
+
+    // The calls run concurrently, so the total time is bounded by the slowest exchange
+    assert!(started.elapsed().as_millis() < 400);
+    assert_eq!(prices.len(), 3);
+    assert_eq!(prices[0].as_ref().unwrap().exchange, "Slow");
+    assert_eq!(prices[0].as_ref().unwrap().price, "50000");
+    assert_eq!(
+        prices[1].as_ref().unwrap_err(),
+        &ExchangeError::Request("connection reset".to_string())
+    );
+    assert_eq!(prices[2].as_ref().unwrap().exchange, "Fast");
+    assert_eq!(prices[2].as_ref().unwrap().symbol, "BTC/USDT");
+}
+
+#[tokio::test]
+async fn test_best_bid_ask_picks_venue_per_side() {
+    let exchanges: Vec<Box<dyn Exchange>> = vec![
+        Box::new(
+            MockExchange::new("Upbit", 20, Ok("50000"))
+                .with_order_book(&[("50010", "50005"), ("50020", "50000")])
+        ),
+        Box::new(
+            MockExchange::new("Okx", 0, Ok("50000"))
+                .with_order_book(&[("50008", "50001"), ("50009", "49990")])
+        ),
+        Box::new(MockExchange::new("Failing", 0, Err("maintenance"))),
+    ];
+
+    let best_quote = best_bid_ask(&exchanges, "BTC/USDT").await.unwrap();
+    assert_eq!(best_quote.best_bid_exchange, "Upbit");
+    assert_eq!(best_quote.best_bid, "50005");
+    assert_eq!(best_quote.best_ask_exchange, "Okx");
+    assert_eq!(best_quote.best_ask, "50008");
+    assert_eq!(best_quote.skipped, vec!["Failing: maintenance".to_string()]);
+}
+
+#[tokio::test]
+async fn test_best_bid_ask_fails_when_all_exchanges_fail() {
+    let exchanges: Vec<Box<dyn Exchange>> = vec![
+        Box::new(MockExchange::new("Failing", 0, Err("maintenance")))
+    ];
+
+    assert!(best_bid_ask(&exchanges, "BTC/USDT").await.is_err());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_get_order_books_returns_results_in_order() {
+    let exchange = MockExchange::new("Mock", 100, Ok("50000")).with_order_book(&[("50010", "49990")]);
+
+    let started = tokio::time::Instant::now();
+    let orderbooks = exchange.get_order_books(&["BTC/USDT", "ETH/USDT", "XRP/USDT"]).await;
+
+    // 기본 동시 요청 수(4) 안에서 세 호가를 한꺼번에 조회
+    assert_eq!(started.elapsed(), Duration::from_millis(100));
+    let markets = orderbooks
+        .iter()
+        .map(|orderbook| orderbook.as_ref().unwrap().market.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(markets, vec!["BTC/USDT", "ETH/USDT", "XRP/USDT"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_get_order_books_with_limit_bounds_concurrency() {
+    let exchange = MockExchange::new("Mock", 100, Ok("50000"));
+
+    // 한 번에 하나씩만 요청하므로 세 번의 지연이 누적됨
+    let started = tokio::time::Instant::now();
+    let orderbooks = get_order_books_with_limit(&exchange, &["BTC/USDT", "ETH/USDT", "XRP/USDT"], 1).await;
+    assert_eq!(started.elapsed(), Duration::from_millis(300));
+    assert_eq!(orderbooks.len(), 3);
+    assert_eq!(orderbooks[2].as_ref().unwrap().market, "XRP/USDT");
+}
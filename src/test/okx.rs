@@ -1,5 +1,26 @@
 use std::collections::BTreeMap;
-use crate::okx::{Okx, OkxTrait};
+use std::sync::Arc;
+use futures::StreamExt;
+use serde_json::{ json, Value };
+use crate::okx::{
+    check_response,
+    get_access_timestamp,
+    get_login_message,
+    get_signed_payload,
+    parse_account_info,
+    parse_deposit_address,
+    parse_mark_price,
+    parse_markets,
+    parse_symbol,
+    parse_symbol_info,
+    parse_ticker,
+    parse_trading_fees,
+    Okx,
+    OkxTrait,
+};
+use crate::test::mock::{MockResponse, MockServer, MockWsServer};
+use crate::upbit::{Upbit, UpbitTrait};
+use crate::{top_by_volume, Clock, Exchange, ExchangeError, MockClock, OrderState, PriceSource, SystemClock};
 
 // Helper function: Create a test Okx object
 fn create_test_okx() -> Okx {
@@ -31,7 +52,7 @@ fn test_new_okx_with_empty_secret() {
 
 #[test]
 fn test_new_okx_with_empty_passphrase() {
-    assert_okx_creation_error("test_api_key", "test_secret", "", "passphrase cannout be empty");
+    assert_okx_creation_error("test_api_key", "test_secret", "", "passphrase cannot be empty");
 }
 
 #[test]
@@ -39,18 +60,6 @@ fn test_new_okx_with_empty_credentials() {
     assert_okx_creation_error("", "", "", "API key cannot be empty");
 }
 
-#[test]
-fn test_get_end_point() {
-    let okx = create_test_okx();
-    let endpoints = okx.get_end_point();
-    let expected_endpoints = BTreeMap::from([
-        ("make_order".to_string(), ["POST".to_string(), "api/v5/trade/order".to_string()]),
-        ("cancel_order".to_string(), ["POST".to_string(), "api/v5/trade/cancel-order".to_string()])
-    ]);
-
-    assert_eq!(endpoints, &expected_endpoints);
-}
-
 #[test]
 fn test_get_end_point_with_key_existing() {
     let okx = create_test_okx();
@@ -85,11 +94,752 @@ fn test_get_signature() {
     let method = "POST".to_string();
     let endpoint = "api/v5/trade/order".to_string();
 
-    let signature = okx.get_signature(&params, timestamp.clone(), method.clone(), endpoint.clone());
-    assert!(signature.is_ok());
-    // Note: Actual value of the signature would depend on the HMAC calculation
-    // In a real test, you might want to compare it with a known correct value
-    // For this example, we just check if it's not an error.
+    let (request_path, body) = get_signed_payload(&params, &method, &endpoint);
+    let signature = okx.get_signature(&timestamp, &method, &request_path, body.as_ref());
+    // Base64 HMAC-SHA256 of the timestamp, method, request path and JSON body, concatenated
+    assert_eq!(signature.unwrap(), "+soj654hPVHkRxrsIRgCXtJ1UW2+JCLy7ASS41wzucM=");
+}
+
+#[test]
+fn test_signature_with_injected_clock_has_known_value() {
+    let okx = create_test_okx();
+    let clock: Arc<dyn Clock> = Arc::new(MockClock::new(1700000000000));
+    let timestamp = get_access_timestamp(&json!({}), &clock);
+    assert_eq!(timestamp, "2023-11-14T22:13:20.000Z");
+
+    let (request_path, body) = get_signed_payload(&BTreeMap::new(), "GET", "/api/v5/account/balance");
+    let signature = okx.get_signature(&timestamp, "GET", &request_path, body.as_ref());
+    // Base64 HMAC-SHA256 of "2023-11-14T22:13:20.000ZGET/api/v5/account/balance" with "test_secret"
+    assert_eq!(signature.unwrap(), "47ivDB9jYrRKPa4Bppy7Kwi9Kx5oPhhkJ5iZcIaQ7V8=");
+}
+
+#[test]
+fn test_signature_matches_documented_example() {
+    // OKX 문서의 서명 예시: timestamp + "GET" + "/api/v5/account/balance?ccy=BTC"
+    let okx = create_test_okx();
+    let clock: Arc<dyn Clock> = Arc::new(MockClock::new(1607418537715));
+    let timestamp = get_access_timestamp(&json!({}), &clock);
+    assert_eq!(timestamp, "2020-12-08T09:08:57.715Z");
+
+    let params = BTreeMap::from([("ccy", "BTC")]);
+    let (request_path, body) = get_signed_payload(&params, "GET", "/api/v5/account/balance");
+    assert_eq!(request_path, "/api/v5/account/balance?ccy=BTC");
+    let signature = okx.get_signature(&timestamp, "GET", &request_path, body.as_ref());
+    // Base64 HMAC-SHA256 of "2020-12-08T09:08:57.715ZGET/api/v5/account/balance?ccy=BTC" with "test_secret"
+    assert_eq!(signature.unwrap(), "7ySiPVi/lhUA5KcmuyAYnW1qir695kDljxmxmNmQ1Sw=");
+}
+
+#[test]
+fn test_access_timestamp_keeps_an_iso_timestamp() {
+    let clock: Arc<dyn Clock> = Arc::new(MockClock::new(1700000000000));
+    let req = json!({ "timestamp": "2020-12-08T09:08:57.715Z" });
+    assert_eq!(get_access_timestamp(&req, &clock), "2020-12-08T09:08:57.715Z");
+    let req = json!({ "timestamp": 1724112000123u64 });
+    assert_eq!(get_access_timestamp(&req, &clock), "2024-08-20T00:00:00.123Z");
+}
+
+#[test]
+fn test_signature_with_explicit_timestamp_is_reproducible() {
+    let okx = create_test_okx();
+    let req = json!({ "symbol": "BTC/USDT", "order_id": "12345", "timestamp": "1724112000000" });
+    let params = BTreeMap::from([("instId", "BTC-USDT"), ("ordId", "12345")]);
+
+    let (request_path, body) = get_signed_payload(&params, "POST", "cancel_order");
+
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let first = okx.get_signature(&get_access_timestamp(&req, &clock), "POST", &request_path, body.as_ref());
+    let second = okx.get_signature(&get_access_timestamp(&req, &clock), "POST", &request_path, body.as_ref());
+    assert_eq!(first.unwrap(), second.unwrap());
+}
+
+#[test]
+fn test_parse_deposit_address_with_memo() {
+    let res = json!({
+        "code": "0",
+        "msg": "",
+        "data": [
+            {
+                "chain": "XLM-Stellar Lumens",
+                "ccy": "XLM",
+                "to": "6",
+                "addr": "GCXRKVWVFUGFIYLWJN4HAJKN5RZ6FK6RBE4C5MRKSDYYIBZLRS4NCAXB",
+                "memo": "1025839",
+                "tag": "",
+                "selected": true
+            }
+        ]
+    });
+
+    let deposit_address = parse_deposit_address(&res, Some("XLM-Stellar Lumens")).unwrap();
+    assert_eq!(deposit_address.currency, "XLM");
+    assert_eq!(deposit_address.address, "GCXRKVWVFUGFIYLWJN4HAJKN5RZ6FK6RBE4C5MRKSDYYIBZLRS4NCAXB");
+    assert_eq!(deposit_address.memo, Some("1025839".to_string()));
+}
+
+#[tokio::test]
+async fn test_stream_bbo_subscribes_and_parses_updates() {
+    let server = MockWsServer::start(vec![
+        r#"{"event":"subscribe","arg":{"channel":"bbo-tbt","instId":"BTC-USDT"},"connId":"a4d3ae55"}"#.to_string(),
+        r#"{"arg":{"channel":"bbo-tbt","instId":"BTC-USDT"},"data":[{"asks":[["8446","95","0","3"]],"bids":[["8445","10","0","1"]],"ts":"1597026383085","seqId":3}]}"#.to_string()
+    ]).await;
+    let okx = create_test_okx().with_ws_url(&server.url);
+
+    let mut stream = okx.stream_bbo("BTC/USDT").await.unwrap();
+
+    // The subscription acknowledgement is skipped
+    let bbo = stream.next().await.unwrap().unwrap();
+    assert_eq!(bbo.exchange, "Okx");
+    assert_eq!(bbo.symbol, "BTC/USDT");
+    assert_eq!(bbo.bid, "8445");
+    assert_eq!(bbo.bid_size, "10");
+    assert_eq!(bbo.ask, "8446");
+    assert_eq!(bbo.ask_size, "95");
+    assert_eq!(bbo.ts, 1597026383085);
+
+    let received = server.received(1).await;
+    assert_eq!(received.len(), 1);
+    let subscription: Value = serde_json::from_str(&received[0]).unwrap();
+    assert_eq!(
+        subscription,
+        json!({ "op": "subscribe", "args": [{ "channel": "bbo-tbt", "instId": "BTC-USDT" }] })
+    );
+}
+
+#[test]
+fn test_login_message_signs_verify_path() {
+    let message = get_login_message("985d5b66", "test_secret", "passphrase", "1538054050").unwrap();
+
+    let args = &message["args"][0];
+    assert_eq!(message["op"], "login");
+    assert_eq!(args["apiKey"], "985d5b66");
+    assert_eq!(args["passphrase"], "passphrase");
+    assert_eq!(args["timestamp"], "1538054050");
+    // Same as a REST signature of GET /users/self/verify without a body
+    let okx = create_test_okx();
+    assert_eq!(
+        args["sign"].as_str().unwrap(),
+        okx.get_signature("1538054050", "GET", "/users/self/verify", None).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_stream_user_orders_logs_in_and_parses_updates() {
+    let server = MockWsServer::start(vec![
+        r#"{"event":"login","code":"0","msg":"","connId":"a4d3ae55"}"#.to_string(),
+        r#"{"event":"subscribe","arg":{"channel":"orders","instType":"ANY"},"connId":"a4d3ae55"}"#.to_string(),
+        r#"{"arg":{"channel":"orders","instType":"ANY","uid":"614488474791936"},"data":[{"instType":"SPOT","instId":"BTC-USDT","ordId":"452197707845865472","clOrdId":"","px":"50000","sz":"0.01","side":"buy","ordType":"limit","state":"partially_filled","accFillSz":"0.004","fee":"-0.000004","feeCcy":"BTC","cTime":"1646984939000"},{"instType":"SWAP","instId":"BTC-USDT-SWAP","ordId":"452197707845865473","px":"","sz":"1","side":"sell","ordType":"market","state":"filled","accFillSz":"1","fee":"-0.2","feeCcy":"USDT","cTime":"1646984939001"}]}"#.to_string()
+    ]).await;
+    let okx = create_test_okx().with_private_ws_url(&server.url);
+
+    let mut stream = okx.stream_user_orders().await.unwrap();
+
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.ord_id, "452197707845865472");
+    assert_eq!(first.market, "BTC/USDT");
+    assert_eq!(first.state, OrderState::PartiallyFilled);
+    assert_eq!(first.amount, "0.004");
+    let second = stream.next().await.unwrap().unwrap();
+    assert_eq!(second.market, "BTC-USDT-SWAP");
+    assert_eq!(second.state, OrderState::Filled);
+
+    let received = server.received(2).await;
+    let login: Value = serde_json::from_str(&received[0]).unwrap();
+    assert_eq!(login["op"], "login");
+    assert_eq!(login["args"][0]["apiKey"], "test_api_key");
+    let subscription: Value = serde_json::from_str(&received[1]).unwrap();
+    assert_eq!(
+        subscription,
+        json!({ "op": "subscribe", "args": [{ "channel": "orders", "instType": "ANY" }] })
+    );
+}
+
+#[tokio::test]
+async fn test_stream_user_orders_reports_failed_login() {
+    let server = MockWsServer::start(vec![
+        r#"{"event":"error","code":"60009","msg":"Login failed."}"#.to_string()
+    ]).await;
+    let okx = create_test_okx().with_private_ws_url(&server.url);
+
+    let error = okx.stream_user_orders().await.err().unwrap();
+    assert_eq!(error.to_string(), "Authentication failed: 60009: Login failed.");
+}
+
+#[test]
+fn test_parse_trading_fees_flips_sign() {
+    let res = json!({
+        "code": "0",
+        "data": [{
+            "category": "1",
+            "instType": "SPOT",
+            "level": "Lv1",
+            "maker": "0.0001",
+            "taker": "-0.001",
+            "ts": "1763979985847"
+        }],
+        "msg": ""
+    });
+
+    // Okx reports charged fees as negative numbers and rebates as positive ones
+    let fees = parse_trading_fees(&res, "BTC/USDT").unwrap();
+    assert_eq!(fees.exchange, "Okx");
+    assert_eq!(fees.maker, "-0.0001");
+    assert_eq!(fees.taker, "0.001");
+}
+
+#[test]
+fn test_parse_account_info_of_sub_account() {
+    let res = json!({
+        "code": "0",
+        "data": [{
+            "acctLv": "2",
+            "autoLoan": false,
+            "ctIsoMode": "automatic",
+            "label": "bot",
+            "level": "Lv1",
+            "levelTmp": "",
+            "mainUid": "44705892343619584",
+            "mgnIsoMode": "automatic",
+            "perm": "read_only,trade",
+            "posMode": "long_short_mode",
+            "uid": "44705892343619585"
+        }],
+        "msg": ""
+    });
+
+    let info = parse_account_info(&res).unwrap();
+    assert_eq!(info.exchange, "Okx");
+    assert_eq!(info.can_trade, Some(true));
+    assert_eq!(info.can_withdraw, Some(false));
+    assert_eq!(info.fee_tier.as_deref(), Some("Lv1"));
+    assert_eq!(info.is_sub_account, Some(true));
+}
+
+#[tokio::test]
+async fn test_place_order_generates_client_order_id() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"code":"0","data":[{"ordId":"312269865356374016","sCode":"0"}],"msg":""}"#)
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url).with_auto_client_order_id();
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001"
+    });
+    okx.place_order(order.clone()).await.unwrap();
+    okx.place_order(order).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    let client_order_ids = requests
+        .iter()
+        .map(|request| {
+            let body: Value = serde_json::from_str(&request.body).unwrap();
+            body["clOrdId"].as_str().unwrap().to_string()
+        })
+        .collect::<Vec<String>>();
+
+    // clOrdId must be alphanumeric and at most 32 characters
+    for client_order_id in &client_order_ids {
+        assert_eq!(client_order_id.len(), 32);
+        assert!(client_order_id.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+    assert_ne!(client_order_ids[0], client_order_ids[1]);
+}
+
+#[tokio::test]
+async fn test_place_margin_and_swap_orders() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"code":"0","data":[{"ordId":"312269865356374016","sCode":"0"}],"msg":""}"#)
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001"
+    });
+    let mut margin = order.clone();
+    margin["inst_type"] = json!("margin");
+    let mut swap = order.clone();
+    swap["inst_type"] = json!("SWAP");
+    swap["td_mode"] = json!("isolated");
+    okx.place_order(order).await.unwrap();
+    okx.place_order(margin).await.unwrap();
+    okx.place_order(swap).await.unwrap();
+
+    let bodies = server
+        .requests()
+        .iter()
+        .map(|request| serde_json::from_str::<Value>(&request.body).unwrap())
+        .collect::<Vec<Value>>();
+    assert_eq!(bodies[0]["instId"], "BTC-USDT");
+    assert_eq!(bodies[0]["tdMode"], "cash");
+    assert_eq!(bodies[1]["instId"], "BTC-USDT");
+    assert_eq!(bodies[1]["tdMode"], "cross");
+    assert_eq!(bodies[2]["instId"], "BTC-USDT-SWAP");
+    assert_eq!(bodies[2]["tdMode"], "isolated");
+}
+
+#[tokio::test]
+async fn test_place_post_only_and_ioc_orders() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"code":"0","data":[{"ordId":"312269865356374016","sCode":"0"}],"msg":""}"#)
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "0.001",
+        "time_in_force": "GTC"
+    });
+    let mut post_only = order.clone();
+    post_only["post_only"] = json!(true);
+    let mut ioc = order.clone();
+    ioc["time_in_force"] = json!("ioc");
+    okx.place_order(order).await.unwrap();
+    okx.place_order(post_only).await.unwrap();
+    okx.place_order(ioc).await.unwrap();
+
+    let ord_types = server
+        .requests()
+        .iter()
+        .map(|request| serde_json::from_str::<Value>(&request.body).unwrap()["ordType"].clone())
+        .collect::<Vec<Value>>();
+    assert_eq!(ord_types, vec![json!("limit"), json!("post_only"), json!("ioc")]);
+}
+
+#[tokio::test]
+async fn test_place_stop_loss_sell_as_algo_order() {
+    let server = MockServer::start(|request| {
+        if request.path.starts_with("/api/v5/market/ticker") {
+            MockResponse::json(r#"{"code":"0","data":[{"instId":"BTC-USDT","last":"50000"}],"msg":""}"#)
+        } else {
+            MockResponse::json(
+                r#"{"code":"0","data":[{"algoId":"681096944655273984","algoClOrdId":"","sCode":"0","sMsg":""}],"msg":""}"#
+            )
+        }
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let order = okx.place_conditional_order(json!({
+        "symbol": "BTC/USDT",
+        "side": "sell",
+        "amount": "0.01",
+        "trigger_type": "stop_loss",
+        "trigger_price": "48000"
+    })).await.unwrap();
+    assert_eq!(order.ord_id, "681096944655273984");
+    assert_eq!(order.state, OrderState::New);
+
+    let requests = server.requests();
+    assert_eq!(requests[1].path, "/api/v5/trade/order-algo");
+    let body: Value = serde_json::from_str(&requests[1].body).unwrap();
+    assert_eq!(body["instId"], "BTC-USDT");
+    assert_eq!(body["ordType"], "conditional");
+    assert_eq!(body["side"], "sell");
+    assert_eq!(body["sz"], "0.01");
+    assert_eq!(body["slTriggerPx"], "48000");
+    assert_eq!(body["slOrdPx"], "-1");
+    assert!(body.get("tpTriggerPx").is_none());
+}
+
+#[tokio::test]
+async fn test_swap_inst_id_is_passed_through() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"code":"0","data":[{"asks":[],"bids":[],"ts":"1"}],"msg":""}"#)
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    okx.get_order_book(json!({ "symbol": "BTC-USDT-SWAP" })).await.unwrap();
+    okx.get_order_book(json!({ "symbol": "ETH/USDT", "inst_type": "swap" })).await.unwrap();
+
+    let requests = server.requests();
+    assert!(requests[0].path.contains("instId=BTC-USDT-SWAP"), "{}", requests[0].path);
+    assert!(requests[1].path.contains("instId=ETH-USDT-SWAP"), "{}", requests[1].path);
+}
+
+#[tokio::test]
+async fn test_cash_mode_is_rejected_for_swaps() {
+    let okx = create_test_okx();
+    let error = okx.place_order(json!({
+        "symbol": "BTC-USDT-SWAP",
+        "side": "buy",
+        "order_type": "market",
+        "amount": "1",
+        "td_mode": "cash"
+    })).await.unwrap_err();
+    assert_eq!(error, "Invalid request: td_mode cash is not valid for SWAP orders");
+}
+
+#[tokio::test]
+async fn test_cancel_order_by_client_order_id() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"code":"0","data":[{"clOrdId":"my-order-1","sCode":"0"}],"msg":""}"#)
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    okx.cancel_order(json!({ "symbol": "BTC/USDT", "client_order_id": "my-order-1" })).await.unwrap();
+
+    let requests = server.requests();
+    let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["clOrdId"], "my-order-1");
+    assert!(body.get("ordId").is_none());
+}
+
+#[tokio::test]
+async fn test_amend_order_native() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"code":"0","data":[{"clOrdId":"","ordId":"2510789768709120","reqId":"","sCode":"0","sMsg":""}],"msg":""}"#
+        )
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let order = okx
+        .amend_order(
+            json!({
+                "symbol": "BTC/USDT",
+                "order_id": "2510789768709120",
+                "side": "buy",
+                "order_type": "limit",
+                "price": "49000",
+                "amount": "0.002"
+            })
+        ).await
+        .unwrap();
+    assert_eq!(order.ord_id, "2510789768709120");
+    assert_eq!(order.price, "49000");
+    assert_eq!(order.volume, "0.002");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/api/v5/trade/amend-order");
+    let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["ordId"], "2510789768709120");
+    assert_eq!(body["newPx"], "49000");
+    assert_eq!(body["newSz"], "0.002");
+}
+
+#[test]
+fn test_parse_markets_top_by_volume() {
+    let res = json!({
+        "code": "0",
+        "data": [
+            { "instId": "ETH-USDT", "last": "2500", "volCcy24h": "400000000" },
+            { "instId": "BTC-USDT", "last": "60000", "volCcy24h": "900000000" },
+            { "instId": "BTC-USDC", "last": "60010", "volCcy24h": "999999999" },
+            { "instId": "DOGE-USDT", "last": "0.1", "volCcy24h": "50000000" }
+        ],
+        "msg": ""
+    });
+
+    let top = top_by_volume(parse_markets(&res, "USDT").unwrap(), 5);
+
+    let symbols = top.iter().map(|market| market.symbol.as_str()).collect::<Vec<&str>>();
+    assert_eq!(symbols, vec!["BTC/USDT", "ETH/USDT", "DOGE/USDT"]);
+}
+
+#[tokio::test]
+async fn test_ping_hits_public_time_endpoint() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(r#"{"code":"0","data":[{"ts":"1597026383085"}],"msg":""}"#)
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    okx.ping().await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].path.starts_with("/api/v5/public/time"));
+}
+
+#[test]
+fn test_parse_mark_price() {
+    let res = json!({
+        "code": "0",
+        "data": [{
+            "instId": "BTC-USDT-SWAP",
+            "instType": "SWAP",
+            "markPx": "60512.3",
+            "ts": "1597026383085"
+        }],
+        "msg": ""
+    });
+
+    let price = parse_mark_price(&res, "BTC/USDT").unwrap();
+    assert_eq!(price.exchange, "Okx");
+    assert_eq!(price.symbol, "BTC/USDT");
+    assert_eq!(price.price, "60512.3");
+    assert_eq!(price.source, PriceSource::Mark);
+
+    let error = json!({ "code": "51001", "data": [], "msg": "Instrument ID does not exist" });
+    assert!(parse_mark_price(&error, "BTC/USDT").is_err());
 }
 
+#[tokio::test]
+async fn test_signed_request_rejects_unknown_method() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"code":"0","data":[],"msg":""}"#)).await;
+    let mut okx = create_test_okx().with_api_url(&server.url);
+    // set_endpoint가 거르는 오타를 엔드포인트 맵에 직접 넣음
+    okx.endpoint.insert(
+        "make_order".to_string(),
+        ["GETT".to_string(), "api/v5/trade/order".to_string()]
+    );
+
+    let error = okx
+        .send_req_with_sign(BTreeMap::from([("instId", "BTC-USDT")]), "make_order").await
+        .unwrap_err();
+
+    assert_eq!(error, ExchangeError::InvalidRequest("Unknown HTTP method \"GETT\"".to_string()));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_close_all_requires_confirmation() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"code":"0","data":[],"msg":""}"#)).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let error = okx.close_all(json!({})).await.unwrap_err();
+    assert!(matches!(error, ExchangeError::InvalidRequest(_)));
+    assert!(server.requests().is_empty());
+}
 
+#[tokio::test]
+async fn test_close_all_cancels_orders_and_closes_positions() {
+    let server = MockServer::start(|request| {
+        match request.path.split('?').next().unwrap_or_default() {
+            "/api/v5/trade/orders-pending" =>
+                MockResponse::json(
+                    r#"{"code":"0","data":[{"instId":"BTC-USDT-SWAP","ordId":"111"}],"msg":""}"#
+                ),
+            "/api/v5/trade/cancel-batch-orders" =>
+                MockResponse::json(r#"{"code":"0","data":[{"ordId":"111","sCode":"0"}],"msg":""}"#),
+            "/api/v5/account/positions" =>
+                MockResponse::json(
+                    r#"{"code":"0","data":[
+                        {"instId":"BTC-USDT-SWAP","mgnMode":"cross","posSide":"net","pos":"-2"},
+                        {"instId":"ETH-USDT-SWAP","mgnMode":"isolated","posSide":"long","pos":"5"},
+                        {"instId":"SOL-USDT-SWAP","mgnMode":"cross","posSide":"net","pos":"0"}
+                    ],"msg":""}"#
+                ),
+            _ => MockResponse::json(r#"{"code":"0","data":[{}],"msg":""}"#),
+        }
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let report = okx.close_all(json!({ "confirm": true })).await.unwrap();
+    assert_eq!(report.canceled_orders, vec!["111"]);
+    assert_eq!(report.closed_positions, vec!["BTC-USDT-SWAP", "ETH-USDT-SWAP"]);
+    assert!(report.failed.is_empty());
+
+    let closes = server
+        .requests()
+        .into_iter()
+        .filter(|request| request.path == "/api/v5/trade/close-position")
+        .map(|request| serde_json::from_str::<Value>(&request.body).unwrap())
+        .collect::<Vec<Value>>();
+    assert_eq!(closes.len(), 2);
+    assert_eq!(closes[0]["mgnMode"], "cross");
+    assert!(closes[0].get("posSide").is_none());
+    assert_eq!(closes[1]["posSide"], "long");
+}
+
+#[tokio::test]
+async fn test_close_all_reports_orders_left_open() {
+    let server = MockServer::start(|request| {
+        match request.path.split('?').next().unwrap_or_default() {
+            "/api/v5/trade/orders-pending" =>
+                MockResponse::json(
+                    r#"{"code":"0","data":[{"instId":"BTC-USDT","ordId":"111"},{"instId":"BTC-USDT","ordId":"222"}],"msg":""}"#
+                ),
+            "/api/v5/trade/cancel-batch-orders" =>
+                MockResponse::json(
+                    r#"{"code":"2","data":[{"ordId":"111","sCode":"0","sMsg":""},{"ordId":"222","sCode":"51400","sMsg":"Cancellation failed as the order has been filled"}],"msg":""}"#
+                ),
+            "/api/v5/account/positions" =>
+                MockResponse::json(
+                    r#"{"code":"0","data":[{"instId":"BTC-USDT-SWAP","mgnMode":"cross","posSide":"net","pos":"1"}],"msg":""}"#
+                ),
+            _ => MockResponse::json(r#"{"code":"0","data":[{}],"msg":""}"#),
+        }
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    // 일부 주문 취소가 실패해도 포지션 청산은 계속 진행
+    let report = okx.close_all(json!({ "confirm": true })).await.unwrap();
+    assert_eq!(report.canceled_orders, vec!["111"]);
+    assert_eq!(report.failed, vec!["222: Cancellation failed as the order has been filled"]);
+    assert_eq!(report.closed_positions, vec!["BTC-USDT-SWAP"]);
+
+    let error = okx.cancel_all_orders(json!({})).await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::CancelFailed {
+            canceled: vec!["111".to_string()],
+            failed: vec!["222: Cancellation failed as the order has been filled".to_string()],
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_failed_order_is_returned_as_exchange_rejected() {
+    let failure = r#"{"code":"1","data":[{"clOrdId":"","ordId":"","sCode":"51008","sMsg":"Order failed. Insufficient USDT balance in account.","tag":""}],"inTime":"1695190491421339","msg":"All operations failed","outTime":"1695190491423240"}"#;
+
+    let error = check_response(&serde_json::from_str(failure).unwrap()).unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::ExchangeRejected {
+            code: "51008".to_string(),
+            message: "All operations failed: Order failed. Insufficient USDT balance in account.".to_string(),
+        }
+    );
+
+    // HTTP 200 does not make it a success
+    let server = MockServer::start(move |_| MockResponse::json(failure)).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+    let order = json!({
+        "symbol": "BTC/USDT",
+        "side": "buy",
+        "order_type": "limit",
+        "price": "50000",
+        "amount": "100"
+    });
+    let error = okx.place_order(order).await.unwrap_err();
+    assert!(error.starts_with("51008: "));
+}
+
+#[tokio::test]
+async fn test_request_timeout_override() {
+    // Accepts connections but never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let okx = create_test_okx().with_api_url(&url);
+
+    let error = okx.cancel_all_orders(json!({ "timeout": 1 })).await.unwrap_err();
+    assert!(matches!(error, ExchangeError::Timeout(_)));
+
+    let error = okx
+        .cancel_order(json!({ "symbol": "BTC/USDT", "order_id": "12345", "timeout": 1 })).await
+        .unwrap_err();
+    assert!(error.starts_with("Timed out"));
+    drop(listener);
+}
+
+#[test]
+fn test_parse_ticker() {
+    let res = json!({
+        "code": "0",
+        "msg": "",
+        "data": [{
+            "instType": "SPOT",
+            "instId": "BTC-USDT",
+            "last": "43250.1",
+            "lastSz": "0.0012",
+            "askPx": "43250.2",
+            "askSz": "1.5",
+            "bidPx": "43250.1",
+            "bidSz": "0.3",
+            "open24h": "42000.3",
+            "high24h": "43500",
+            "low24h": "41800.5",
+            "volCcy24h": "215443620.52",
+            "vol24h": "5012.34",
+            "sodUtc0": "42900.1",
+            "sodUtc8": "42650.2",
+            "ts": "1700000000000"
+        }]
+    });
+
+    let ticker = parse_ticker(&res, "BTC/USDT").unwrap();
+    assert_eq!(ticker.last, "43250.1");
+    assert_eq!(ticker.bid.as_deref(), Some("43250.1"));
+    assert_eq!(ticker.ask.as_deref(), Some("43250.2"));
+    assert_eq!(ticker.high_24h.as_deref(), Some("43500"));
+    assert_eq!(ticker.low_24h.as_deref(), Some("41800.5"));
+    assert_eq!(ticker.volume_24h.as_deref(), Some("5012.34"));
+    // Rounded to the precision of the last price
+    assert_eq!(ticker.change_24h.as_deref(), Some("1249.8"));
+}
+
+#[test]
+fn test_parse_symbol_info() {
+    let res = json!({
+        "code": "0",
+        "msg": "",
+        "data": [{
+            "instType": "SPOT",
+            "instId": "BTC-USDT",
+            "baseCcy": "BTC",
+            "quoteCcy": "USDT",
+            "tickSz": "0.1",
+            "lotSz": "0.00000001",
+            "minSz": "0.00001",
+            "state": "live"
+        }]
+    });
+
+    let info = parse_symbol_info(&res, "BTC/USDT").unwrap();
+    assert_eq!((info.base.as_str(), info.quote.as_str()), ("BTC", "USDT"));
+    assert_eq!(info.tick_size.as_deref(), Some("0.1"));
+    assert_eq!(info.lot_size.as_deref(), Some("0.00000001"));
+    assert_eq!(info.min_size.as_deref(), Some("0.00001"));
+    assert_eq!(info.min_notional, None);
+}
+
+#[test]
+fn test_capabilities() {
+    let capabilities = create_test_okx().capabilities();
+    assert!(capabilities.requires_passphrase);
+    assert!(capabilities.supports_websocket);
+    assert_eq!(capabilities.min_order_notional, None);
+
+    let upbit = Upbit::new("test_api_key".to_string(), "test_secret".to_string()).unwrap();
+    let capabilities = upbit.capabilities();
+    assert!(!capabilities.requires_passphrase);
+    assert!(!capabilities.supports_websocket);
+    assert_eq!(capabilities.min_order_notional.as_deref(), Some("5000"));
+}
+
+#[test]
+fn test_parse_symbol_passes_inst_ids_through() {
+    assert_eq!(parse_symbol("BTC/USDT").unwrap(), "BTC-USDT");
+    // instId 형식은 그대로 통과
+    assert_eq!(parse_symbol("BTC-USDT-SWAP").unwrap(), "BTC-USDT-SWAP");
+    assert_eq!(parse_symbol("BTC--USDT"), Err(ExchangeError::InvalidSymbol("BTC--USDT".to_string())));
+}
+
+#[tokio::test]
+async fn test_get_transfer_history_pages_back_with_after() {
+    let server = MockServer::start(|request| {
+        // 헬퍼: `ts` 시각의 입금 기록 하나
+        let deposit = |ts: u64| json!({ "ccy": "USDT", "amt": "1", "state": "2", "ts": ts.to_string() });
+        let data = if request.path.starts_with("/api/v5/asset/withdrawal-history") {
+            vec![]
+        } else if request.path.contains("after=") {
+            vec![deposit(1700000000000)]
+        } else {
+            (0..100).map(|i| deposit(1700000001000 + i)).collect()
+        };
+        MockResponse::json(&json!({ "code": "0", "msg": "", "data": data }).to_string())
+    }).await;
+    let okx = create_test_okx().with_api_url(&server.url);
+
+    let transfers = okx.get_transfer_history(json!({})).await.unwrap();
+    assert_eq!(transfers.len(), 101);
+    assert_eq!(transfers[0].timestamp, 1700000000000);
+
+    let paths = server.requests().into_iter().map(|request| request.path).collect::<Vec<_>>();
+    assert_eq!(paths, vec![
+        "/api/v5/asset/deposit-history",
+        "/api/v5/asset/deposit-history?after=1700000001000",
+        "/api/v5/asset/withdrawal-history",
+    ]);
+}
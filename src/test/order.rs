@@ -0,0 +1,69 @@
+use serde_json::json;
+
+use crate::{ Order, OrderState, Side };
+
+#[test]
+fn test_side_from_exchange_str() {
+    // Binance
+    assert_eq!(Side::from_exchange_str("BUY").unwrap(), Side::Buy);
+    assert_eq!(Side::from_exchange_str("SELL").unwrap(), Side::Sell);
+    // Upbit, Bithumb
+    assert_eq!(Side::from_exchange_str("bid").unwrap(), Side::Buy);
+    assert_eq!(Side::from_exchange_str("ask").unwrap(), Side::Sell);
+    // Okx
+    assert_eq!(Side::from_exchange_str("buy").unwrap(), Side::Buy);
+    assert_eq!(Side::from_exchange_str("sell").unwrap(), Side::Sell);
+
+    assert!(Side::from_exchange_str("long").is_err());
+}
+
+#[test]
+fn test_order_side_deserializes_legacy_strings() {
+    let order: Order = serde_json::from_value(
+        json!({
+            "exchange": "Upbit",
+            "ord_id": "cdd92199-2897-4e14-9448-f923320408ad",
+            "side": "ask",
+            "ord_type": "limit",
+            "price": "140000000",
+            "state": "wait",
+            "market": "BTC/KRW",
+            "volume": "0.01",
+            "create_at": "2024-08-20T00:00:00+09:00",
+            "amount": "0"
+        })
+    ).unwrap();
+
+    assert_eq!(order.side, Side::Sell);
+    assert_eq!(order.fee, "");
+    assert_eq!(order.fee_currency, "");
+    assert_eq!(serde_json::to_value(&order).unwrap()["side"], "sell");
+    assert_eq!(order.state, OrderState::New);
+    assert_eq!(order.raw_state, "");
+    assert_eq!(serde_json::to_value(&order).unwrap()["state"], "new");
+}
+
+#[test]
+fn test_order_state_from_exchange_str() {
+    // Binance
+    assert_eq!(OrderState::from_exchange_str("NEW"), OrderState::New);
+    assert_eq!(OrderState::from_exchange_str("PARTIALLY_FILLED"), OrderState::PartiallyFilled);
+    assert_eq!(OrderState::from_exchange_str("FILLED"), OrderState::Filled);
+    assert_eq!(OrderState::from_exchange_str("CANCELED"), OrderState::Canceled);
+    assert_eq!(OrderState::from_exchange_str("REJECTED"), OrderState::Rejected);
+    assert_eq!(OrderState::from_exchange_str("EXPIRED"), OrderState::Expired);
+    assert_eq!(OrderState::from_exchange_str("EXPIRED_IN_MATCH"), OrderState::Expired);
+    // Upbit, Bithumb
+    assert_eq!(OrderState::from_exchange_str("wait"), OrderState::New);
+    assert_eq!(OrderState::from_exchange_str("watch"), OrderState::New);
+    assert_eq!(OrderState::from_exchange_str("done"), OrderState::Filled);
+    assert_eq!(OrderState::from_exchange_str("cancel"), OrderState::Canceled);
+    // Okx
+    assert_eq!(OrderState::from_exchange_str("live"), OrderState::New);
+    assert_eq!(OrderState::from_exchange_str("partially_filled"), OrderState::PartiallyFilled);
+    assert_eq!(OrderState::from_exchange_str("filled"), OrderState::Filled);
+    assert_eq!(OrderState::from_exchange_str("canceled"), OrderState::Canceled);
+    assert_eq!(OrderState::from_exchange_str("mmp_canceled"), OrderState::Canceled);
+
+    assert_eq!(OrderState::from_exchange_str("open"), OrderState::Unknown);
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{ json, Value };
+use tokio::sync::mpsc;
+
+use crate::test::mock::{ MockResponse, MockServer, RecordedRequest };
+use crate::upbit::{ Upbit, UpbitTrait };
+use crate::{ place_order_guarded, Exchange, OrderState };
+
+// 헬퍼 함수: 주문 시 `state`, 조회 시 체결, 취소 시 취소 상태를 돌려주는 서버와 Upbit 생성.
+// 서버가 받은 요청은 수신 채널로도 전달된다.
+async fn create_upbit_with_order_state(
+    state: &'static str
+) -> (MockServer, Arc<dyn Exchange>, mpsc::UnboundedReceiver<RecordedRequest>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let server = MockServer::start(move |request| {
+        let _ = sender.send(request.clone());
+        let state = match request.method.as_str() {
+            "GET" => "done",
+            "DELETE" => "cancel",
+            _ => state,
+        };
+        MockResponse::json(
+            &format!(
+                r#"{{"uuid":"order-1","side":"bid","ord_type":"limit","price":"140000000","state":"{}","market":"KRW-BTC","created_at":"2024-08-20T10:00:00+09:00","volume":"0.001","executed_volume":"0","paid_fee":"0"}}"#,
+                state
+            )
+        )
+    }).await;
+    let upbit = Upbit::new("test_api_key".to_string(), "test_secret".to_string())
+        .unwrap()
+        .with_api_url(&server.url);
+    (server, Arc::new(upbit), receiver)
+}
+
+// 헬퍼 함수: 테스트용 주문 요청
+fn create_order() -> Value {
+    json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000",
+        "amount": "0.001"
+    })
+}
+
+#[tokio::test]
+async fn test_dropped_open_order_is_canceled() {
+    let (server, upbit, mut requests) = create_upbit_with_order_state("wait").await;
+
+    let guard = place_order_guarded(upbit, create_order(), true).await.unwrap();
+    assert_eq!(guard.order().state, OrderState::New);
+    assert_eq!(requests.recv().await.unwrap().method, "POST");
+    drop(guard);
+
+    // 드롭 시 띄운 취소 요청이 서버에 도착할 때까지 기다린다
+    let cancel = requests.recv().await.unwrap();
+    assert_eq!(cancel.method, "DELETE");
+    assert_eq!(cancel.path, "/v1/order?uuid=order-1");
+    assert_eq!(server.requests().len(), 2);
+}
+
+// 시간을 멈춘 채 잠들면 런타임에 할 일이 남지 않았을 때에야 깨어나므로, 그때까지 취소 요청이
+// 없었다면 띄워진 취소도 없다
+#[tokio::test(start_paused = true)]
+async fn test_released_or_filled_order_is_left_alone() {
+    let (server, upbit, _) = create_upbit_with_order_state("wait").await;
+    let guard = place_order_guarded(upbit, create_order(), true).await.unwrap();
+    let order = guard.release();
+    assert_eq!(order.ord_id, "order-1");
+
+    let (filled_server, upbit, _) = create_upbit_with_order_state("done").await;
+    drop(place_order_guarded(upbit, create_order(), true).await.unwrap());
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(filled_server.requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_refresh_picks_up_fill() {
+    let (server, upbit, _) = create_upbit_with_order_state("wait").await;
+    let mut guard = place_order_guarded(upbit, create_order(), true).await.unwrap();
+
+    let order = guard.refresh().await.unwrap();
+    assert_eq!(order.state, OrderState::Filled);
+    drop(guard);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "GET");
+    assert!(requests[1].path.contains("uuid=order-1"), "{}", requests[1].path);
+}
@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::test::mock::{ MockExchange, OrderBookOnlyExchange };
+use crate::{
+    estimate_fill_duration,
+    Decimal,
+    Exchange,
+    FillEstimate,
+    OrderBook,
+    OrderBookUnit,
+    PriceSource,
+    Side,
+    Trade,
+};
+
+// 헬퍼 함수: (ask_price, bid_price) 목록으로 OrderBook 생성
+fn create_test_orderbook(levels: &[(&str, &str)]) -> OrderBook {
+    OrderBook {
+        market: "BTC/USDT".to_string(),
+        exchange: "Binance".to_string(),
+        orderbook_unit: levels
+            .iter()
+            .map(|(ask_price, bid_price)| OrderBookUnit {
+                ask_price: ask_price.to_string(),
+                bid_price: bid_price.to_string(),
+                ask_size: "1".to_string(),
+                bid_size: "1".to_string(),
+            })
+            .collect(),
+        timestamp: None,
+    }
+}
+
+// 헬퍼 함수: (ask_price, ask_size, bid_price, bid_size) 목록으로 OrderBook 생성
+fn create_sized_orderbook(levels: &[(&str, &str, &str, &str)]) -> OrderBook {
+    let mut orderbook = create_test_orderbook(&[]);
+    orderbook.orderbook_unit = levels
+        .iter()
+        .map(|(ask_price, ask_size, bid_price, bid_size)| OrderBookUnit {
+            ask_price: ask_price.to_string(),
+            bid_price: bid_price.to_string(),
+            ask_size: ask_size.to_string(),
+            bid_size: bid_size.to_string(),
+        })
+        .collect();
+    orderbook
+}
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn test_validate_monotonic_orderbook() {
+    let orderbook = create_test_orderbook(&[
+        ("50001", "50000"),
+        ("50002", "49999"),
+        ("50003", "49998"),
+    ]);
+    assert!(orderbook.validate().is_ok());
+}
+
+#[test]
+fn test_validate_non_monotonic_orderbook() {
+    let orderbook = create_test_orderbook(&[
+        ("50001", "50000"),
+        ("50000.5", "49999"),
+        ("50003", "49999"),
+    ]);
+
+    let error = orderbook.validate().unwrap_err();
+    assert!(error.contains("ask level 1: 50000.5 is not above 50001"));
+    assert!(error.contains("bid level 2: 49999 is not below 49999"));
+    assert!(!error.contains("ask level 2"));
+}
+
+#[test]
+fn test_estimate_fill_duration() {
+    let orderbook = create_test_orderbook(&[
+        ("50001", "50000"),
+        ("50002", "49999"),
+        ("50003", "49998"),
+    ]);
+    // 10 units traded over 10 seconds
+    let trades = (0..=10u64)
+        .map(|i| Trade {
+            exchange: "Binance".to_string(),
+            symbol: "BTC/USDT".to_string(),
+            trade_id: i.to_string(),
+            price: "50000".to_string(),
+            size: if i == 0 { "0".to_string() } else { "1".to_string() },
+            side: Side::Sell,
+            timestamp: 1724112000000 + i * 1000,
+            fee: String::new(),
+        })
+        .collect::<Vec<Trade>>();
+
+    // A buy at 49999 waits behind the 2 units bid at or above it, then fills its own 2
+    let estimate = estimate_fill_duration(&trades, &orderbook, "49999", "2").unwrap();
+    assert!(estimate >= Duration::from_secs(3) && estimate <= Duration::from_secs(5));
+
+    assert!(estimate_fill_duration(&[], &orderbook, "49999", "2").is_err());
+}
+
+#[test]
+fn test_estimate_fill_duration_skips_padded_levels() {
+    // 매도 호가가 한 단계뿐이고 나머지는 빈 값으로 채워진 호가창
+    let orderbook = create_sized_orderbook(&[
+        ("50001", "1", "50000", "1"),
+        ("", "", "49999", "1"),
+        ("", "", "49998", "1"),
+    ]);
+    let trades = (0..=10u64)
+        .map(|i| Trade {
+            exchange: "Binance".to_string(),
+            symbol: "BTC/USDT".to_string(),
+            trade_id: i.to_string(),
+            price: "50000".to_string(),
+            size: if i == 0 { "0".to_string() } else { "1".to_string() },
+            side: Side::Sell,
+            timestamp: 1724112000000 + i * 1000,
+            fee: String::new(),
+        })
+        .collect::<Vec<Trade>>();
+
+    let estimate = estimate_fill_duration(&trades, &orderbook, "49998", "1").unwrap();
+    assert_eq!(estimate, Duration::from_secs(4));
+    // A sell above the only ask waits behind it
+    let estimate = estimate_fill_duration(&trades, &orderbook, "50002", "1").unwrap();
+    assert_eq!(estimate, Duration::from_secs(2));
+
+    // 매도 호가가 아예 없으면 모든 주문이 매수 쪽에 줄을 섬
+    let bids_only = create_sized_orderbook(&[("", "", "50000", "1"), ("", "", "49999", "1")]);
+    let estimate = estimate_fill_duration(&trades, &bids_only, "49999", "1").unwrap();
+    assert_eq!(estimate, Duration::from_secs(3));
+}
+
+#[test]
+fn test_validate_skips_empty_levels_of_the_shorter_side() {
+    let orderbook = create_test_orderbook(&[
+        ("50001", "50000"),
+        ("50002", "49999"),
+        ("50003", ""),
+    ]);
+    assert!(orderbook.validate().is_ok());
+}
+
+#[test]
+fn test_spread_and_mid_price_keep_satoshi_precision() {
+    let orderbook = create_test_orderbook(&[("50000.00000001", "49999.99999999"), ("50001", "49999")]);
+
+    assert_eq!(orderbook.spread(), Some("0.00000002".parse::<Decimal>().unwrap()));
+    assert_eq!(orderbook.mid_price(), Some("50000".parse::<Decimal>().unwrap()));
+    assert_eq!(orderbook.spread_bps(), Some("0.000000004".parse::<Decimal>().unwrap()));
+}
+
+#[test]
+fn test_spread_bps() {
+    let orderbook = create_test_orderbook(&[("101", "99")]);
+
+    assert_eq!(orderbook.spread(), Some(Decimal::from(2)));
+    assert_eq!(orderbook.mid_price(), Some(Decimal::from(100)));
+    assert_eq!(orderbook.spread_bps(), Some(Decimal::from(200)));
+}
+
+#[test]
+fn test_one_sided_book_has_no_spread() {
+    let orderbook = create_test_orderbook(&[("50001", ""), ("50002", "")]);
+    assert_eq!(orderbook.mid_price(), None);
+    assert_eq!(orderbook.spread(), None);
+    assert_eq!(orderbook.spread_bps(), None);
+
+    let orderbook = create_test_orderbook(&[]);
+    assert_eq!(orderbook.spread(), None);
+}
+
+#[test]
+fn test_estimate_fill_within_one_level() {
+    let orderbook = create_sized_orderbook(&[
+        ("50001", "2", "50000", "3"),
+        ("50002", "5", "49999", "4"),
+    ]);
+
+    let estimate = orderbook.estimate_fill(Side::Sell, decimal("1.5")).unwrap();
+    assert_eq!(estimate, FillEstimate {
+        avg_price: decimal("50000"),
+        filled: decimal("1.5"),
+        remaining: Decimal::ZERO,
+        levels_consumed: 1,
+    });
+}
+
+#[test]
+fn test_estimate_fill_across_levels() {
+    let orderbook = create_sized_orderbook(&[
+        ("50001", "2", "50000", "3"),
+        ("50002", "5", "49999", "4"),
+        ("50010", "5", "49990", "4"),
+    ]);
+
+    // 2 @ 50001 + 1 @ 50002 = 150004 for 3
+    let estimate = orderbook.estimate_fill(Side::Buy, decimal("3")).unwrap();
+    assert_eq!(estimate.avg_price, decimal("150004") / decimal("3"));
+    assert_eq!(estimate.filled, decimal("3"));
+    assert_eq!(estimate.remaining, Decimal::ZERO);
+    assert_eq!(estimate.levels_consumed, 2);
+}
+
+#[test]
+fn test_estimate_fill_on_under_liquid_book() {
+    let orderbook = create_sized_orderbook(&[
+        ("50001", "2", "50000", "0.5"),
+        ("50002", "5", "49990", "0.5"),
+        ("50003", "1", "", ""),
+    ]);
+
+    let estimate = orderbook.estimate_fill(Side::Sell, decimal("2")).unwrap();
+    assert_eq!(estimate.avg_price, decimal("49995"));
+    assert_eq!(estimate.filled, decimal("1"));
+    assert_eq!(estimate.remaining, decimal("1"));
+    assert_eq!(estimate.levels_consumed, 2);
+
+    assert_eq!(create_test_orderbook(&[]).estimate_fill(Side::Buy, decimal("1")), None);
+}
+
+#[test]
+fn test_estimate_fill_reads_exponent_sizes() {
+    let orderbook = create_sized_orderbook(&[("64000000", "1e-8", "63990000", "2E-8")]);
+
+    let estimate = orderbook.estimate_fill(Side::Sell, decimal("0.00000002")).unwrap();
+    assert_eq!(estimate.avg_price, decimal("63990000"));
+    assert_eq!(estimate.remaining, Decimal::ZERO);
+    assert_eq!(orderbook.spread(), Some(decimal("10000")));
+}
+
+#[test]
+fn test_as_ladders_aggregates_repeated_price_levels() {
+    let orderbook = create_sized_orderbook(&[
+        ("50001.0", "0.5", "50000.0", "1.5"),
+        ("50001.00", "0.25", "50000", "0.5"),
+        ("50002.5", "2", "49999.5", "3"),
+        ("", "", "49999", "1"),
+    ]);
+
+    let (bids, asks) = orderbook.as_ladders();
+    assert_eq!(
+        bids.into_iter().collect::<Vec<_>>(),
+        vec![
+            (Decimal::new(49999, 0), Decimal::ONE),
+            (Decimal::new(499995, 1), Decimal::from(3)),
+            (Decimal::new(50000, 0), Decimal::TWO),
+        ]
+    );
+    assert_eq!(
+        asks.into_iter().collect::<Vec<_>>(),
+        vec![
+            (Decimal::new(50001, 0), Decimal::new(75, 2)),
+            (Decimal::new(500025, 1), Decimal::TWO),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_default_current_price_is_order_book_mid() {
+    let exchange = OrderBookOnlyExchange(
+        MockExchange::new("BookOnly", 0, Ok("")).with_order_book(&[("50010", "49990"), ("50020", "49980")])
+    );
+
+    let price = exchange.get_current_price(json!({ "symbol": "BTC/USDT" })).await.unwrap();
+    assert_eq!(price.exchange, "BookOnly");
+    assert_eq!(price.symbol, "BTC/USDT");
+    assert_eq!(price.price, "50000");
+    assert_eq!(price.source, PriceSource::Mid);
+
+    // 호가가 비어 있으면 가격을 만들 수 없음
+    let empty = OrderBookOnlyExchange(MockExchange::new("BookOnly", 0, Ok("")));
+    let error = empty.get_current_price(json!({ "symbol": "BTC/USDT" })).await.unwrap_err();
+    assert_eq!(error, "BookOnly order book for BTC/USDT is empty");
+}
@@ -0,0 +1,88 @@
+use crate::{ realized_pnl, Decimal, ExchangeError, Side, Trade };
+
+// 헬퍼 함수: 체결 내역 생성
+fn create_test_trade(side: Side, price: &str, size: &str, fee: &str) -> Trade {
+    Trade {
+        exchange: "Binance".to_string(),
+        symbol: "BTC/USDT".to_string(),
+        trade_id: "1".to_string(),
+        price: price.to_string(),
+        size: size.to_string(),
+        side,
+        timestamp: 1724112000000,
+        fee: fee.to_string(),
+    }
+}
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn test_realized_pnl_round_trip() {
+    let buys = [
+        create_test_trade(Side::Buy, "100", "1", "0.1"),
+        create_test_trade(Side::Buy, "110", "1", "0.11"),
+    ];
+    let sells = [create_test_trade(Side::Sell, "120", "2", "0.24")];
+
+    let pnl = realized_pnl(&buys, &sells).unwrap();
+    assert_eq!(pnl.matched_size, decimal("2"));
+    assert_eq!(pnl.avg_buy_price, decimal("105"));
+    // (120 - 105) * 2 - (0.1 + 0.11 + 0.24)
+    assert_eq!(pnl.fees, decimal("0.45"));
+    assert_eq!(pnl.realized, decimal("29.55"));
+    assert_eq!(pnl.remaining_size, decimal("0"));
+}
+
+#[test]
+fn test_realized_pnl_sums_fills_exactly() {
+    // f64로는 0.1 + 0.1 + 0.1 != 0.3
+    let buys = [
+        create_test_trade(Side::Buy, "100", "0.1", ""),
+        create_test_trade(Side::Buy, "100", "0.1", ""),
+        create_test_trade(Side::Buy, "100", "0.1", ""),
+    ];
+    let sells = [create_test_trade(Side::Sell, "100.1", "0.3", "")];
+
+    let pnl = realized_pnl(&buys, &sells).unwrap();
+    assert_eq!(pnl.matched_size, decimal("0.3"));
+    assert_eq!(pnl.remaining_size, Decimal::ZERO);
+    assert_eq!(pnl.realized, decimal("0.03"));
+}
+
+#[test]
+fn test_realized_pnl_partial() {
+    let buys = [create_test_trade(Side::Buy, "100", "3", "0.3")];
+    let sells = [create_test_trade(Side::Sell, "90", "1", "")];
+
+    // Only a third of the buy fee belongs to the matched size
+    let pnl = realized_pnl(&buys, &sells).unwrap();
+    assert_eq!(pnl.matched_size, decimal("1"));
+    assert_eq!(pnl.fees, decimal("0.1"));
+    assert_eq!(pnl.realized, decimal("-10.1"));
+    assert_eq!(pnl.remaining_size, decimal("2"));
+
+    let invalid = [create_test_trade(Side::Sell, "abc", "1", "")];
+    assert!(realized_pnl(&buys, &invalid).is_err());
+}
+
+#[test]
+fn test_realized_pnl_rejects_mismatched_trades() {
+    let buys = [create_test_trade(Side::Buy, "100", "1", "")];
+
+    // 매도 목록에 매수 체결이 섞인 경우
+    let wrong_side = [create_test_trade(Side::Buy, "120", "1", "")];
+    assert!(matches!(realized_pnl(&buys, &wrong_side), Err(ExchangeError::InvalidRequest(_))));
+
+    // 다른 심볼의 체결이 섞인 경우
+    let other_symbol = [
+        Trade { symbol: "ETH/USDT".to_string(), ..create_test_trade(Side::Sell, "120", "1", "") },
+    ];
+    assert!(matches!(realized_pnl(&buys, &other_symbol), Err(ExchangeError::InvalidRequest(_))));
+
+    let malformed = [
+        Trade { symbol: "BTCUSDT".to_string(), ..create_test_trade(Side::Buy, "100", "1", "") },
+    ];
+    assert!(matches!(realized_pnl(&malformed, &[]), Err(ExchangeError::InvalidSymbol(_))));
+}
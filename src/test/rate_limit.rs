@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::{ Duration, Instant, UNIX_EPOCH };
+
+use http::header::RETRY_AFTER;
+use http::HeaderMap;
+use futures::future::join_all;
+use serde_json::json;
+
+use crate::test::mock::{ MockResponse, MockServer };
+use crate::upbit::{ Upbit, UpbitTrait };
+use crate::http_util::build_request;
+use crate::{ get_retry_after, parse_http_date, Exchange, ExchangeError, HttpClient };
+
+// 헬퍼 함수: Upbit 객체 생성
+fn create_test_upbit(api_url: &str) -> Upbit {
+    Upbit::new("test_api_key".to_string(), "test_secret".to_string())
+        .unwrap()
+        .with_api_url(api_url)
+}
+
+// 헬퍼 함수: 429 응답 생성
+fn too_many_requests(retry_after: Option<&str>) -> MockResponse {
+    let mut response = MockResponse {
+        status: 429,
+        ..MockResponse::json(r#"{"error":{"name":"too_many_requests","message":"Too many requests"}}"#)
+    };
+    if let Some(retry_after) = retry_after {
+        response.headers.push(("Retry-After".to_string(), retry_after.to_string()));
+    }
+    response
+}
+
+#[test]
+fn test_get_retry_after() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(get_retry_after(&headers), None);
+
+    headers.insert(RETRY_AFTER, "120".parse().unwrap());
+    assert_eq!(get_retry_after(&headers), Some(Duration::from_secs(120)));
+
+    // A date in the past means no wait
+    headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+    assert_eq!(get_retry_after(&headers), Some(Duration::ZERO));
+
+    headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+    assert_eq!(get_retry_after(&headers), None);
+}
+
+#[test]
+fn test_parse_http_date() {
+    assert_eq!(
+        parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"),
+        Some(UNIX_EPOCH + Duration::from_secs(1445412480))
+    );
+    assert_eq!(
+        parse_http_date("Thu, 29 Feb 2024 23:59:59 GMT"),
+        Some(UNIX_EPOCH + Duration::from_secs(1709251199))
+    );
+    assert_eq!(parse_http_date("Wed, 21 Oct 2015 07:28:00 PST"), None);
+}
+
+#[tokio::test]
+async fn test_rate_limited_get_waits_retry_after() {
+    let attempts = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            too_many_requests(Some("1"))
+        } else {
+            MockResponse::json(r#"[{"market":"KRW-BTC","trade_price":140000000.0}]"#)
+        }
+    }).await;
+    let upbit = create_test_upbit(&server.url);
+
+    let started = Instant::now();
+    let price = upbit.get_current_price(json!({ "symbol": "BTC/KRW" })).await.unwrap();
+    assert_eq!(price.price, "140000000.0");
+    // The default backoff is far shorter than the second the exchange asked for
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_rate_limited_post_is_not_retried() {
+    let server = MockServer::start(|_| too_many_requests(Some("1"))).await;
+    let upbit = create_test_upbit(&server.url);
+
+    let error = upbit.send_req_with_sign(BTreeMap::new(), "make_order").await.unwrap_err();
+    assert_eq!(error, ExchangeError::RateLimited { retry_after: Some(Duration::from_secs(1)) });
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_rate_limited_after_retries_are_exhausted() {
+    let server = MockServer::start(|_| too_many_requests(None)).await;
+    let upbit = create_test_upbit(&server.url);
+
+    let error = upbit.send_req_with_sign(BTreeMap::new(), "accounts").await.unwrap_err();
+    assert_eq!(error, ExchangeError::RateLimited { retry_after: None });
+    assert_eq!(server.requests().len(), 4);
+}
+
+// 헬퍼 함수: 200ms 뒤에 시세를 돌려주는 서버
+async fn start_slow_ticker_server() -> MockServer {
+    MockServer::start_delayed(Duration::from_millis(200), |_| {
+        MockResponse::json(r#"[{"market":"KRW-BTC","trade_price":140000000.0}]"#)
+    }).await
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_requests_beyond_max_concurrent_requests_wait() {
+    let server = start_slow_ticker_server().await;
+    let upbit = create_test_upbit(&server.url).with_max_concurrent_requests(2);
+
+    let started = tokio::time::Instant::now();
+    let requests = (0..4).map(|_| upbit.get_current_price(json!({ "symbol": "BTC/KRW" })));
+    let prices = join_all(requests).await;
+
+    // 허용량이 2이므로 네 요청은 두 차례에 나뉘어 처리됨
+    assert!(prices.iter().all(|price| price.is_ok()));
+    assert_eq!(started.elapsed(), Duration::from_millis(400));
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_zero_max_concurrent_requests_is_taken_as_one() {
+    let server = start_slow_ticker_server().await;
+    let upbit = create_test_upbit(&server.url).with_max_concurrent_requests(0);
+
+    let started = tokio::time::Instant::now();
+    let requests = (0..2).map(|_| upbit.get_current_price(json!({ "symbol": "BTC/KRW" })));
+    let prices = join_all(requests).await;
+
+    // 0은 1로 취급되어 멈추지 않고 하나씩 처리됨
+    assert!(prices.iter().all(|price| price.is_ok()));
+    assert_eq!(started.elapsed(), Duration::from_millis(400));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_backoff_does_not_hold_a_request_permit() {
+    let limited = AtomicUsize::new(0);
+    let server = MockServer::start(move |request| {
+        if request.path == "/limited" && limited.fetch_add(1, Ordering::SeqCst) == 0 {
+            too_many_requests(Some("1"))
+        } else {
+            MockResponse::json("{}")
+        }
+    }).await;
+    let client = HttpClient::new().with_max_concurrent_requests(1);
+
+    let send = |path: &str| {
+        let url = format!("{}{}", server.url, path.trim_start_matches('/'));
+        let request = build_request("GET", &url, Vec::new(), BTreeMap::new()).unwrap();
+        client.send(request)
+    };
+    let (limited, other) = tokio::join!(send("/limited"), async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        send("/other").await
+    });
+
+    // 429 대기 중에는 허용량을 놓으므로 다른 요청이 먼저 처리됨
+    assert!(limited.is_ok() && other.is_ok());
+    let paths = server.requests().into_iter().map(|request| request.path).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["/limited", "/other", "/limited"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_cloned_clients_share_max_concurrent_requests() {
+    let server = start_slow_ticker_server().await;
+    let client = HttpClient::new().with_max_concurrent_requests(1);
+    let clone = client.clone();
+
+    let send = |client: &HttpClient| {
+        let request = build_request("GET", &server.url, Vec::new(), BTreeMap::new()).unwrap();
+        let client = client.clone();
+        async move { client.send(request).await }
+    };
+    let started = tokio::time::Instant::now();
+    let (first, second) = tokio::join!(send(&client), send(&clone));
+
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(started.elapsed(), Duration::from_millis(400));
+}
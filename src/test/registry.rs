@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::binance::{ Binance, BinanceTrait };
+use crate::okx::{ Okx, OkxTrait };
+use crate::ExchangeRegistry;
+
+#[test]
+fn test_registry_get_by_name() {
+    let mut registry = ExchangeRegistry::new();
+    registry.register(
+        Arc::new(Binance::new("test_api_key".to_string(), "test_secret".to_string()).unwrap())
+    );
+    registry.register(
+        Arc::new(
+            Okx::new(
+                "test_api_key".to_string(),
+                "test_secret".to_string(),
+                "test_passphrase".to_string()
+            ).unwrap()
+        )
+    );
+
+    // Lookup is case-insensitive so config strings can be used directly
+    let okx = registry.get("okx").unwrap();
+    assert_eq!(okx.get_name(), "Okx");
+    assert_eq!(registry.get("BINANCE").unwrap().get_name(), "Binance");
+    assert!(registry.get("upbit").is_none());
+
+    let names = registry
+        .all()
+        .iter()
+        .map(|exchange| exchange.get_name())
+        .collect::<Vec<String>>();
+    assert_eq!(names, vec!["Binance", "Okx"]);
+}
@@ -0,0 +1,23 @@
+use sha2::{ Sha256, Sha512 };
+
+use crate::sign::{ hmac_base64, hmac_hex };
+
+// RFC 4231 테스트 케이스 2의 키와 메시지
+const KEY: &str = "Jefe";
+const MESSAGE: &str = "what do ya want for nothing?";
+
+#[test]
+fn test_hmac_sha256_hex_matches_rfc_4231() {
+    assert_eq!(
+        hmac_hex::<Sha256>(KEY, MESSAGE).unwrap(),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn test_hmac_sha512_base64_matches_rfc_4231() {
+    assert_eq!(
+        hmac_base64::<Sha512>(KEY, MESSAGE).unwrap(),
+        "Fkt6e/z4GeLjlfvnO1bgo4e9ZCIugx/WECcM1+olBVSXWL91wFqZSm0DT2X48Ob9yuqxo01Ka0tjbgcKOLznNw=="
+    );
+}
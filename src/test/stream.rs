@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::stream::WsManager;
+use crate::test::mock::MockWsServer;
+
+#[tokio::test]
+async fn test_stream_ends_after_max_reconnect_attempts() {
+    let server = MockWsServer::start_dropping().await;
+
+    let mut stream = WsManager::new(&server.url)
+        .with_max_reconnect_attempts(Some(3))
+        .with_reconnect_delay(Duration::from_millis(10))
+        .subscribe().await
+        .unwrap();
+
+    let error = tokio::time
+        ::timeout(Duration::from_secs(5), stream.next()).await
+        .expect("stream kept reconnecting")
+        .unwrap()
+        .unwrap_err();
+    assert!(error.to_string().contains("gave up after 3 reconnect attempts"));
+    assert!(stream.next().await.is_none());
+
+    // The initial connection plus three reconnects
+    assert_eq!(server.connections(), 4);
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use crate::binance::{ Binance, BinanceTrait };
+use crate::test::mock::{ MockResponse, MockServer };
+use crate::{ Exchange, SymbolInfoCache };
+
+const EXCHANGE_INFO: &str = r#"{
+    "timezone": "UTC",
+    "symbols": [{
+        "symbol": "BTCUSDT",
+        "status": "TRADING",
+        "baseAsset": "BTC",
+        "quoteAsset": "USDT",
+        "filters": [
+            {"filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000"},
+            {"filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000"},
+            {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true}
+        ]
+    }]
+}"#;
+
+// 헬퍼 함수: Binance 객체 생성
+fn create_test_binance(api_url: &str) -> Binance {
+    Binance::new("test_api_key".to_string(), "test_secret".to_string())
+        .unwrap()
+        .with_api_url(api_url)
+}
+
+#[tokio::test]
+async fn test_symbol_info_is_cached_within_ttl() {
+    let server = MockServer::start(|_| MockResponse::json(EXCHANGE_INFO)).await;
+    let binance = create_test_binance(&server.url);
+
+    let info = binance.symbol_info("BTC/USDT").await.unwrap();
+    assert_eq!((info.base.as_str(), info.quote.as_str()), ("BTC", "USDT"));
+    assert_eq!(info.tick_size.as_deref(), Some("0.01000000"));
+    assert_eq!(info.lot_size.as_deref(), Some("0.00001000"));
+    assert_eq!(info.min_size.as_deref(), Some("0.00001000"));
+    assert_eq!(info.min_notional.as_deref(), Some("5.00000000"));
+
+    assert_eq!(binance.symbol_info("BTC/USDT").await.unwrap(), info);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/api/v3/exchangeInfo?symbol=BTCUSDT");
+}
+
+#[tokio::test]
+async fn test_symbol_info_cache_is_shared_across_handles() {
+    let server = MockServer::start(|_| MockResponse::json(EXCHANGE_INFO)).await;
+    let cache = SymbolInfoCache::new(Duration::from_secs(60));
+    let first = create_test_binance(&server.url).with_symbol_info_cache(cache.clone());
+    let second = create_test_binance(&server.url).with_symbol_info_cache(cache);
+
+    first.symbol_info("BTC/USDT").await.unwrap();
+    second.symbol_info("BTC/USDT").await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_symbol_info_is_refetched_after_ttl() {
+    let server = MockServer::start(|_| MockResponse::json(EXCHANGE_INFO)).await;
+    let binance = create_test_binance(&server.url)
+        .with_symbol_info_cache(SymbolInfoCache::new(Duration::ZERO));
+
+    binance.symbol_info("BTC/USDT").await.unwrap();
+    binance.symbol_info("BTC/USDT").await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
@@ -1,5 +1,14 @@
 use std::collections::BTreeMap;
-use crate::upbit::{Upbit, UpbitTrait};
+use std::time::Duration;
+use base64::{ Engine as _, engine::general_purpose };
+use http::header::AUTHORIZATION;
+use serde_json::{ json, Value };
+use hmac::{ Hmac, Mac };
+use sha2::{ Digest, Sha256, Sha512 };
+use crate::bithumb::{Bithumb, BithumbTrait};
+use crate::test::mock::{MockResponse, MockServer};
+use crate::upbit::{parse_ticker, parse_trading_fees, parse_transfer_history, Upbit, UpbitTrait};
+use crate::{CoinListCache, Exchange, ExchangeError, OrderState, Side, TransferKind, TransferStatus, WithdrawalFee};
 
 // 헬퍼 함수: Upbit 객체 생성
 fn create_test_upbit() -> Upbit {
@@ -34,18 +43,6 @@ fn test_new_upbit_with_empty_credentials() {
     assert_upbit_creation_error("", "", "API key cannot be empty");
 }
 
-#[test]
-fn test_get_end_point() {
-    let upbit = create_test_upbit();
-    let endpoints = upbit.get_end_point();
-    let expected_endpoints = BTreeMap::from([
-        ("make_order".to_string(), ["POST".to_string(), "v1/orders".to_string()]),
-        ("cancel_order".to_string(), ["DELETE".to_string(), "v1/order".to_string()])
-    ]);
-
-    assert_eq!(endpoints, &expected_endpoints);
-}
-
 #[test]
 fn test_get_end_point_with_key_existing() {
     let upbit = create_test_upbit();
@@ -78,8 +75,11 @@ fn test_get_query_hash() {
     ]);
 
     let query_hash = upbit.get_query_hash(&params);
-    assert!(query_hash.is_ok());
-    // 해시값이 정확한지 확인하는 부분은 테스트 환경에 맞게 추가할 수 있습니다.
+    // SHA512 of "market=BTC-USD&ord_type=limit&price=50000&side=buy&volume=0.01"
+    assert_eq!(
+        query_hash.unwrap(),
+        "9e04eb338c3b7429a416c2cb7d46d25158afccf8ef696f9b422b3247c50c740d2238b4212793288f0c099b467b407f2457d270dddde776e3ee5457955d5aea5b"
+    );
 }
 
 #[test]
@@ -87,7 +87,893 @@ fn test_get_json_with_valid_query_hash() {
     let upbit = create_test_upbit();
     let query_hash = "valid_query_hash".to_string();
     let json_result = upbit.get_json(query_hash);
-    assert!(json_result.is_ok());
-    // 결과가 유효한지 추가로 확인하는 부분은 테스트 환경에 맞게 구현할 수 있습니다.
+    // header.payload.signature
+    assert_eq!(json_result.unwrap().split('.').count(), 3);
+}
+
+#[test]
+fn test_jwt_is_signed_over_known_query_hash() {
+    let upbit = create_test_upbit();
+    let params = BTreeMap::from([
+        ("market", "KRW-BTC"),
+        ("side", "bid"),
+        ("ord_type", "limit"),
+        ("price", "140000000"),
+        ("volume", "0.001"),
+    ]);
+    // SHA512 of "market=KRW-BTC&ord_type=limit&price=140000000&side=bid&volume=0.001"
+    let expected_hash = "4d68bcf675ef9260d07a97eff1bd2c6ce869fc1abe7a79cdca7c7239e1221593bed45b604f652c4114e43f9161d864d8a831d9911e691b5c480690e63bf76904";
+    let query_hash = upbit.get_query_hash(&params).unwrap();
+    assert_eq!(query_hash, expected_hash);
+
+    let token = upbit.get_json(query_hash).unwrap();
+    let [header, payload, signature] = token.split('.').collect::<Vec<_>>()[..] else {
+        panic!("not a JWT: {}", token);
+    };
+    // {"alg":"HS256"}
+    assert_eq!(header, "eyJhbGciOiJIUzI1NiJ9");
+    let claims: Value = serde_json::from_slice(&general_purpose::URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+    assert_eq!(claims["access_key"], "test_api_key");
+    assert_eq!(claims["query_hash"], expected_hash);
+    assert_eq!(claims["query_hash_alg"], "SHA512");
+
+    // nonce가 매번 달라지므로 서명은 secret으로 직접 계산한 HMAC과 비교
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"test_secret").unwrap();
+    mac.update(format!("{}.{}", header, payload).as_bytes());
+    assert_eq!(signature, general_purpose::URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()));
+}
+
+#[test]
+fn test_signed_get_hashes_the_query_string_it_sends() {
+    let upbit = create_test_upbit();
+    let params = BTreeMap::from([("market", "KRW-BTC"), ("uuid", "9ca023a5-851b-4fec-9f0a-48cd83c2eaae")]);
+
+    let request = upbit.build_signed_request(params, "order_chance").unwrap();
+    assert!(request.body().is_empty());
+    let query = request.uri().query().unwrap();
+    assert_eq!(query, "market=KRW-BTC&uuid=9ca023a5-851b-4fec-9f0a-48cd83c2eaae");
+
+    let authorization = request.headers()[AUTHORIZATION].to_str().unwrap();
+    let token = authorization.strip_prefix("Bearer ").unwrap();
+    let payload = token.split('.').nth(1).unwrap();
+    let claims: Value = serde_json::from_slice(&general_purpose::URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+    assert_eq!(claims["query_hash"], hex::encode(Sha512::digest(query.as_bytes())));
+}
+
+
+#[tokio::test]
+async fn test_withdraw_below_minimum_plus_fee_is_rejected() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"currency":{"code":"BTC","withdraw_fee":"0.0005"},"withdraw_limit":{"currency":"BTC","minimum":"0.001"}}"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let result = upbit.withdraw(
+        json!({ "currency": "BTC", "network": "BTC", "amount": "0.0012", "address": "bc1q" })
+    ).await;
+    assert!(matches!(result, Err(ExchangeError::InvalidRequest(_))));
+
+    // Only the withdrawal chance lookup was sent, never the withdrawal itself
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].path.starts_with("/v1/withdraws/chance"));
+}
+
+#[test]
+fn test_withdrawal_of_exactly_minimum_plus_fee_is_accepted() {
+    // f64로는 0.1 + 0.2가 0.3보다 커짐
+    let fee = WithdrawalFee { fee: "0.2".to_string(), min_withdrawal: "0.1".to_string() };
+    assert!(fee.check_amount("0.3").is_ok());
+    assert!(matches!(fee.check_amount("0.29999999"), Err(ExchangeError::InvalidRequest(_))));
+    assert!(matches!(fee.check_amount("abc"), Err(ExchangeError::InvalidRequest(_))));
+}
+
+#[test]
+fn test_parse_trading_fees_from_order_chance() {
+    let res = json!({
+        "bid_fee": "0.0005",
+        "ask_fee": "0.0005",
+        "maker_bid_fee": "0.0002",
+        "maker_ask_fee": "0.0002",
+        "market": { "id": "KRW-BTC", "name": "BTC/KRW" }
+    });
+
+    let fees = parse_trading_fees(&res, "BTC/KRW").unwrap();
+    assert_eq!(fees.exchange, "Upbit");
+    assert_eq!(fees.maker, "0.0002");
+    assert_eq!(fees.taker, "0.0005");
+}
+
+#[tokio::test]
+async fn test_place_order_sends_identifier() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"uuid":"order-1"}"#)).await;
+    // The caller's id takes precedence over the generated one
+    let upbit = create_test_upbit().with_api_url(&server.url).with_auto_client_order_id();
+
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000",
+        "amount": "0.001",
+        "client_order_id": "my-order-1"
+    });
+    upbit.place_order(order).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/orders");
+    assert!(requests[0].body.contains(r#""identifier":"my-order-1""#));
+}
+
+#[tokio::test]
+async fn test_place_post_only_order_sends_time_in_force() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"uuid":"order-1"}"#)).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000",
+        "amount": "0.001",
+        "time_in_force": "GTC"
+    });
+    let mut post_only = order.clone();
+    post_only["post_only"] = json!(true);
+    upbit.place_order(order).await.unwrap();
+    upbit.place_order(post_only).await.unwrap();
+
+    let requests = server.requests();
+    // Good till canceled is the default, so it is not sent
+    assert!(!requests[0].body.contains("time_in_force"), "{}", requests[0].body);
+    assert!(requests[1].body.contains(r#""time_in_force":"post_only""#), "{}", requests[1].body);
+}
+
+#[tokio::test]
+async fn test_place_order_param_shapes() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"uuid":"order-1"}"#)).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+    let order = |side: &str, order_type: &str, price: &str, amount: &str| {
+        json!({
+            "symbol": "BTC/KRW",
+            "side": side,
+            "order_type": order_type,
+            "price": price,
+            "amount": amount
+        })
+    };
+
+    upbit.place_order(order("bid", "limit", "140000000", "0.001")).await.unwrap();
+    upbit.place_order(order("bid", "price", "10000", "")).await.unwrap();
+    upbit.place_order(order("ask", "market", "", "0.001")).await.unwrap();
+
+    let bodies = server
+        .requests()
+        .iter()
+        .map(|request| serde_json::from_str::<Value>(&request.body).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(bodies[0]["ord_type"], "limit");
+    assert_eq!((&bodies[0]["price"], &bodies[0]["volume"]), (&json!("140000000"), &json!("0.001")));
+    // 시장가 매수는 총액(price)만, 시장가 매도는 수량(volume)만 전송
+    assert_eq!(bodies[1]["ord_type"], "price");
+    assert_eq!(bodies[1]["price"], "10000");
+    assert!(bodies[1].get("volume").is_none(), "{}", bodies[1]);
+    assert_eq!(bodies[2]["ord_type"], "market");
+    assert_eq!(bodies[2]["volume"], "0.001");
+    assert!(bodies[2].get("price").is_none(), "{}", bodies[2]);
+}
+
+#[tokio::test]
+async fn test_place_order_missing_required_field() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"uuid":"order-1"}"#)).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let market_buy = json!({ "symbol": "BTC/KRW", "side": "bid", "order_type": "market" });
+    let error = upbit.place_order_typed(market_buy).await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::InvalidRequest(
+            "A market buy needs a quote_amount, the total to spend in the quote currency".to_string()
+        )
+    );
+
+    // 시장가 매수에 수량이나 가격을 함께 주면 거부
+    for (key, value) in [("amount", "0.001"), ("price", "10000")] {
+        let mut market_buy = json!({
+            "symbol": "BTC/KRW",
+            "side": "bid",
+            "order_type": "market",
+            "quote_amount": "10000"
+        });
+        market_buy[key] = json!(value);
+        let error = upbit.place_order_typed(market_buy).await.unwrap_err();
+        assert_eq!(
+            error,
+            ExchangeError::InvalidRequest(
+                "A market buy is sized by quote_amount, not price or amount".to_string()
+            )
+        );
+    }
+
+    let price_order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "price",
+        "price": "10000",
+        "amount": "0.001"
+    });
+    let error = upbit.place_order_typed(price_order).await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::InvalidRequest("A price order is sized by its price, not amount".to_string())
+    );
+
+    let market_sell = json!({
+        "symbol": "BTC/KRW",
+        "side": "ask",
+        "order_type": "market",
+        "price": "10000"
+    });
+    let error = upbit.place_order_typed(market_sell).await.unwrap_err();
+    assert_eq!(error, ExchangeError::InvalidRequest("A market sell needs an amount".to_string()));
+
+    let limit = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000"
+    });
+    let error = upbit.place_order_typed(limit).await.unwrap_err();
+    assert_eq!(error, ExchangeError::InvalidRequest("A limit order needs an amount".to_string()));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_safe_mode_blocks_orders_but_not_market_data() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-BTC","timestamp":1,"orderbook_units":[{"ask_price":140000000,"bid_price":139990000,"ask_size":1,"bid_size":1}]}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url).with_safe_mode(true);
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000",
+        "amount": "0.001"
+    });
+    let blocked = |method: &str| ExchangeError::SafeModeBlocked {
+        exchange: "Upbit".to_string(),
+        method: method.to_string(),
+    };
+
+    let error = upbit.place_order(order.clone()).await.unwrap_err();
+    assert_eq!(error, "Upbit place_order is blocked in safe mode");
+    assert_eq!(upbit.place_order_typed(order).await.unwrap_err(), blocked("place_order_typed"));
+    let cancel = json!({ "order_id": "order-1" });
+    let error = upbit.cancel_order_typed(cancel.clone()).await.unwrap_err();
+    assert_eq!(error, blocked("cancel_order_typed"));
+    assert_eq!(upbit.amend_order(cancel).await.unwrap_err(), blocked("amend_order"));
+    let withdrawal = json!({ "currency": "BTC", "amount": "0.01", "address": "bc1q" });
+    assert_eq!(upbit.withdraw(withdrawal).await.unwrap_err(), blocked("withdraw"));
+    let params = BTreeMap::from([("uuid", "order-1")]);
+    let error = upbit.send_req_with_sign(params, "cancel_order").await.unwrap_err();
+    assert_eq!(error, blocked("cancel_order"));
+    assert!(server.requests().is_empty());
+
+    let orderbook = upbit.get_order_book(json!({ "symbol": "BTC/KRW" })).await.unwrap();
+    assert_eq!(orderbook.orderbook_unit[0].bid_price, "139990000");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_cancel_order_by_identifier() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"uuid":"order-1"}"#)).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    upbit.cancel_order(json!({ "client_order_id": "my-order-1" })).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[0].path, "/v1/order?identifier=my-order-1");
+    assert!(requests[0].body.is_empty());
+}
+
+#[tokio::test]
+async fn test_amend_order_reports_lost_order_when_replace_fails() {
+    let server = MockServer::start(|request| {
+        if request.method == "DELETE" {
+            MockResponse::json(
+                r#"{"uuid":"order-1","side":"bid","ord_type":"limit","price":"140000000","state":"wait","market":"KRW-BTC","created_at":"2024-08-20T00:00:00+09:00","volume":"0.001","executed_volume":"0","paid_fee":"0"}"#
+            )
+        } else {
+            MockResponse {
+                status: 400,
+                ..MockResponse::json(
+                    r#"{"error":{"name":"insufficient_funds_bid","message":"주문가능한 금액(KRW)이 부족합니다."}}"#
+                )
+            }
+        }
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let error = upbit
+        .amend_order(
+            json!({
+                "symbol": "BTC/KRW",
+                "order_id": "order-1",
+                "side": "bid",
+                "order_type": "limit",
+                "price": "150000000",
+                "amount": "0.001"
+            })
+        ).await
+        .unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("order-1 was canceled"));
+    assert!(message.contains("no longer on the book"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("DELETE", "/v1/order?uuid=order-1"));
+    assert_eq!((requests[1].method.as_str(), requests[1].path.as_str()), ("POST", "/v1/orders"));
+    // The replacement must not reuse the canceled order's id
+    assert!(!requests[1].body.contains("order-1"));
+}
+
+#[tokio::test]
+async fn test_place_order_confirmed_looks_up_order_after_read_failure() {
+    let server = MockServer::start(|request| {
+        if request.method == "POST" {
+            // The order is accepted, but its response body cannot be decoded
+            MockResponse {
+                headers: vec![("Content-Encoding".to_string(), "gzip".to_string())],
+                body: b"not gzip".to_vec(),
+                ..MockResponse::json("")
+            }
+        } else {
+            MockResponse::json(
+                r#"{"uuid":"order-1","side":"bid","ord_type":"limit","price":"140000000","state":"wait","market":"KRW-BTC","created_at":"2024-08-20T00:00:00+09:00","volume":"0.001","executed_volume":"0","paid_fee":"0"}"#
+            )
+        }
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let order = upbit
+        .place_order_confirmed(
+            json!({
+                "symbol": "BTC/KRW",
+                "side": "bid",
+                "order_type": "limit",
+                "price": "140000000",
+                "amount": "0.001",
+                "client_order_id": "my-order-1"
+            })
+        ).await
+        .unwrap();
+    assert_eq!(order.ord_id, "order-1");
+    assert_eq!(order.state, OrderState::New);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[1].method, "GET");
+    assert_eq!(requests[1].path, "/v1/order?identifier=my-order-1");
+}
+
+#[tokio::test]
+async fn test_place_order_confirmed_reports_a_placed_order_it_could_not_read_back() {
+    let server = MockServer::start(|request| {
+        if request.method == "POST" {
+            MockResponse::json(
+                r#"{"uuid":"order-1","side":"bid","ord_type":"limit","price":"140000000","state":"wait","market":"KRW-BTC","created_at":"2024-08-20T00:00:00+09:00","volume":"0.001","executed_volume":"0","paid_fee":"0"}"#
+            )
+        } else {
+            MockResponse {
+                status: 404,
+                ..MockResponse::json(r#"{"error":{"name":"order_not_found","message":"주문을 찾지 못했습니다."}}"#)
+            }
+        }
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let error = upbit
+        .place_order_confirmed(
+            json!({
+                "symbol": "BTC/KRW",
+                "side": "bid",
+                "order_type": "limit",
+                "price": "140000000",
+                "amount": "0.001",
+                "client_order_id": "my-order-1"
+            })
+        ).await
+        .unwrap_err();
+    match error {
+        ExchangeError::Unconfirmed { client_order_id, message } => {
+            assert_eq!(client_order_id, "my-order-1");
+            assert!(message.contains("order_not_found"));
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[tokio::test]
+async fn test_get_order_book_parses_snapshot_timestamp() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-BTC","timestamp":1529910247984,"total_ask_size":8.83621228,"total_bid_size":2.43976741,"orderbook_units":[{"ask_price":6956000,"bid_price":6954000,"ask_size":0.24078656,"bid_size":0.00718341},{"ask_price":6958000,"bid_price":6953000,"ask_size":1.12919,"bid_size":0.11500074}],"level":0}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let orderbook = upbit.get_order_book(json!({ "symbol": "BTC/KRW" })).await.unwrap();
+    assert_eq!(orderbook.timestamp, Some(1529910247984));
+    assert_eq!(orderbook.age_millis(1529910250000), Some(2016));
+    // Clock skew must not underflow
+    assert_eq!(orderbook.age_millis(1529910240000), Some(0));
 }
 
+#[tokio::test]
+async fn test_get_order_books_batches_markets_into_one_request() {
+    // 응답 순서가 요청 순서와 달라도 결과는 symbols 순서를 따라야 함
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-XRP","timestamp":3,"orderbook_units":[{"ask_price":701,"bid_price":700,"ask_size":1,"bid_size":1}]},{"market":"KRW-BTC","timestamp":1,"orderbook_units":[{"ask_price":140000000,"bid_price":139990000,"ask_size":1,"bid_size":1}]},{"market":"KRW-ETH","timestamp":2,"orderbook_units":[{"ask_price":4501000,"bid_price":4500000,"ask_size":1,"bid_size":1}]}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let orderbooks = upbit.get_order_books(&["BTC/KRW", "ETH/KRW", "XRP/KRW"]).await;
+    let markets = orderbooks
+        .iter()
+        .map(|orderbook| orderbook.as_ref().unwrap().market.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(markets, vec!["BTC/KRW", "ETH/KRW", "XRP/KRW"]);
+    assert_eq!(orderbooks[1].as_ref().unwrap().orderbook_unit[0].bid_price, "4500000");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/orderbook?level=0&markets=KRW-BTC%2CKRW-ETH%2CKRW-XRP");
+}
+
+#[tokio::test]
+async fn test_get_order_books_fails_symbols_individually() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-BTC","timestamp":1,"orderbook_units":[{"ask_price":140000000,"bid_price":139990000,"ask_size":1,"bid_size":1}]}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let orderbooks = upbit.get_order_books(&["BTC/KRW", "BTCKRW", "DOGE/KRW"]).await;
+    assert_eq!(orderbooks.len(), 3);
+    assert_eq!(orderbooks[0].as_ref().unwrap().market, "BTC/KRW");
+    assert_eq!(orderbooks[1].as_ref().unwrap_err(), &ExchangeError::InvalidSymbol("BTCKRW".to_string()));
+    assert!(matches!(&orderbooks[2], Err(ExchangeError::Parse(message)) if message.starts_with("No order book for KRW-DOGE")));
+}
+
+#[tokio::test]
+async fn test_get_prices_batches_markets_into_one_request() {
+    // Upbit does not promise the tickers come back in the requested order
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-ETH","trade_price":4500000.0},{"market":"KRW-BTC","trade_price":140000000.0}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let prices = upbit.get_prices(&["BTC/KRW", "ETH/KRW"]).await.unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!((prices[0].symbol.as_str(), prices[0].price.as_str()), ("BTC/KRW", "140000000.0"));
+    assert_eq!((prices[1].symbol.as_str(), prices[1].price.as_str()), ("ETH/KRW", "4500000.0"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/ticker?markets=KRW-BTC%2CKRW-ETH");
+}
+
+#[test]
+fn test_parse_ticker() {
+    let res = json!([{
+        "market": "KRW-BTC",
+        "trade_date": "20241015",
+        "trade_time": "051232",
+        "opening_price": 92000000.0,
+        "high_price": 93500000.0,
+        "low_price": 91200000.0,
+        "trade_price": 92850000.0,
+        "prev_closing_price": 92000000.0,
+        "change": "RISE",
+        "change_price": 850000.0,
+        "change_rate": 0.0092391304,
+        "signed_change_price": 850000.0,
+        "signed_change_rate": 0.0092391304,
+        "trade_volume": 0.00215,
+        "acc_trade_price_24h": 171542398045.1235,
+        "acc_trade_volume_24h": 1853.09102446,
+        "timestamp": 1728969152543u64
+    }]);
+
+    let ticker = parse_ticker(&res, "BTC/KRW").unwrap();
+    assert_eq!(ticker.last, "92850000.0");
+    // The ticker carries no quotes
+    assert!(ticker.bid.is_none() && ticker.ask.is_none());
+    assert_eq!(ticker.high_24h.as_deref(), Some("93500000.0"));
+    assert_eq!(ticker.low_24h.as_deref(), Some("91200000.0"));
+    assert_eq!(ticker.volume_24h.as_deref(), Some("1853.09102446"));
+    assert_eq!(ticker.change_24h.as_deref(), Some("850000.0"));
+}
+
+#[tokio::test]
+async fn test_endpoint_override_is_used_for_requests() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-BTC","timestamp":1529910247984,"orderbook_units":[{"ask_price":6956000,"bid_price":6954000,"ask_size":0.24078656,"bid_size":0.00718341}]}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit()
+        .with_api_url(&server.url)
+        .with_endpoint_override("order_book", "get", "/v2/orderbook")
+        .unwrap();
+    assert_eq!(
+        upbit.get_end_point_with_key("order_book"),
+        Some(&["GET".to_string(), "v2/orderbook".to_string()])
+    );
+
+    upbit.get_order_book(json!({ "symbol": "BTC/KRW" })).await.unwrap();
+    assert!(server.requests()[0].path.starts_with("/v2/orderbook?"));
+}
+
+#[test]
+fn test_set_endpoint_rejects_unknown_method() {
+    let mut upbit = create_test_upbit();
+
+    let error = upbit.set_endpoint("order_book", "FETCH", "v2/orderbook").unwrap_err();
+    assert!(matches!(error, ExchangeError::InvalidRequest(_)));
+    assert_eq!(
+        upbit.get_end_point_with_key("order_book"),
+        Some(&["GET".to_string(), "v1/orderbook".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn test_market_buy_is_sized_by_quote_amount() {
+    let server = MockServer::start(|_| MockResponse::json(r#"{"uuid":"order-1"}"#)).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "market",
+        "price": "",
+        "amount": "",
+        "quote_amount": "10000"
+    });
+
+    upbit.place_order(order).await.unwrap();
+
+    // 원화 시장가 매수는 quote_amount를 총액(price)으로 전송
+    let body = serde_json::from_str::<Value>(&server.requests()[0].body).unwrap();
+    assert_eq!(body["ord_type"], "price");
+    assert_eq!(body["price"], "10000");
+    assert!(body.get("volume").is_none(), "{}", body);
+}
+
+#[tokio::test]
+async fn test_get_order_book_keeps_numbers_as_sent() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"[{"market":"KRW-BTC","timestamp":1529910247984,"orderbook_units":[{"ask_price":64000000.0,"bid_price":63990000,"ask_size":1e-8,"bid_size":0.00000001},{"ask_price":64010000,"bid_price":63980000,"ask_size":0.123456789012345678,"bid_size":12345678901234567890}]}]"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let orderbook = upbit.get_order_book(json!({ "symbol": "BTC/KRW" })).await.unwrap();
+    let unit = &orderbook.orderbook_unit[0];
+    assert_eq!(unit.ask_price, "64000000.0");
+    assert_eq!(unit.bid_price, "63990000");
+    assert_eq!(unit.ask_size, "1e-8");
+    assert_eq!(unit.bid_size, "0.00000001");
+    // f64로는 반올림되는 자릿수도 그대로
+    let unit = &orderbook.orderbook_unit[1];
+    assert_eq!(unit.ask_size, "0.123456789012345678");
+    assert_eq!(unit.bid_size, "12345678901234567890");
+}
+
+#[tokio::test]
+async fn test_get_coin_list_is_cached_within_ttl() {
+    let server = MockServer::start(|_| MockResponse::json(r#"[{"market":"KRW-BTC"},{"market":"KRW-ETH"}]"#)).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let coin_list = upbit.get_coin_list().await.unwrap();
+    assert_eq!(coin_list.coin_list, vec!["BTC/KRW".to_string(), "ETH/KRW".to_string()]);
+    upbit.get_coin_list().await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    upbit.refresh_coin_list().await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_shared_coin_list_cache_is_keyed_by_exchange() {
+    let server = MockServer::start(|_| MockResponse::json(r#"[{"market":"KRW-BTC"}]"#)).await;
+    let cache = CoinListCache::new(Duration::from_secs(60));
+    let upbit = create_test_upbit().with_api_url(&server.url).with_coin_list_cache(cache.clone());
+    let bithumb = Bithumb::new("test_api_key".to_string(), "test_secret".to_string())
+        .unwrap()
+        .with_api_url(&server.url)
+        .with_coin_list_cache(cache);
+
+    assert_eq!(upbit.get_coin_list().await.unwrap().market, "Upbit");
+    assert_eq!(bithumb.get_coin_list().await.unwrap().market, "Bithumb");
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_place_order_typed_parses_order_response() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"uuid":"cdd92199-2897-4e14-9448-f923320408ad","side":"bid","ord_type":"limit","price":"140000000","state":"wait","market":"KRW-BTC","created_at":"2024-08-20T10:00:00+09:00","volume":"0.001","remaining_volume":"0.0006","reserved_fee":"35","remaining_fee":"21","paid_fee":"14","locked":"84021","executed_volume":"0.0004","trades_count":1}"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000",
+        "amount": "0.001"
+    });
+
+    let order = upbit.place_order_typed(order).await.unwrap();
+    assert_eq!(order.exchange, "Upbit");
+    assert_eq!(order.ord_id, "cdd92199-2897-4e14-9448-f923320408ad");
+    assert_eq!(order.side, Side::Buy);
+    assert_eq!(order.ord_type, "limit");
+    assert_eq!(order.price, "140000000");
+    assert_eq!(order.state, OrderState::PartiallyFilled);
+    assert_eq!(order.market, "BTC/KRW");
+    assert_eq!(order.volume, "0.001");
+    assert_eq!(order.create_at, "2024-08-20T10:00:00+09:00");
+    assert_eq!(order.amount, "0.0004");
+    assert_eq!((order.fee.as_str(), order.fee_currency.as_str()), ("14", "KRW"));
+    assert_eq!(order.raw["locked"], "84021");
+}
+
+#[tokio::test]
+async fn test_get_order_keeps_an_unknown_state() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"uuid":"order-1","side":"bid","ord_type":"limit","price":"140000000","state":"trigger_wait","market":"KRW-BTC","created_at":"2024-08-20T10:00:00+09:00","volume":"0.001","executed_volume":"0","paid_fee":"0"}"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let order = upbit.get_order(json!({ "order_id": "order-1" })).await.unwrap();
+    assert_eq!(order.state, OrderState::Unknown);
+    assert_eq!(order.raw_state, "trigger_wait");
+}
+
+#[tokio::test]
+async fn test_cancel_order_typed_parses_canceled_order() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"uuid":"cdd92199-2897-4e14-9448-f923320408ad","side":"ask","ord_type":"limit","price":"141000000","state":"cancel","market":"KRW-BTC","created_at":"2024-08-20T10:00:00+09:00","volume":"0.001","remaining_volume":"0.001","reserved_fee":"0","remaining_fee":"0","paid_fee":"0","locked":"0.001","executed_volume":"0","trades_count":0}"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let order = upbit
+        .cancel_order_typed(json!({ "order_id": "cdd92199-2897-4e14-9448-f923320408ad" }))
+        .await
+        .unwrap();
+    assert_eq!(order.exchange, "Upbit");
+    assert_eq!(order.ord_id, "cdd92199-2897-4e14-9448-f923320408ad");
+    assert_eq!(order.side, Side::Sell);
+    assert_eq!(order.ord_type, "limit");
+    assert_eq!(order.price, "141000000");
+    assert_eq!(order.state, OrderState::Canceled);
+    assert_eq!(order.raw_state, "cancel");
+    assert_eq!(order.market, "BTC/KRW");
+    assert_eq!(order.volume, "0.001");
+    assert_eq!(order.create_at, "2024-08-20T10:00:00+09:00");
+    assert_eq!(order.amount, "0");
+    assert_eq!((order.fee.as_str(), order.fee_currency.as_str()), ("0", "KRW"));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[0].path, "/v1/order?uuid=cdd92199-2897-4e14-9448-f923320408ad");
+    assert!(requests[0].body.is_empty());
+}
+
+#[tokio::test]
+async fn test_custom_pool_settings_build_a_working_client() {
+    let server = MockServer::start(|_| MockResponse::json(r#"[{"market":"KRW-BTC"}]"#)).await;
+    let upbit = create_test_upbit()
+        .with_api_url(&server.url)
+        .with_pool_max_idle_per_host(4)
+        .with_pool_idle_timeout(Duration::from_secs(30))
+        .with_tcp_keepalive(Duration::from_secs(15));
+
+    upbit.get_coin_list().await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_min_notional_check_rejects_small_order_before_sending() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"bid_fee":"0.0005","ask_fee":"0.0005","market":{"id":"KRW-BTC","bid":{"currency":"KRW","min_total":"5000"},"ask":{"currency":"BTC","min_total":"5000"}}}"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url).with_min_notional_check();
+    assert_eq!(upbit.min_order_notional("BTC/KRW").await.unwrap().to_string(), "5000");
+
+    let order = json!({
+        "symbol": "BTC/KRW",
+        "side": "bid",
+        "order_type": "limit",
+        "price": "140000000",
+        "amount": "0.00003"
+    });
+    let error = upbit.place_order_typed(order).await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::InvalidRequest("Order value 4200 is below the BTC/KRW minimum of 5000".to_string())
+    );
+
+    // 주문 가능 정보는 한 번만 조회되고, 두 번째 검사는 캐시에서 처리됨
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/orders/chance?market=KRW-BTC");
+}
+
+#[tokio::test]
+async fn test_min_notional_check_values_a_market_buy_at_its_quote_amount() {
+    let server = MockServer::start(|_| {
+        MockResponse::json(
+            r#"{"market":{"id":"KRW-BTC","bid":{"currency":"KRW","min_total":"5000"},"ask":{"currency":"BTC","min_total":"5000"}}}"#
+        )
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url).with_min_notional_check();
+
+    let order = json!({ "symbol": "BTC/KRW", "side": "bid", "order_type": "market", "quote_amount": "4000" });
+    let error = upbit.place_order_typed(order).await.unwrap_err();
+    assert_eq!(
+        error,
+        ExchangeError::InvalidRequest("Order value 4000 is below the BTC/KRW minimum of 5000".to_string())
+    );
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_order_book_reports_maintenance_page() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 200,
+        headers: vec![("Content-Type".to_string(), "text/html".to_string())],
+        body: b"<html><head><title>Upbit - Scheduled maintenance</title></head><body>...</body></html>".to_vec(),
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let error = upbit.get_order_book(json!({ "symbol": "BTC/KRW" })).await.unwrap_err();
+    assert!(error.starts_with("Failed to parse response: "), "{}", error);
+    assert!(error.contains("Scheduled maintenance"), "{}", error);
+    // Retried once before giving up
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_korean_error_message_round_trips() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 400,
+        headers: vec![("Content-Type".to_string(), "application/json; charset=utf-8".to_string())],
+        body: json!({
+            "error": { "name": "under_min_total_bid", "message": "최소주문금액 이상으로 주문해주세요" }
+        }).to_string().into_bytes(),
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let error = upbit.get_all_balances().await.unwrap_err();
+    assert_eq!(error, ExchangeError::ExchangeRejected {
+        code: "under_min_total_bid".to_string(),
+        message: "최소주문금액 이상으로 주문해주세요".to_string(),
+    });
+}
+
+#[tokio::test]
+async fn test_non_json_korean_body_is_cut_on_a_character_boundary() {
+    let server = MockServer::start(|_| MockResponse {
+        status: 200,
+        headers: vec![("Content-Type".to_string(), "text/plain; charset=utf-8".to_string())],
+        body: "서비스 점검 중입니다. ".repeat(30).into_bytes(),
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let error = upbit.get_all_balances().await.unwrap_err().to_string();
+    assert!(error.contains("서비스 점검 중입니다"), "{}", error);
+    assert!(!error.contains('\u{FFFD}'), "{}", error);
+}
+
+#[test]
+fn test_parse_transfer_history_converts_kst_timestamps() {
+    let res = json!([
+        {
+            "type": "deposit",
+            "uuid": "94332e99-3a87-4a35-ad98-28b0c969f830",
+            "currency": "KRW",
+            "net_type": null,
+            "txid": "9e37c537-6849-4c8b-a134-57313f5dfc5a",
+            "state": "ACCEPTED",
+            "created_at": "2017-12-08T15:38:02+09:00",
+            "done_at": "2017-12-08T15:38:02+09:00",
+            "amount": "100000.0",
+            "fee": "0.0",
+            "transaction_type": "default"
+        },
+        {
+            "type": "deposit",
+            "uuid": "6e7a4e6d-1b6e-4d43-b2fe-2a66e5e0a1c4",
+            "currency": "BTC",
+            "net_type": "BTC",
+            "txid": "",
+            "state": "PROCESSING",
+            "created_at": "2017-12-09T00:00:00+09:00",
+            "done_at": null,
+            "amount": "0.01",
+            "fee": "0.0",
+            "transaction_type": "default"
+        }
+    ]);
+
+    let transfers = parse_transfer_history(&res, TransferKind::Deposit).unwrap();
+    assert_eq!(transfers[0].status, TransferStatus::Completed);
+    assert_eq!(transfers[0].tx_id.as_deref(), Some("9e37c537-6849-4c8b-a134-57313f5dfc5a"));
+    assert_eq!(transfers[0].address, None);
+    // 2017-12-08T06:38:02Z
+    assert_eq!(transfers[0].timestamp, 1512715082000);
+    assert_eq!(transfers[1].status, TransferStatus::Pending);
+    assert_eq!(transfers[1].tx_id, None);
+    assert_eq!(transfers[1].timestamp, 1512745200000);
+}
+
+#[tokio::test]
+async fn test_get_transfer_history_reads_every_page() {
+    let server = MockServer::start(|request| {
+        // 헬퍼: 입금 기록 하나
+        let deposit = |created_at: &str| json!({
+            "type": "deposit",
+            "uuid": "94332e99-3a87-4a35-ad98-28b0c969f830",
+            "currency": "KRW",
+            "txid": "",
+            "state": "ACCEPTED",
+            "created_at": created_at,
+            "amount": "100000.0",
+            "fee": "0.0"
+        });
+        let page = if request.path.starts_with("/v1/withdraws") {
+            json!([])
+        } else if request.path.contains("page=1") {
+            Value::Array(vec![deposit("2024-01-02T09:00:00+09:00"); 100])
+        } else {
+            json!([deposit("2024-01-01T09:00:00.123+09:00")])
+        };
+        MockResponse::json(&page.to_string())
+    }).await;
+    let upbit = create_test_upbit().with_api_url(&server.url);
+
+    let transfers = upbit.get_transfer_history(json!({})).await.unwrap();
+    assert_eq!(transfers.len(), 101);
+    // 소수점 이하 초는 밀리초로 유지
+    assert_eq!(transfers[0].timestamp, 1704067200123);
+
+    let paths = server.requests().into_iter().map(|request| request.path).collect::<Vec<_>>();
+    assert_eq!(paths, vec![
+        "/v1/deposits?limit=100&page=1",
+        "/v1/deposits?limit=100&page=2",
+        "/v1/withdraws?limit=100&page=1",
+    ]);
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{ Arc, RwLock };
+use std::time::{ Duration, Instant };
+
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+// Values fetched on first use and again once older than the TTL. Clones share the same
+// entries, so one cache can back several exchange handles. A zero TTL turns caching off.
+#[derive(Clone)]
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: Arc<RwLock<HashMap<K, (Instant, V)>>>,
+}
+
+impl<K, V> Default for TtlCache<K, V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL)
+    }
+}
+
+impl<K, V> TtlCache<K, V> {
+    pub fn new(ttl: Duration) -> Self {
+        TtlCache { ttl, entries: Arc::new(RwLock::new(HashMap::new())) }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    // Concurrent misses for the same key may each fetch; the last one stored wins
+    pub async fn get_or_fetch<F, Fut, E>(&self, key: K, fetch: F) -> Result<V, E>
+        where F: FnOnce() -> Fut, Fut: Future<Output = Result<V, E>>
+    {
+        if let Some((fetched_at, value)) = self.entries.read().unwrap().get(&key) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+        self.refresh(key, fetch).await
+    }
+
+    // Fetches regardless of the cached entry and stores the result
+    pub async fn refresh<F, Fut, E>(&self, key: K, fetch: F) -> Result<V, E>
+        where F: FnOnce() -> Fut, Fut: Future<Output = Result<V, E>>
+    {
+        let value = fetch().await?;
+        self.entries.write().unwrap().insert(key, (Instant::now(), value.clone()));
+        Ok(value)
+    }
+}
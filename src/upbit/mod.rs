@@ -1,19 +1,108 @@
 use std::collections::BTreeMap;
+use std::future::Future;
 use async_trait::async_trait;
-use serde_json::{ from_slice, Value };
-use http::{ header::{ ACCEPT, AUTHORIZATION, CONTENT_TYPE }, HeaderName, Request };
+use serde_json::{ json, Value };
+use http::{ header::{ ACCEPT, AUTHORIZATION, CONTENT_TYPE }, Request };
 use sha2::{ Digest, Sha256, Sha512 };
 use uuid::Uuid;
-use hmac::{ Hmac, Mac };
 use jwt::SignWithKey;
 
-use crate::{ get_query_string, send, CoinList, Exchange, OrderBook, OrderBookUnit, Price };
+use crate::http_util::build_request;
+use crate::sign::hmac_key;
+use crate::{
+    cancel_each,
+    cancel_then_place,
+    check_min_notional,
+    check_quote_amount,
+    check_safe_mode,
+    check_safe_mode_endpoint,
+    fetch_json_retrying,
+    filter_transfers,
+    get_client_order_id,
+    get_number_string,
+    get_optional_str,
+    get_order_id_param,
+    get_query_string,
+    get_time_in_force,
+    parse_datetime_millis,
+    parse_decimal,
+    parse_json_body,
+    parse_response,
+    resolve_symbol,
+    split_symbol,
+    supports_method,
+    top_by_volume,
+    with_request_timeout,
+    AccountInfo,
+    Balance,
+    Capabilities,
+    CoinList,
+    CoinListCache,
+    Decimal,
+    DepositAddress,
+    Exchange,
+    ExchangeError,
+    FeeSchedule,
+    HttpClient,
+    Market,
+    Order,
+    OrderBook,
+    OrderBookUnit,
+    OrderState,
+    Price,
+    PriceSource,
+    Quote,
+    Side,
+    Ticker,
+    TimeInForce,
+    Trade,
+    Transfer,
+    TransferKind,
+    TransferStatus,
+    TtlCache,
+    Wallet,
+    WithdrawalFee,
+};
+
+// Deposit and withdrawal history pages hold at most 100 records, newest first
+const TRANSFER_PAGE_SIZE: usize = 100;
+
+// Optional `Exchange` methods implemented here, reported by `supports`
+const IMPLEMENTED_METHODS: &[&str] = &[
+    "amend_order",
+    "cancel_all_orders",
+    "cancel_order_typed",
+    "check_auth",
+    "get_account_info",
+    "get_all_balances",
+    "get_best_quote",
+    "get_deposit_address",
+    "get_order",
+    "get_recent_trades",
+    "min_order_notional",
+    "get_ticker",
+    "get_trading_fees",
+    "get_transfer_history",
+    "get_withdrawal_fee",
+    "ping",
+    "place_order_typed",
+    "top_markets_by_volume",
+    "withdraw",
+];
 
 pub struct Upbit {
     api_url: String,
     api_key: String,
     secret: String,
     endpoint: BTreeMap<String, [String; 2]>,
+    client: HttpClient,
+    coin_list_cache: CoinListCache,
+    // Minimum order value per market, from order chance
+    min_order_notional_cache: TtlCache<String, Decimal>,
+    fuzzy_symbols: bool,
+    safe_mode: bool,
+    auto_client_order_id: bool,
+    min_notional_check: bool,
 }
 
 #[allow(dead_code)]
@@ -26,42 +115,89 @@ pub trait UpbitTrait {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> impl std::future::Future<Output = Result<Value, String>> + Send;
+    ) -> impl std::future::Future<Output = Result<Value, ExchangeError>> + Send;
 }
 
+impl_endpoint_overrides!(Upbit);
+impl_client_settings!(Upbit, client);
+impl_public_requests!(Upbit, check_response);
+impl_exchange_options!(Upbit);
+impl_auto_client_order_id!(Upbit);
+
 impl Upbit {
-    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
-        if api_key.is_empty() || secret.is_empty() {
-            return Err("API key and Secret cannot be empty".to_string());
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
+    // place_order_typed checks the order value against min_order_notional before sending it
+    pub fn with_min_notional_check(mut self) -> Self {
+        self.min_notional_check = true;
+        self
+    }
+
+    // The first url is the primary host, the rest are failed over to in order
+    pub fn with_api_urls(mut self, api_urls: &[&str]) -> Self {
+        if let Some((api_url, fallback_urls)) = api_urls.split_first() {
+            self.api_url = api_url.to_string();
+            self.client = self.client.with_fallback_urls(fallback_urls);
         }
-        Ok(())
+        self
     }
 
-    fn create_hmac_key(&self) -> Result<Hmac<Sha256>, String> {
-        Hmac::new_from_slice(self.secret.as_bytes()).map_err(|e| e.to_string())
+    // Fetches pages until one comes back short or reaches back past `start_time`
+    async fn fetch_transfer_pages(
+        &self,
+        params: &BTreeMap<&str, &str>,
+        endpoint_key: &str,
+        kind: TransferKind,
+        start_time: u64
+    ) -> Result<Vec<Transfer>, ExchangeError> {
+        let limit = TRANSFER_PAGE_SIZE.to_string();
+        let mut transfers = Vec::new();
+        for page_number in 1.. {
+            let page_number = page_number.to_string();
+            let mut page_params = params.clone();
+            page_params.insert("limit", &limit);
+            page_params.insert("page", &page_number);
+            let res = self.send_req_with_sign(page_params, endpoint_key).await?;
+            let page = parse_transfer_history(&res, kind)?;
+            let done = page.len() < TRANSFER_PAGE_SIZE
+                || page.iter().any(|transfer| transfer.timestamp < start_time);
+            transfers.extend(page);
+            if done {
+                break;
+            }
+        }
+        Ok(transfers)
     }
 
-    fn build_request<'a>(
-        &'a self,
-        method: &str,
-        uri: &str,
-        headers: Vec<(HeaderName, &str)>,
-        body: BTreeMap<&'a str, &'a str>
-    ) -> Result<Request<BTreeMap<&str, &str>>, String> {
-        let mut builder = Request::builder().method(method).uri(uri);
-        for (key, value) in headers {
-            builder = builder.header(key, value);
+    fn validate_api_credentials(api_key: &str, secret: &str) -> Result<(), String> {
+        if api_key.is_empty() {
+            return Err("API key cannot be empty".to_string());
+        }
+        if secret.is_empty() {
+            return Err("Secret cannot be empty".to_string());
         }
-        builder.body(body).map_err(|e| e.to_string())
+        Ok(())
     }
 
-    fn get_authorization_header(&self, param: BTreeMap<&str, &str>) -> Result<String, String> {
-        let query = get_query_string(param.clone());
+    // SHA512 hex digest of the query string, sent as the JWT's query_hash claim. The exchange
+    // hashes the query before it is percent-encoded, so the raw pairs are joined here.
+    pub(crate) fn get_query_hash(&self, param: &BTreeMap<&str, &str>) -> Result<String, String> {
+        let query = param
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
 
         let mut hasher = Sha512::new();
         hasher.update(query.as_bytes());
-        let query_hash = hex::encode(hasher.finalize());
+        Ok(hex::encode(hasher.finalize()))
+    }
 
+    // JWT signed with the secret. Every call gets a fresh nonce.
+    pub(crate) fn get_json(&self, query_hash: String) -> Result<String, String> {
         let nonce = Uuid::new_v4().to_string();
         let payload = BTreeMap::from([
             ("access_key", self.api_key.clone()),
@@ -70,10 +206,136 @@ impl Upbit {
             ("query_hash_alg", "SHA512".to_string()),
         ]);
 
-        let key = self.create_hmac_key()?;
-        let jwt_token = payload.sign_with_key(&key).map_err(|e| e.to_string())?;
+        let key = hmac_key::<Sha256>(&self.secret)?;
+        payload.sign_with_key(&key).map_err(|e| e.to_string())
+    }
+
+    fn get_authorization_header(&self, param: BTreeMap<&str, &str>) -> Result<String, String> {
+        let query_hash = self.get_query_hash(&param)?;
+        Ok(format!("Bearer {}", self.get_json(query_hash)?))
+    }
+
+    // Signed GET and DELETE endpoints take their parameters in the query string. The JWT's
+    // query_hash is computed over that same string.
+    pub(crate) fn build_signed_request<'a>(
+        &'a self,
+        param: BTreeMap<&'a str, &'a str>,
+        endpoint_key: &str
+    ) -> Result<Request<BTreeMap<&'a str, &'a str>>, String> {
+        let base = self
+            .get_end_point_with_key(endpoint_key)
+            .ok_or("Endpoint not found".to_string())?;
+        let authorization = self.get_authorization_header(param.clone())?;
+
+        let (uri, body) = if (base[0] == "GET" || base[0] == "DELETE") && !param.is_empty() {
+            (format!("{}{}?{}", self.api_url, base[1], get_query_string(param)), BTreeMap::new())
+        } else {
+            (format!("{}{}", self.api_url, base[1]), param)
+        };
+        Ok(build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(AUTHORIZATION, &authorization), (CONTENT_TYPE, "application/json")],
+            body
+        )?)
+    }
+
+    async fn send_order(&self, req: &Value) -> Result<Value, ExchangeError> {
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())?;
+        let (ord_type, price, volume) = get_order_shape(req)?;
+        // A market buy is sized by its quote_amount, so there is nothing to cross-check
+        if ord_type != "price" {
+            check_quote_amount(req)?;
+        }
+        let mut params = BTreeMap::from([
+            ("market", symbol.as_str()),
+            ("side", req["side"].as_str().unwrap_or_default()),
+            ("ord_type", ord_type),
+        ]);
+        if let Some(price) = price {
+            params.insert("price", price);
+        }
+        if let Some(volume) = volume {
+            params.insert("volume", volume);
+        }
+        // Orders are good till canceled unless `time_in_force` says otherwise
+        match get_time_in_force(req)? {
+            (_, true) => {
+                params.insert("time_in_force", "post_only");
+            }
+            (Some(TimeInForce::Ioc), _) => {
+                params.insert("time_in_force", "ioc");
+            }
+            (Some(TimeInForce::Fok), _) => {
+                params.insert("time_in_force", "fok");
+            }
+            _ => {}
+        }
+        let client_order_id = get_client_order_id(req, self.auto_client_order_id);
+        if let Some(client_order_id) = &client_order_id {
+            params.insert("identifier", client_order_id);
+        }
+
+        self.send_req_with_sign(params, "make_order").await
+    }
+
+    async fn send_cancel(&self, req: &Value) -> Result<Value, ExchangeError> {
+        let params = BTreeMap::from([get_order_id_param(req, "uuid", "identifier")?]);
+
+        self.send_req_with_sign(params, "cancel_order").await
+    }
+
+    // Raw order books of `markets`, a comma separated list
+    async fn fetch_order_books(&self, markets: &str) -> Result<Value, String> {
+        let params = BTreeMap::from([
+            ("markets", markets),
+            ("level", "0"),
+        ]);
+
+        let query_string = get_query_string(params);
+        let base = self
+            .get_end_point_with_key("order_book")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
+        fetch_order_book_json(&self.client, &base[0], &uri).await
+    }
+
+    async fn fetch_coin_list(&self) -> Result<CoinList, String> {
+        let params = BTreeMap::from([("isDetails", "false")]);
+
+        let query_string = get_query_string(params);
+        let base = self
+            .get_end_point_with_key("coin_list")
+            .ok_or("Endpoint not found".to_string())?;
+
+        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
+        let request = build_request(
+            base[0].as_str(),
+            &uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
+        let body = response.into_body();
+        let res = parse_json_body(&body)?;
+
+        // Parsing response to create CoinList struct
+        let market = "Upbit".to_string();
+        let coin_list = res
+            .as_array()
+            .ok_or("Response is not an array".to_string())?
+            .iter()
+            .filter_map(|coin| coin["market"].as_str().map(encode_symbol))
+            .collect::<Vec<String>>();
+
+        let coin_list_struct = CoinList {
+            market,
+            coin_list,
+        };
 
-        Ok(format!("Bearer {}", jwt_token))
+        Ok(coin_list_struct)
     }
 }
 
@@ -84,9 +346,23 @@ impl UpbitTrait for Upbit {
         let endpoint = BTreeMap::from([
             ("make_order".to_string(), ["POST".to_string(), "v1/orders".to_string()]),
             ("cancel_order".to_string(), ["DELETE".to_string(), "v1/order".to_string()]),
+            ("get_order".to_string(), ["GET".to_string(), "v1/order".to_string()]),
+            ("open_orders".to_string(), ["GET".to_string(), "v1/orders".to_string()]),
+            ("accounts".to_string(), ["GET".to_string(), "v1/accounts".to_string()]),
             ("order_book".to_string(), ["GET".to_string(), "v1/orderbook".to_string()]),
             ("current_price".to_string(), ["GET".to_string(), "v1/ticker".to_string()]),
             ("coin_list".to_string(), ["GET".to_string(), "v1/market/all".to_string()]),
+            ("tickers".to_string(), ["GET".to_string(), "v1/ticker/all".to_string()]),
+            ("recent_trades".to_string(), ["GET".to_string(), "v1/trades/ticks".to_string()]),
+            (
+                "deposit_address".to_string(),
+                ["GET".to_string(), "v1/deposits/coin_address".to_string()],
+            ),
+            ("order_chance".to_string(), ["GET".to_string(), "v1/orders/chance".to_string()]),
+            ("withdrawal_chance".to_string(), ["GET".to_string(), "v1/withdraws/chance".to_string()]),
+            ("withdraw".to_string(), ["POST".to_string(), "v1/withdraws/coin".to_string()]),
+            ("deposits".to_string(), ["GET".to_string(), "v1/deposits".to_string()]),
+            ("withdraws".to_string(), ["GET".to_string(), "v1/withdraws".to_string()]),
         ]);
 
         Ok(Self {
@@ -94,6 +370,13 @@ impl UpbitTrait for Upbit {
             api_key,
             secret,
             endpoint,
+            client: HttpClient::new(),
+            coin_list_cache: CoinListCache::default(),
+            min_order_notional_cache: TtlCache::default(),
+            fuzzy_symbols: false,
+            safe_mode: false,
+            auto_client_order_id: false,
+            min_notional_check: false,
         })
     }
 
@@ -113,80 +396,86 @@ impl UpbitTrait for Upbit {
         &self,
         param: BTreeMap<&str, &str>,
         endpoint_key: &str
-    ) -> Result<Value, String> {
-        let authorization = self.get_authorization_header(param.clone())?;
+    ) -> Result<Value, ExchangeError> {
+        check_safe_mode_endpoint(self.safe_mode, self.get_name(), endpoint_key)?;
+        let request = self.build_signed_request(param, endpoint_key)?;
 
-        let base = self
-            .get_end_point_with_key(endpoint_key)
-            .ok_or("Endpoint not found".to_string())?;
-
-        let uri = format!("{}{}", self.api_url, base[1]);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(AUTHORIZATION, &authorization), (CONTENT_TYPE, "application/json")],
-            param
-        )?;
-
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        from_slice(&body).map_err(|e| e.to_string())
+        let response = self.client.send(request).await?;
+        let res = parse_response(response, check_response)?;
+        check_response(&res)?;
+        Ok(res)
     }
 }
 
 #[async_trait]
 impl Exchange for Upbit {
     async fn place_order(&self, req: Value) -> Result<Value, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
-        let params = BTreeMap::from([
-            ("market", symbol.as_str()),
-            ("side", req["side"].as_str().unwrap_or_default()),
-            ("ord_type", req["order_type"].as_str().unwrap_or_default()),
-            ("price", req["price"].as_str().unwrap_or_default()),
-            ("volume", req["amount"].as_str().unwrap_or_default()),
-        ]);
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        with_request_timeout(&req, self.send_order(&req)).await.map_err(|e| e.to_string())
+    }
 
-        self.send_req_with_sign(params, "make_order").await
+    async fn place_order_typed(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "place_order_typed")?;
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        if self.min_notional_check {
+            let symbol = req["symbol"].as_str().unwrap_or_default();
+            check_min_notional(&req, self.min_order_notional(symbol).await?)?;
+        }
+        let res = with_request_timeout(&req, self.send_order(&req)).await?;
+        parse_order(&res)
     }
 
     async fn cancel_order(&self, req: Value) -> Result<Value, String> {
-        let params = BTreeMap::from([("uuid", req["order_id"].as_str().unwrap_or_default())]);
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order")?;
+        with_request_timeout(&req, self.send_cancel(&req)).await.map_err(|e| e.to_string())
+    }
 
-        self.send_req_with_sign(params, "cancel_order").await
+    // The canceled order comes back in the same shape as a placed one
+    async fn cancel_order_typed(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_order_typed")?;
+        let res = with_request_timeout(&req, self.send_cancel(&req)).await?;
+        parse_order(&res)
     }
 
     async fn get_order_book(&self, req: Value) -> Result<OrderBook, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
-        let params = BTreeMap::from([
-            ("markets", symbol.as_str()),
-            ("level", "0"),
-        ]);
-
-        let query_string = get_query_string(params);
-        let base = self
-            .get_end_point_with_key("order_book")
-            .ok_or("Endpoint not found".to_string())?;
-
-        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
-
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).unwrap();
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        let res = self.fetch_order_books(&symbol).await?;
         Ok(parse_orderbook(res)?)
     }
 
+    // The order book endpoint takes a comma separated list of markets, so every book comes
+    // from one request
+    async fn get_order_books(&self, symbols: &[&str]) -> Vec<Result<OrderBook, ExchangeError>> {
+        get_order_books_joined(symbols, "Upbit", |markets| async move {
+            self.fetch_order_books(&markets).await
+        }).await
+    }
+
     fn get_name(&self) -> String {
         "Upbit".to_string()
     }
 
+    fn supports(&self, method: &str) -> bool {
+        supports_method(IMPLEMENTED_METHODS, method)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_market_orders: true,
+            supports_websocket: self.supports("stream_bbo"),
+            supports_batch_orders: false,
+            requires_passphrase: false,
+            min_order_notional: Some("5000".to_string()),
+        }
+    }
+
     async fn get_current_price(&self, req: Value) -> Result<Price, String> {
-        let symbol = parse_symbol(req["symbol"].as_str().unwrap());
+        let req = resolve_symbol(self, self.fuzzy_symbols, req).await?;
+        let symbol = parse_symbol(req["symbol"].as_str().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
         let params = BTreeMap::from([
             ("markets", symbol.as_str()),
             ("level", "0"),
@@ -198,71 +487,322 @@ impl Exchange for Upbit {
             .ok_or("Endpoint not found".to_string())?;
 
         let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
+        let request = build_request(
             base[0].as_str(),
             &uri,
             vec![(ACCEPT, "application/json")],
             BTreeMap::new()
         )?;
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
+        let response = self.client.send(request).await.map_err(|e| e.to_string())?;
         let body = response.into_body();
-        let res: Value = from_slice(&body).unwrap();
+        let res = parse_json_body(&body)?;
 
         // Parsing response to create Price struct
         let symbol_name = req["symbol"].as_str().unwrap().to_string();
-        let current_price = res[0]["trade_price"].as_f64().unwrap_or(0.0).to_string();
+        let current_price = get_number_string(&res[0]["trade_price"])
+            .unwrap_or_else(|| "0".to_string());
 
         let price = Price {
             exchange: "Upbit".to_string(),
             symbol: symbol_name,
             price: current_price,
+            source: PriceSource::Last,
         };
 
         Ok(price)
     }
 
-    async fn get_coin_list(&self) -> Result<CoinList, String> {
-        let params = BTreeMap::from([("isDetails", "false")]);
+    // The order book has no depth parameter, but it is only 15 levels deep
+    async fn get_best_quote(&self, symbol: &str) -> Result<Quote, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("markets", market.as_str())]);
 
-        let query_string = get_query_string(params);
-        let base = self
-            .get_end_point_with_key("coin_list")
-            .ok_or("Endpoint not found".to_string())?;
+        let res = self.send_public_req(params, "order_book").await?;
+        check_response(&res)?;
+        parse_quote(&res)
+    }
 
-        let uri = format!("{}{}?{}", self.api_url, base[1], query_string);
-        let request = self.build_request(
-            base[0].as_str(),
-            &uri,
-            vec![(ACCEPT, "application/json")],
-            BTreeMap::new()
-        )?;
+    async fn get_ticker(&self, symbol: &str) -> Result<Ticker, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("markets", market.as_str())]);
 
-        let response = send(request).await.map_err(|e| e.to_string())?;
-        let body = response.into_body();
-        let res: Value = from_slice(&body).unwrap();
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res)?;
+        parse_ticker(&res, symbol)
+    }
 
-        // Parsing response to create CoinList struct
-        let market = "Upbit".to_string();
-        let coin_list = res
-            .as_array()
-            .ok_or("Response is not an array".to_string())?
+    async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>
+    ) -> Result<Vec<Trade>, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let count = limit.map(|limit| limit.to_string());
+        let mut params = BTreeMap::from([("market", market.as_str())]);
+        if let Some(count) = count.as_deref() {
+            params.insert("count", count);
+        }
+
+        let res = self.send_public_req(params, "recent_trades").await?;
+        check_response(&res)?;
+        parse_recent_trades(&res, symbol, "Upbit")
+    }
+
+    // The ticker endpoint takes a comma separated list of markets
+    async fn get_prices(&self, symbols: &[&str]) -> Result<Vec<Price>, ExchangeError> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        let markets = symbols
             .iter()
-            .filter_map(|coin| coin["market"].as_str().map(|s| encode_symbol(s)))
-            .collect::<Vec<String>>();
+            .map(|symbol| parse_symbol(symbol))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(",");
+        let params = BTreeMap::from([("markets", markets.as_str())]);
 
-        let coin_list_struct = CoinList {
-            market,
-            coin_list,
-        };
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res)?;
+        parse_prices(&res, symbols, "Upbit")
+    }
 
-        Ok(coin_list_struct)
+    async fn get_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.get_or_fetch(self.get_name(), || self.fetch_coin_list()).await
+    }
+
+    async fn refresh_coin_list(&self) -> Result<CoinList, String> {
+        self.coin_list_cache.refresh(self.get_name(), || self.fetch_coin_list()).await
+    }
+
+    async fn top_markets_by_volume(
+        &self,
+        quote: &str,
+        n: usize
+    ) -> Result<Vec<Market>, ExchangeError> {
+        let params = BTreeMap::from([("quote_currencies", quote)]);
+        let res = self.send_public_req(params, "tickers").await?;
+
+        let markets = parse_markets(&res, quote)?;
+        Ok(top_by_volume(markets, n))
+    }
+
+    async fn get_deposit_address(&self, req: Value) -> Result<DepositAddress, ExchangeError> {
+        let currency = req["currency"].as_str().unwrap_or_default();
+        let params = BTreeMap::from([
+            ("currency", currency),
+            ("net_type", req["network"].as_str().unwrap_or(currency)),
+        ]);
+
+        let res = self.send_req_with_sign(params, "deposit_address").await?;
+        parse_deposit_address(&res)
+    }
+
+    // Upbit has no native amend, so the order is canceled and placed again
+    async fn amend_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "amend_order")?;
+        cancel_then_place(self, req, parse_order).await
+    }
+
+    // There is no dedicated ping endpoint, so a single ticker is fetched instead
+    async fn ping(&self) -> Result<(), ExchangeError> {
+        let params = BTreeMap::from([("markets", "KRW-BTC")]);
+        let res = self.send_public_req(params, "current_price").await?;
+        check_response(&res)
+    }
+
+    async fn get_order(&self, req: Value) -> Result<Order, ExchangeError> {
+        let params = BTreeMap::from([get_order_id_param(&req, "uuid", "identifier")?]);
+
+        let res = self.send_req_with_sign(params, "get_order").await?;
+        parse_order(&res)
+    }
+
+    async fn check_auth(&self) -> Result<(), ExchangeError> {
+        self.send_req_with_sign(BTreeMap::new(), "accounts").await?;
+        Ok(())
+    }
+
+    // Upbit only has a spot wallet
+    async fn get_all_balances(&self) -> Result<Vec<Balance>, ExchangeError> {
+        let res = self.send_req_with_sign(BTreeMap::new(), "accounts").await?;
+        parse_balances(&res)
+    }
+
+    // Upbit does not report a key's permissions, only whether it can read the account, and
+    // has neither fee tiers nor sub-accounts
+    async fn get_account_info(&self) -> Result<AccountInfo, ExchangeError> {
+        self.send_req_with_sign(BTreeMap::new(), "accounts").await?;
+        Ok(AccountInfo {
+            exchange: "Upbit".to_string(),
+            can_trade: None,
+            can_withdraw: None,
+            fee_tier: None,
+            is_sub_account: Some(false),
+        })
+    }
+
+    async fn cancel_all_orders(&self, req: Value) -> Result<Vec<String>, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "cancel_all_orders")?;
+        with_request_timeout::<_, ExchangeError, _>(&req, async {
+            // There is no batch cancel endpoint, so every open order is canceled individually
+            let symbol = req["symbol"].as_str().map(parse_symbol).transpose()?;
+            let mut params = BTreeMap::from([("state", "wait")]);
+            if let Some(symbol) = &symbol {
+                params.insert("market", symbol.as_str());
+            }
+
+            let open_orders = self.send_req_with_sign(params, "open_orders").await?;
+            let cancels = open_orders
+                .as_array()
+                .ok_or(ExchangeError::Parse(open_orders.to_string()))?
+                .iter()
+                .map(|order| {
+                    let order_id = order["uuid"].as_str().unwrap_or_default();
+                    (order_id.to_string(), self.cancel_order(json!({ "order_id": order_id })))
+                })
+                .collect();
+            cancel_each(cancels).await
+        }).await
+    }
+
+    async fn get_withdrawal_fee(
+        &self,
+        currency: &str,
+        network: &str
+    ) -> Result<WithdrawalFee, ExchangeError> {
+        let params = BTreeMap::from([
+            ("currency", currency),
+            ("net_type", network),
+        ]);
+
+        let res = self.send_req_with_sign(params, "withdrawal_chance").await?;
+        parse_withdrawal_fee(&res)
+    }
+
+    // Order chance reports the minimum order value of the market's bid and ask sides
+    // Cached per market, so checked orders do not each cost a lookup
+    async fn min_order_notional(&self, symbol: &str) -> Result<Decimal, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        self.min_order_notional_cache.get_or_fetch(market.clone(), || async {
+            let params = BTreeMap::from([("market", market.as_str())]);
+
+            let res = self.send_req_with_sign(params, "order_chance").await?;
+            parse_min_order_notional(&res)
+        }).await
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<FeeSchedule, ExchangeError> {
+        let market = parse_symbol(symbol)?;
+        let params = BTreeMap::from([("market", market.as_str())]);
+
+        let res = self.send_req_with_sign(params, "order_chance").await?;
+        parse_trading_fees(&res, symbol)
+    }
+
+    async fn withdraw(&self, req: Value) -> Result<Value, ExchangeError> {
+        check_safe_mode(self.safe_mode, self.get_name(), "withdraw")?;
+        let currency = req["currency"].as_str().unwrap_or_default();
+        let network = req["network"].as_str().unwrap_or(currency);
+        let amount = req["amount"].as_str().unwrap_or_default();
+        self.get_withdrawal_fee(currency, network).await?.check_amount(amount)?;
+
+        let mut params = BTreeMap::from([
+            ("currency", currency),
+            ("net_type", network),
+            ("amount", amount),
+            ("address", req["address"].as_str().unwrap_or_default()),
+        ]);
+        if let Some(memo) = req["memo"].as_str() {
+            params.insert("secondary_address", memo);
+        }
+
+        Ok(self.send_req_with_sign(params, "withdraw").await?)
+    }
+
+    // Upbit has no time filter on these lists, so each is paged back, newest first, until a
+    // page reaches past `start_time`, and the range is applied to what was fetched
+    async fn get_transfer_history(&self, req: Value) -> Result<Vec<Transfer>, ExchangeError> {
+        let start_time = req["start_time"].as_u64().unwrap_or(0);
+        let mut params = BTreeMap::new();
+        if let Some(currency) = req["currency"].as_str() {
+            params.insert("currency", currency);
+        }
+
+        let mut transfers = self.fetch_transfer_pages(
+            &params,
+            "deposits",
+            TransferKind::Deposit,
+            start_time
+        ).await?;
+        transfers.extend(
+            self.fetch_transfer_pages(&params, "withdraws", TransferKind::Withdrawal, start_time).await?
+        );
+        Ok(filter_transfers(transfers, &req))
+    }
+}
+
+// Upbit's (ord_type, price, volume) for the order. Limit orders take both price and volume.
+// A market buy is an `ord_type=price` order for the total to spend in the quote currency,
+// taken from `quote_amount`, or from `price` when the caller uses Upbit's own `price` order
+// type. A market sell is an `ord_type=market` order for `volume`. The other param must be
+// left out.
+fn get_order_shape(
+    req: &Value
+) -> Result<(&'static str, Option<&str>, Option<&str>), ExchangeError> {
+    let side = Side::from_exchange_str(req["side"].as_str().unwrap_or_default())?;
+    let order_type = req["order_type"].as_str().unwrap_or_default().to_ascii_lowercase();
+    let require = |key: &str, message: &str| {
+        req[key]
+            .as_str()
+            .filter(|value| !value.is_empty())
+            .ok_or(ExchangeError::InvalidRequest(message.to_string()))
+    };
+
+    match (order_type.as_str(), side) {
+        ("limit", _) => {
+            let price = require("price", "A limit order needs a price")?;
+            let volume = require("amount", "A limit order needs an amount")?;
+            Ok(("limit", Some(price), Some(volume)))
+        }
+        ("market", Side::Buy) => {
+            if require("price", "").is_ok() || require("amount", "").is_ok() {
+                return Err(
+                    ExchangeError::InvalidRequest(
+                        "A market buy is sized by quote_amount, not price or amount".to_string()
+                    )
+                );
+            }
+            let message =
+                "A market buy needs a quote_amount, the total to spend in the quote currency";
+            Ok(("price", Some(require("quote_amount", message)?), None))
+        }
+        ("price", Side::Buy) => {
+            if require("amount", "").is_ok() {
+                return Err(
+                    ExchangeError::InvalidRequest(
+                        "A price order is sized by its price, not amount".to_string()
+                    )
+                );
+            }
+            let message = "A price order needs a price, the total to spend in the quote currency";
+            Ok(("price", Some(require("price", message)?), None))
+        }
+        ("market", Side::Sell) => {
+            Ok(("market", None, Some(require("amount", "A market sell needs an amount")?)))
+        }
+        _ => {
+            Err(
+                ExchangeError::InvalidRequest(
+                    format!("Unsupported Upbit order type {:?} for a {:?} order", order_type, side)
+                )
+            )
+        }
     }
 }
 
-fn parse_symbol(symbol: &str) -> String {
-    let v: Vec<&str> = symbol.split("/").collect();
-    format!("{}-{}", v[1], v[0])
+pub(crate) fn parse_symbol(symbol: &str) -> Result<String, ExchangeError> {
+    let (base, quote) = split_symbol(symbol)?;
+    Ok(format!("{}-{}", quote, base))
 }
 
 fn encode_symbol(symbol: &str) -> String {
@@ -270,35 +810,370 @@ fn encode_symbol(symbol: &str) -> String {
     format!("{}/{}", v[1], v[0])
 }
 
-fn parse_order(order_res: Value) -> Value {
-    todo!()
+// Upbit has no bid/ask on the ticker, and its high/low are for the day since 00:00 KST
+pub(crate) fn parse_ticker(res: &Value, symbol: &str) -> Result<Ticker, ExchangeError> {
+    let ticker = &res[0];
+    let number = |field: &str| get_number_string(&ticker[field]);
+    let last = number("trade_price")
+        .ok_or(ExchangeError::Parse(format!("trade_price field is missing: {}", res)))?;
+
+    Ok(Ticker {
+        exchange: "Upbit".to_string(),
+        symbol: symbol.to_string(),
+        last,
+        bid: None,
+        ask: None,
+        high_24h: number("high_price"),
+        low_24h: number("low_price"),
+        volume_24h: number("acc_trade_volume_24h"),
+        change_24h: number("signed_change_price"),
+    })
+}
+
+// `ask_bid` is the side of the order that took liquidity. Shared with Bithumb, whose trades
+// come in the same shape.
+pub(crate) fn parse_recent_trades(
+    res: &Value,
+    symbol: &str,
+    exchange: &str
+) -> Result<Vec<Trade>, ExchangeError> {
+    let trades = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Unexpected trades response: {}", res)))?;
+
+    trades
+        .iter()
+        .map(|trade| {
+            let number = |name: &str| {
+                get_number_string(&trade[name])
+                    .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", name, trade)))
+            };
+            let ask_bid = trade["ask_bid"].as_str().unwrap_or_default().to_lowercase();
+            Ok(Trade {
+                exchange: exchange.to_string(),
+                symbol: symbol.to_string(),
+                trade_id: number("sequential_id")?,
+                price: number("trade_price")?,
+                size: number("trade_volume")?,
+                side: Side::from_exchange_str(&ask_bid)?,
+                timestamp: trade["timestamp"]
+                    .as_u64()
+                    .ok_or(ExchangeError::Parse(format!("timestamp field is missing: {}", trade)))?,
+                fee: String::new(),
+            })
+        })
+        .collect()
+}
+
+// Shared with Bithumb, whose tickers come in the same shape
+pub(crate) fn parse_prices(
+    res: &Value,
+    symbols: &[&str],
+    exchange: &str
+) -> Result<Vec<Price>, ExchangeError> {
+    let tickers = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Unexpected tickers response: {}", res)))?;
+
+    symbols
+        .iter()
+        .map(|symbol| {
+            let market = parse_symbol(symbol)?;
+            let ticker = tickers
+                .iter()
+                .find(|ticker| ticker["market"].as_str() == Some(market.as_str()))
+                .ok_or(ExchangeError::Parse(format!("{} is missing from the tickers", market)))?;
+            Ok(Price {
+                exchange: exchange.to_string(),
+                symbol: symbol.to_string(),
+                price: get_number_string(&ticker["trade_price"])
+                    .unwrap_or_else(|| "0".to_string()),
+                source: PriceSource::Last,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn parse_min_order_notional(res: &Value) -> Result<Decimal, ExchangeError> {
+    let min_total = |side: &str| res["market"][side]["min_total"].as_str().and_then(parse_decimal);
+    min_total("bid")
+        .max(min_total("ask"))
+        .ok_or(ExchangeError::Parse(format!("min_total field is missing: {}", res)))
+}
+
+fn parse_order(order_res: &Value) -> Result<Order, ExchangeError> {
+    if let Some(error) = order_res.get("error") {
+        return Err(ExchangeError::Request(error["message"].as_str().unwrap_or_default().to_string()));
+    }
+    let ord_id = order_res["uuid"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("uuid field is missing: {}", order_res)))?;
+    let market = order_res["market"].as_str().unwrap_or_default();
+    let field = |key: &str| order_res[key].as_str().unwrap_or_default().to_string();
+    let raw_state = field("state");
+    let executed = field("executed_volume").parse::<f64>().unwrap_or_default();
+    let state = match OrderState::from_exchange_str(&raw_state) {
+        OrderState::New if executed > 0.0 => OrderState::PartiallyFilled,
+        state => state,
+    };
+
+    Ok(Order {
+        exchange: "Upbit".to_string(),
+        ord_id: ord_id.to_string(),
+        side: Side::from_exchange_str(order_res["side"].as_str().unwrap_or_default())?,
+        ord_type: field("ord_type"),
+        price: field("price"),
+        state,
+        raw_state,
+        market: encode_symbol(market),
+        volume: field("volume"),
+        create_at: field("created_at"),
+        amount: field("executed_volume"),
+        fee: field("paid_fee"),
+        // Fees are charged in the quote currency, e.g. KRW for KRW-BTC
+        fee_currency: market.split('-').next().unwrap_or_default().to_string(),
+        raw: order_res.clone(),
+    })
+}
+
+
+// Upbit quotes bid and ask fees separately; the bid side is used as the taker rate and the
+// maker rate falls back to it when no maker discount is listed
+pub(crate) fn parse_trading_fees(res: &Value, symbol: &str) -> Result<FeeSchedule, ExchangeError> {
+    let taker = res["bid_fee"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("bid_fee field is missing: {}", res)))?;
+
+    Ok(FeeSchedule {
+        exchange: "Upbit".to_string(),
+        symbol: symbol.to_string(),
+        maker: res["maker_bid_fee"].as_str().unwrap_or(taker).to_string(),
+        taker: taker.to_string(),
+    })
+}
+
+pub(crate) fn parse_withdrawal_fee(res: &Value) -> Result<WithdrawalFee, ExchangeError> {
+    let fee = res["currency"]["withdraw_fee"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("withdraw_fee field is missing: {}", res)))?;
+
+    Ok(WithdrawalFee {
+        fee: fee.to_string(),
+        min_withdrawal: res["withdraw_limit"]["minimum"].as_str().unwrap_or("0").to_string(),
+    })
+}
+
+pub(crate) fn parse_deposit_address(res: &Value) -> Result<DepositAddress, ExchangeError> {
+    let address = res["deposit_address"]
+        .as_str()
+        .ok_or(ExchangeError::Parse(format!("deposit_address field is missing: {}", res)))?;
+
+    // Upbit returns the memo of XRP/XLM style coins as `secondary_address`
+    Ok(DepositAddress {
+        currency: res["currency"].as_str().unwrap_or_default().to_string(),
+        network: res["net_type"].as_str().unwrap_or_default().to_string(),
+        address: address.to_string(),
+        memo: get_optional_str(&res["secondary_address"]),
+    })
+}
+
+// Deposit and withdrawal records share one shape, without the address either was sent to
+pub(crate) fn parse_transfer_history(
+    res: &Value,
+    kind: TransferKind
+) -> Result<Vec<Transfer>, ExchangeError> {
+    check_response(res)?;
+    let records = res.as_array().ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
+
+    records
+        .iter()
+        .map(|record| {
+            let status = match record["state"].as_str().unwrap_or_default() {
+                "DONE" | "ACCEPTED" => TransferStatus::Completed,
+                "FAILED" | "REJECTED" | "REFUNDED" => TransferStatus::Failed,
+                "CANCELED" | "CANCELLED" => TransferStatus::Canceled,
+                _ => TransferStatus::Pending,
+            };
+            let timestamp = record["created_at"]
+                .as_str()
+                .and_then(parse_datetime_millis)
+                .ok_or(ExchangeError::Parse(format!("Invalid created_at: {}", record)))?;
+            Ok(Transfer {
+                kind,
+                currency: record["currency"].as_str().unwrap_or_default().to_string(),
+                amount: record["amount"].as_str().unwrap_or_default().to_string(),
+                fee: get_optional_str(&record["fee"]).unwrap_or_else(|| "0".to_string()),
+                tx_id: get_optional_str(&record["txid"]),
+                address: None,
+                status,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+// Errors come back as `{ "error": { "name", "message" } }`
+fn check_response(res: &Value) -> Result<(), ExchangeError> {
+    let Some(error) = res.get("error") else {
+        return Ok(());
+    };
+
+    let name = error["name"].as_str().unwrap_or_default();
+    let message = error["message"].as_str().unwrap_or_default().to_string();
+    match name {
+        "invalid_query_payload"
+        | "jwt_verification"
+        | "expired_access_key"
+        | "nonce_used"
+        | "no_authorization_ip"
+        | "no_authorization_token"
+        | "invalid_access_key"
+        | "out_of_scope" => Err(ExchangeError::Auth(format!("{}: {}", name, message))),
+        _ => Err(ExchangeError::ExchangeRejected { code: name.to_string(), message }),
+    }
+}
+
+fn parse_balances(res: &Value) -> Result<Vec<Balance>, ExchangeError> {
+    let accounts = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
+
+    Ok(
+        accounts
+            .iter()
+            .map(|account| Balance {
+                exchange: "Upbit".to_string(),
+                wallet: Wallet::Spot,
+                currency: account["currency"].as_str().unwrap_or_default().to_string(),
+                free: account["balance"].as_str().unwrap_or("0").to_string(),
+                locked: account["locked"].as_str().unwrap_or("0").to_string(),
+            })
+            .collect()
+    )
+}
+
+// Delisted or suspended markets report a `market_state` other than ACTIVE
+pub(crate) fn parse_markets(res: &Value, quote: &str) -> Result<Vec<Market>, ExchangeError> {
+    let tickers = res
+        .as_array()
+        .ok_or(ExchangeError::Parse(format!("Not an array: {}", res)))?;
+
+    Ok(
+        tickers
+            .iter()
+            .filter(|ticker| ticker["market_state"].as_str().is_none_or(|state| state == "ACTIVE"))
+            .filter_map(|ticker| {
+                let (ticker_quote, base) = ticker["market"].as_str()?.split_once('-')?;
+                (ticker_quote == quote).then(|| Market {
+                    exchange: "Upbit".to_string(),
+                    symbol: format!("{}/{}", base, quote),
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                    last_price: ticker["trade_price"].to_string(),
+                    quote_volume: ticker["acc_trade_price_24h"].to_string(),
+                })
+            })
+            .collect()
+    )
+}
+
+pub(crate) fn parse_quote(res: &Value) -> Result<Quote, ExchangeError> {
+    let unit = &res[0]["orderbook_units"][0];
+    let field = |name: &str| {
+        get_number_string(&unit[name])
+            .ok_or(ExchangeError::Parse(format!("{} field is missing: {}", name, res)))
+    };
+
+    Ok(Quote {
+        bid: field("bid_price")?,
+        bid_size: field("bid_size")?,
+        ask: field("ask_price")?,
+        ask_size: field("ask_size")?,
+    })
 }
 
 fn parse_orderbook(orderbook_res: Value) -> Result<OrderBook, String> {
+    parse_orderbook_entry(&orderbook_res[0], "Upbit")
+}
+
+// Unsigned order book request, sent again if the body is unreadable
+pub(crate) async fn fetch_order_book_json(
+    client: &HttpClient,
+    method: &str,
+    uri: &str
+) -> Result<Value, String> {
+    fetch_json_retrying(|| async {
+        let request = build_request(
+            method,
+            uri,
+            vec![(ACCEPT, "application/json")],
+            BTreeMap::new()
+        )?;
+        let response = client.send(request).await.map_err(|e| e.to_string())?;
+        Ok(response.into_body())
+    }).await
+}
+
+// Order books of every symbol from one `fetch` of the comma separated markets, in the order
+// of `symbols`. A symbol that does not parse or is missing from the response fails on its own.
+pub(crate) async fn get_order_books_joined<F, Fut>(
+    symbols: &[&str],
+    exchange: &str,
+    fetch: F
+) -> Vec<Result<OrderBook, ExchangeError>>
+    where F: FnOnce(String) -> Fut, Fut: Future<Output = Result<Value, String>>
+{
+    let markets = symbols.iter().map(|symbol| parse_symbol(symbol)).collect::<Vec<_>>();
+    let joined = markets.iter().flatten().cloned().collect::<Vec<String>>().join(",");
+    let res = if joined.is_empty() {
+        Ok(Value::Array(Vec::new()))
+    } else {
+        fetch(joined)
+            .await
+            .map_err(ExchangeError::from)
+            .and_then(|res| check_response(&res).map(|_| res))
+    };
+
+    markets
+        .into_iter()
+        .map(|market| {
+            let market = market?;
+            let res = res.as_ref().map_err(Clone::clone)?;
+            let book = res
+                .as_array()
+                .and_then(|books| books.iter().find(|book| book["market"] == market.as_str()))
+                .ok_or(ExchangeError::Parse(format!("No order book for {}: {}", market, res)))?;
+            Ok(parse_orderbook_entry(book, exchange)?)
+        })
+        .collect()
+}
+
+// One market's entry of the order book response
+pub(crate) fn parse_orderbook_entry(book: &Value, exchange: &str) -> Result<OrderBook, String> {
     // Extract and convert the orderbook_units
-    let orderbook_units = orderbook_res[0]["orderbook_units"]
+    let orderbook_units = book["orderbook_units"]
         .as_array()
         .ok_or("orderbook_units field is not an array")?
         .iter()
         .map(|unit| {
-            let ask_price = unit["ask_price"].as_f64().unwrap_or(0.0).to_string();
-            let bid_price = unit["bid_price"].as_f64().unwrap_or(0.0).to_string();
-            let ask_size = unit["ask_size"].as_f64().unwrap_or(0.0).to_string();
-            let bid_size = unit["bid_size"].as_f64().unwrap_or(0.0).to_string();
+            let number = |field: &str| {
+                get_number_string(&unit[field]).unwrap_or_else(|| "0".to_string())
+            };
             OrderBookUnit {
-                ask_price,
-                bid_price,
-                ask_size,
-                bid_size,
+                ask_price: number("ask_price"),
+                bid_price: number("bid_price"),
+                ask_size: number("ask_size"),
+                bid_size: number("bid_size"),
             }
         })
         .collect::<Vec<OrderBookUnit>>();
 
     // Create and return the OrderBook struct
-    let symbol = encode_symbol(orderbook_res[0]["market"].as_str().unwrap_or_default());
+    let symbol = encode_symbol(book["market"].as_str().unwrap_or_default());
     Ok(OrderBook {
         market: symbol,
-        exchange: "Upbit".to_string(),
+        exchange: exchange.to_string(),
         orderbook_unit: orderbook_units,
+        timestamp: book["timestamp"].as_u64(),
     })
 }